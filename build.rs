@@ -1,4 +1,4 @@
-use std::{path, process};
+use std::{env, fs, io, path, process};
 
 fn main() {
     // Install JavaScript dependencies.
@@ -24,6 +24,10 @@ fn main() {
 
     println!("cargo::rerun-if-changed=frontend");
 
+    // Generate TypeScript definitions for the API models.
+    generate_typescript_definitions(&frontend_dir.join("src/generated"))
+        .expect("failed to generate TypeScript definitions");
+
     // Build Tailwind CSS.
     let status = process::Command::new("npx")
         .args([
@@ -45,6 +49,19 @@ fn main() {
         _ => (),
     };
 
+    // Check types, since Vite only removes them without checking.
+    let status = process::Command::new("npx")
+        .args(["tsc", "--noEmit"])
+        .current_dir("./frontend")
+        .status()
+        .expect("failed to check frontend types");
+
+    match status.code() {
+        Some(code) if code != 0 => panic!("failed to check frontend types (exit code: {code})"),
+        None => panic!("failed to check frontend types: process terminated via signal"),
+        _ => (),
+    };
+
     // Build frontend.
     let status = process::Command::new("npx")
         .args(["vite", "build", "./frontend"])
@@ -57,3 +74,37 @@ fn main() {
         _ => (),
     };
 }
+
+/// Export the TypeScript definitions of the API models to `directory`.
+///
+/// The definitions are exported to a temporary directory first, and only the
+/// files that changed are copied over, so unchanged files are left untouched.
+fn generate_typescript_definitions(directory: &path::Path) -> io::Result<()> {
+    let out_dir = path::PathBuf::from(env::var("OUT_DIR").unwrap()).join("generated");
+    if out_dir.exists() {
+        fs::remove_dir_all(&out_dir)?;
+    }
+    models::export_typescript(&out_dir).map_err(io::Error::other)?;
+
+    fs::create_dir_all(directory)?;
+
+    // Remove the definitions of models that no longer exist.
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        if !out_dir.join(entry.file_name()).exists() {
+            fs::remove_file(entry.path())?;
+        }
+    }
+
+    for entry in fs::read_dir(&out_dir)? {
+        let entry = entry?;
+        let contents = fs::read(entry.path())?;
+        let path = directory.join(entry.file_name());
+
+        if fs::read(&path).ok().as_ref() != Some(&contents) {
+            fs::write(path, contents)?;
+        }
+    }
+
+    Ok(())
+}
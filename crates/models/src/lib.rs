@@ -0,0 +1,800 @@
+//! Defines the objects returned by the API.
+//!
+//! The models live in their own crate so the build script of the backend can
+//! use them to generate TypeScript definitions for the frontend (see
+//! [`export_typescript`]).
+
+use std::{fmt, path::Path};
+
+use serde::{Deserialize, Serialize};
+use ts_rs::{ExportError, TS};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, TS)]
+pub struct ExpenseSheet {}
+
+/// The body of every error response, as described by RFC 7807 (problem
+/// details for HTTP APIs).
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct Problem {
+    /// Always `about:blank`; the kind of error is identified by `code`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// The reason phrase of the status code (e.g., `Not Found`).
+    pub title: String,
+    pub status: u16,
+    /// A description of the error, meant to be read by people.
+    pub detail: String,
+    /// Identifies the kind of error (e.g., `property.not_found`); codes do
+    /// not change, so clients can rely on them instead of `detail`.
+    pub code: String,
+    /// The ID of the request, also returned in the `X-Request-Id` header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub request_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct User {
+    pub id: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub email_addresses: Vec<EmailAddress>,
+    pub phone_numbers: Vec<PhoneNumber>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct EmailAddress {
+    pub email_address: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct PhoneNumber {
+    pub phone_numbers: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct Property {
+    pub id: String,
+    /// The ID of the user that owns the property.
+    pub user_id: String,
+    pub name: String,
+    pub address: Option<String>,
+    /// The management fee agreements between the owner and Bojano Homes.
+    #[serde(default)]
+    pub fee_agreements: Vec<FeeAgreement>,
+    #[serde(default)]
+    pub expense_matching: ExpenseMatching,
+}
+
+/// How rows of the shared expense sheet are matched to a property.
+///
+/// Rows with a property code only match the property with that code (or ID);
+/// older rows without one are matched by the name in the `Property` column,
+/// ignoring case.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, TS)]
+pub struct ExpenseMatching {
+    /// The code entered in the `Property Code` column (e.g., `LAKE-01`); it
+    /// does not change when the property is renamed.
+    pub code: Option<String>,
+    /// Other names the property was entered as (e.g., its name before it was
+    /// renamed, or a common misspelling).
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+/// How the management fee is calculated.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeeRule {
+    /// A percentage of each reservation's revenue (e.g., `0.2` for 20%).
+    PercentOfRevenue { rate: f32 },
+    /// A percentage of each reservation's revenue after the cleaning fee.
+    PercentOfRevenueMinusCleaning { rate: f32, cleaning_fee: f32 },
+    /// A fixed amount every month, regardless of revenue.
+    FlatMonthly { amount: f32 },
+}
+
+/// A management fee rule, and the period of time it applies to.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, TS)]
+pub struct FeeAgreement {
+    pub rule: FeeRule,
+    /// The first day the agreement applies to.
+    pub effective_from: chrono::NaiveDate,
+    /// The last day the agreement applies to; `None` if it has not ended.
+    pub effective_until: Option<chrono::NaiveDate>,
+}
+
+/// The management fee for a single reservation.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct ReservationFee {
+    /// The name of the sheet (tab) the reservation was read from.
+    pub sheet: String,
+    /// The row number of the reservation within its sheet (starting at 1).
+    pub row: u32,
+    pub check_in: chrono::NaiveDateTime,
+    pub revenue: f32,
+    /// The management fee entered in the spreadsheet.
+    pub actual: f32,
+    /// The management fee calculated from the fee agreement, if the agreement
+    /// charges per reservation.
+    pub expected: Option<f32>,
+    pub mismatch: bool,
+}
+
+/// Compares the management fees in the spreadsheet with the fee agreement.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct FeeReport {
+    pub year: i32,
+    pub month: u8,
+    /// The agreement in effect for the month, if any.
+    pub agreement: Option<FeeAgreement>,
+    pub reservations: Vec<ReservationFee>,
+    /// The total management fee entered in the spreadsheet.
+    pub actual: f32,
+    /// The total management fee calculated from the fee agreement.
+    pub expected: Option<f32>,
+    pub mismatch: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct Expense {
+    pub amount: f32,
+    pub description: String,
+    pub timestamp: chrono::NaiveDateTime,
+    pub receipt_link: String,
+    pub merchant: String,
+    pub buyers_name: String,
+    /// The category of the expense (e.g., "Supplies"); empty if not set.
+    pub category: String,
+    /// The row number of the expense within the expense sheet (starting at 1).
+    pub row: u32,
+}
+
+/// A row of the shared expense sheet that does not belong to any property.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct UnmatchedExpense {
+    /// The row number within the expense sheet (starting at 1).
+    pub row: u32,
+    /// The property, as entered in the `Property` column.
+    pub property: String,
+    /// The property code, as entered in the `Property Code` column.
+    pub property_code: Option<String>,
+    pub amount: Option<f32>,
+    pub description: String,
+    pub timestamp: Option<chrono::NaiveDateTime>,
+}
+
+/// Every row of a year's expense sheet that does not belong to any property.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct UnmatchedExpenseReport {
+    pub year: i32,
+    pub expenses: Vec<UnmatchedExpense>,
+}
+
+/// How often a budget resets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetPeriod {
+    Monthly,
+    Annual,
+}
+
+/// A limit on how much can be spent on a property.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, TS)]
+pub struct Budget {
+    pub id: String,
+    pub property_id: String,
+    /// The category of expenses the budget applies to; `None` applies the
+    /// budget to every expense.
+    pub category: Option<String>,
+    pub period: BudgetPeriod,
+    pub amount: f32,
+    /// The fractions of the budget (e.g., `0.8` for 80%) that trigger an
+    /// alert when spending reaches them.
+    pub thresholds: Vec<f32>,
+}
+
+/// The information required to create a new budget.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct NewBudget {
+    pub category: Option<String>,
+    pub period: BudgetPeriod,
+    pub amount: f32,
+    /// Defaults to 80% and 100% if not provided.
+    pub thresholds: Option<Vec<f32>>,
+}
+
+/// How much of a budget has been spent.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct BudgetStatus {
+    pub budget: Budget,
+    pub spent: f32,
+    pub remaining: f32,
+    /// The fraction of the budget that has been spent (e.g., `1.5` is 150%).
+    pub utilization: f32,
+    pub over_budget: bool,
+    /// The thresholds that spending has reached.
+    pub thresholds_reached: Vec<f32>,
+}
+
+/// Triggered the first time spending reaches one of a budget's thresholds.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, TS)]
+pub struct BudgetAlert {
+    pub budget_id: String,
+    pub property_id: String,
+    pub category: Option<String>,
+    /// The period the alert was triggered for (e.g., `2025-03` or `2025`).
+    pub period: String,
+    pub threshold: f32,
+    pub spent: f32,
+    pub amount: f32,
+    pub triggered_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Compares a property's expenses with its budgets.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct BudgetReport {
+    pub year: i32,
+    pub month: u8,
+    /// Monthly budgets are compared with the month's expenses; annual budgets
+    /// are compared with every expense in the year up to the end of the month.
+    pub budgets: Vec<BudgetStatus>,
+    /// The alerts that have been triggered for the report's periods.
+    pub alerts: Vec<BudgetAlert>,
+}
+
+/// A value suggested for a field, and how confident the suggestion is.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct Suggested<T> {
+    pub value: T,
+    /// How likely the value is to be correct (0.0 to 1.0).
+    pub confidence: f32,
+}
+
+/// Values for an expense suggested from the text on its receipt.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct ExpenseSuggestion {
+    pub amount: Option<Suggested<f32>>,
+    pub timestamp: Option<Suggested<chrono::NaiveDate>>,
+    pub merchant: Option<Suggested<String>>,
+}
+
+/// Information about a receipt uploaded for an expense.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct Receipt {
+    pub id: String,
+    pub property_id: String,
+    /// The year of the expense sheet containing the expense.
+    pub year: i32,
+    /// The row number of the expense within the expense sheet.
+    pub row: u32,
+    pub content_type: String,
+    /// The size of the receipt in bytes.
+    pub size: usize,
+    pub file_name: Option<String>,
+    /// Where the receipt can be viewed by the owner of the property.
+    pub url: String,
+    pub uploaded_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct Reservation {
+    pub platform: String,
+    pub payout_date: chrono::NaiveDateTime,
+    pub check_in: chrono::NaiveDateTime,
+    pub check_out: chrono::NaiveDateTime,
+    pub revenue: f32,
+    pub management_fee: f32,
+    pub net_profit: f32,
+    /// The name of the sheet (tab) the reservation was read from.
+    #[serde(skip)]
+    pub sheet: String,
+    /// The row number of the reservation within its sheet (starting at 1).
+    #[serde(skip)]
+    pub row: u32,
+    /// Data quality issues found in the reservation's row, if any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ValidationIssue>,
+}
+
+/// How serious a data quality issue is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The data is suspicious, but may be correct.
+    Warning,
+    /// The data is definitely incorrect.
+    Error,
+}
+
+/// The kinds of data quality issues detected in a spreadsheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// The stay overlaps with another reservation.
+    OverlappingStay,
+    /// The check-out date is on or before the check-in date.
+    CheckOutBeforeCheckIn,
+    /// The payout date is before the check-in date.
+    PayoutBeforeCheckIn,
+    /// The net profit is not equal to revenue minus the management fee.
+    NetProfitMismatch,
+}
+
+/// A data quality issue found in a single row of a spreadsheet.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, TS)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    pub kind: IssueKind,
+    /// The name of the sheet (tab) containing the row.
+    pub sheet: String,
+    /// The row number within the sheet (starting at 1).
+    pub row: u32,
+    pub detail: String,
+}
+
+/// Every data quality issue found in a property's spreadsheet for a year.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct ValidationReport {
+    pub property_id: String,
+    pub year: i32,
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// The tab of a property's spreadsheet that holds a month's reservations.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct MonthSheet {
+    /// The month, from 1 (January) to 12 (December).
+    pub month: u8,
+    /// The title of the tab found for the month (e.g., `Sept`), if any.
+    pub sheet: Option<String>,
+}
+
+/// How the tabs of a property's spreadsheet for a year match the months.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct SheetReport {
+    pub property_id: String,
+    pub year: i32,
+    pub months: Vec<MonthSheet>,
+    /// The names of the months without a tab (e.g., `September`).
+    pub missing: Vec<String>,
+    /// The titles of the tabs that do not match any month.
+    pub unmatched: Vec<String>,
+}
+
+impl SheetReport {
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Financial totals for one or more properties over a period of time.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema, TS)]
+pub struct FinancialSummary {
+    pub revenue: f32,
+    pub expenses: f32,
+    /// Net profit from reservations (after management fees) minus expenses.
+    pub net_profit: f32,
+    #[ts(type = "number")]
+    pub nights_booked: i64,
+    #[ts(type = "number")]
+    pub nights_available: i64,
+    /// The fraction of available nights that were booked (0.0 to 1.0).
+    pub occupancy: f32,
+}
+
+/// The financial statement for a property over a single month.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct Statement {
+    pub property_id: String,
+    pub year: i32,
+    pub month: u8,
+    pub summary: FinancialSummary,
+    pub reservations: Vec<Reservation>,
+    pub expenses: Vec<Expense>,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Whether everything needed to track a year has been set up.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct ProvisioningReport {
+    pub year: i32,
+    /// Whether the shared expense sheet for the year has been registered.
+    pub has_expense_sheet: bool,
+    /// The properties without a spreadsheet for the year.
+    pub missing_spreadsheets: Vec<Property>,
+}
+
+impl ProvisioningReport {
+    pub fn is_complete(&self) -> bool {
+        self.has_expense_sheet && self.missing_spreadsheets.is_empty()
+    }
+}
+
+/// The financial summary for a single property within a portfolio.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct PropertySummary {
+    pub property: Property,
+    /// The summary for the property, if it was computed successfully.
+    pub summary: Option<FinancialSummary>,
+    /// The reason the summary could not be computed, if any.
+    pub error: Option<String>,
+}
+
+/// The combined financial summary for every property a user owns.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct Portfolio {
+    pub year: i32,
+    pub properties: Vec<PropertySummary>,
+    /// Totals for every property that was summarized successfully.
+    pub total: FinancialSummary,
+}
+
+/// The projected net profit of a property for a single month.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct MonthlyForecast {
+    pub month: u8,
+    /// Net profit from reservations that have already checked in.
+    pub actual: f32,
+    /// Net profit from reservations that check in after today.
+    pub booked: f32,
+    /// The average net profit for this month in previous years.
+    pub baseline: f32,
+    pub projected: f32,
+    /// The lower bound of the projection's confidence band.
+    pub lower: f32,
+    /// The upper bound of the projection's confidence band.
+    pub upper: f32,
+}
+
+/// The projected net profit of a property for every month of a year.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct Forecast {
+    pub year: i32,
+    /// The previous years used to calculate the seasonal baseline.
+    pub history: Vec<i32>,
+    pub months: Vec<MonthlyForecast>,
+}
+
+/// A payment made to the owner of a property.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct Payout {
+    pub id: String,
+    pub property_id: String,
+    /// The year of the statement period the payout is for.
+    pub year: i32,
+    /// The month of the statement period the payout is for.
+    pub month: u8,
+    pub amount: f32,
+    pub paid_on: chrono::NaiveDate,
+    /// An identifier for the payment (e.g., a check or transfer number).
+    pub reference: Option<String>,
+    pub note: Option<String>,
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+    /// When the payout was voided; voided payouts are kept for history, but
+    /// are not included in the reconciliation.
+    pub voided_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub void_reason: Option<String>,
+}
+
+/// The information required to record a new payout.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct NewPayout {
+    pub year: i32,
+    pub month: u8,
+    pub amount: f32,
+    pub paid_on: chrono::NaiveDate,
+    pub reference: Option<String>,
+    pub note: Option<String>,
+}
+
+/// The information required to void a payout.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema, TS)]
+pub struct VoidPayout {
+    pub reason: Option<String>,
+}
+
+/// Compares what the owner is owed with what was paid for a single month.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct ReconciliationPeriod {
+    pub month: u8,
+    /// Net profit from reservations minus expenses.
+    pub owed: f32,
+    /// The total of every payout recorded for the month (excluding voided).
+    pub paid: f32,
+    /// The running balance still owed to the owner at the end of the month.
+    pub balance: f32,
+}
+
+/// Compares what the owner is owed with what was paid over a year.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct Reconciliation {
+    pub property_id: String,
+    pub year: i32,
+    pub periods: Vec<ReconciliationPeriod>,
+    pub owed: f32,
+    pub paid: f32,
+    /// The balance still owed to the owner at the start of the year (carried
+    /// over from the end of the previous year).
+    pub opening_balance: f32,
+    /// The balance still owed to the owner at the end of the year.
+    pub balance: f32,
+}
+
+/// The kinds of notifications a user can choose to receive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    StatementReady,
+    NewReservation,
+    LargeExpense,
+    BudgetOverrun,
+}
+
+/// Which notifications a user wants to receive, and where.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, TS)]
+#[serde(default)]
+pub struct NotificationPreferences {
+    /// Where to send notifications; defaults to the user's first email
+    /// address if not provided.
+    pub email: Option<String>,
+    pub statement_ready: bool,
+    pub new_reservation: bool,
+    pub large_expense: bool,
+    pub budget_overrun: bool,
+    /// Expenses of at least this amount are considered large.
+    pub large_expense_threshold: f32,
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            email: None,
+            statement_ready: true,
+            new_reservation: true,
+            large_expense: true,
+            budget_overrun: true,
+            large_expense_threshold: 500.0,
+        }
+    }
+}
+
+impl NotificationPreferences {
+    /// Check whether the user wants to receive the kind of notification.
+    pub fn is_enabled(&self, kind: NotificationKind) -> bool {
+        match kind {
+            NotificationKind::StatementReady => self.statement_ready,
+            NotificationKind::NewReservation => self.new_reservation,
+            NotificationKind::LargeExpense => self.large_expense,
+            NotificationKind::BudgetOverrun => self.budget_overrun,
+        }
+    }
+}
+
+/// What happened when trying to deliver a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Sent,
+    Failed,
+    /// The user chose not to receive this kind of notification, or no way of
+    /// delivering notifications has been configured.
+    Skipped,
+}
+
+/// A record of an attempt to deliver a notification to a user.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct NotificationDelivery {
+    pub id: String,
+    pub user_id: String,
+    pub kind: NotificationKind,
+    pub to: Option<String>,
+    pub subject: String,
+    pub status: DeliveryStatus,
+    /// The reason the notification could not be delivered, if it failed.
+    pub error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The kinds of changes detected in the spreadsheets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    ReservationAdded,
+    ReservationChanged,
+    ReservationRemoved,
+    ExpenseAdded,
+}
+
+/// A change detected in the spreadsheets of a property.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Change {
+    ReservationAdded {
+        /// The name of the sheet (tab) the reservation was added to.
+        sheet: String,
+        reservation: Reservation,
+    },
+    ReservationChanged {
+        sheet: String,
+        before: Reservation,
+        after: Reservation,
+    },
+    ReservationRemoved {
+        sheet: String,
+        reservation: Reservation,
+    },
+    ExpenseAdded {
+        expense: Expense,
+    },
+}
+
+impl Change {
+    pub fn kind(&self) -> ChangeKind {
+        match self {
+            Self::ReservationAdded { .. } => ChangeKind::ReservationAdded,
+            Self::ReservationChanged { .. } => ChangeKind::ReservationChanged,
+            Self::ReservationRemoved { .. } => ChangeKind::ReservationRemoved,
+            Self::ExpenseAdded { .. } => ChangeKind::ExpenseAdded,
+        }
+    }
+}
+
+/// A change to a property, as delivered to webhooks.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct Event {
+    pub id: String,
+    pub property_id: String,
+    /// The ID of the user that owns the property.
+    pub user_id: String,
+    pub occurred_at: chrono::DateTime<chrono::Utc>,
+    #[serde(flatten)]
+    pub change: Change,
+}
+
+/// An outgoing webhook that events are delivered to.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    /// The kinds of events delivered to the webhook; every kind is delivered
+    /// if empty.
+    pub events: Vec<ChangeKind>,
+    /// Only deliver events for this property, if provided.
+    pub property_id: Option<String>,
+    /// Used to sign deliveries; only included when the webhook is created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub secret: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The information required to register a new webhook.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct NewWebhook {
+    pub url: String,
+    #[serde(default)]
+    pub events: Vec<ChangeKind>,
+    pub property_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// The event has not been delivered yet, but will be (re)tried.
+    Pending,
+    /// The event is being sent to the webhook.
+    Delivering,
+    Delivered,
+    /// Every attempt to deliver the event failed.
+    Failed,
+}
+
+/// The delivery of a single event to a webhook.
+#[derive(Debug, Serialize, Deserialize, ToSchema, TS)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub webhook_id: String,
+    pub event_id: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    /// The reason the last attempt failed, if it did.
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub delivered_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Write the TypeScript definitions of the models returned by the API (and
+/// of every type they use) to `directory`, one file per type.
+pub fn export_typescript(directory: &Path) -> Result<(), ExportError> {
+    Problem::export_all_to(directory)?;
+    User::export_all_to(directory)?;
+    Property::export_all_to(directory)?;
+    FeeReport::export_all_to(directory)?;
+    Expense::export_all_to(directory)?;
+    ExpenseMatching::export_all_to(directory)?;
+    UnmatchedExpenseReport::export_all_to(directory)?;
+    NewBudget::export_all_to(directory)?;
+    BudgetReport::export_all_to(directory)?;
+    ExpenseSuggestion::export_all_to(directory)?;
+    Receipt::export_all_to(directory)?;
+    Reservation::export_all_to(directory)?;
+    ValidationReport::export_all_to(directory)?;
+    SheetReport::export_all_to(directory)?;
+    Statement::export_all_to(directory)?;
+    ProvisioningReport::export_all_to(directory)?;
+    Portfolio::export_all_to(directory)?;
+    Forecast::export_all_to(directory)?;
+    Payout::export_all_to(directory)?;
+    NewPayout::export_all_to(directory)?;
+    VoidPayout::export_all_to(directory)?;
+    Reconciliation::export_all_to(directory)?;
+    NotificationPreferences::export_all_to(directory)?;
+    NotificationDelivery::export_all_to(directory)?;
+    Event::export_all_to(directory)?;
+    Webhook::export_all_to(directory)?;
+    NewWebhook::export_all_to(directory)?;
+    WebhookDelivery::export_all_to(directory)?;
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum Month {
+    January,
+    February,
+    March,
+    April,
+    May,
+    June,
+    July,
+    August,
+    September,
+    October,
+    November,
+    December,
+}
+
+impl TryFrom<u8> for Month {
+    type Error = &'static str;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        let month = match value {
+            1 => Month::January,
+            2 => Month::February,
+            3 => Month::March,
+            4 => Month::April,
+            5 => Month::May,
+            6 => Month::June,
+            7 => Month::July,
+            8 => Month::August,
+            9 => Month::September,
+            10 => Month::October,
+            11 => Month::November,
+            12 => Month::December,
+            _ => return Err("expected value to be between 1 and 12"),
+        };
+
+        Ok(month)
+    }
+}
+
+impl fmt::Display for Month {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::January => write!(f, "January"),
+            Self::February => write!(f, "February"),
+            Self::March => write!(f, "March"),
+            Self::April => write!(f, "April"),
+            Self::May => write!(f, "May"),
+            Self::June => write!(f, "June"),
+            Self::July => write!(f, "July"),
+            Self::August => write!(f, "August"),
+            Self::September => write!(f, "September"),
+            Self::October => write!(f, "October"),
+            Self::November => write!(f, "November"),
+            Self::December => write!(f, "December"),
+        }
+    }
+}
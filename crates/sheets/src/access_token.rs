@@ -1,10 +1,11 @@
-use std::{error, fmt};
+use std::{error, fmt, sync::Mutex};
 
 use base64::prelude::*;
 use openssl::{hash, pkey, rsa, sign};
+use reqwest::{Method, StatusCode};
 use serde_json::json;
 
-use crate::Client;
+use crate::{retry, Client, Scope, ServiceAccountKey};
 
 #[derive(Debug, serde::Deserialize)]
 pub struct AccessToken {
@@ -14,20 +15,80 @@ pub struct AccessToken {
     pub expires_at: i64,
 }
 
-/// Request a new access token from the Google API.
-#[must_use]
-pub async fn refresh_access_token(client: &Client) -> Result<AccessToken, RefreshAccessTokenError> {
-    let client_email = &client.credentials.client_email;
-    let private_key = format!("{}\n", client.credentials.private_key);
+/// Keeps an access token until shortly before it expires.
+#[derive(Debug, Default)]
+pub(crate) struct TokenCache(Mutex<Option<AccessToken>>);
+
+impl TokenCache {
+    /// Get the access token, unless it expires soon (or there is none).
+    pub fn get(&self) -> Option<String> {
+        let token = self.0.lock().unwrap();
+        let token = token.as_ref()?;
+
+        let now = chrono::Utc::now().timestamp();
+        // `60 * n` converts `n` minutes into seconds. If `n` is 10,
+        // we are checking if the token expires within the next 10 minutes.
+        (now < (token.expires_at - (60 * 10))).then(|| token.value.clone())
+    }
+
+    pub fn set(&self, token: AccessToken) -> String {
+        let value = token.value.clone();
+        *self.0.lock().unwrap() = Some(token);
+        value
+    }
+}
+
+/// Request a new access token for a service account from the Google API.
+///
+/// If `subject` is provided, the token acts on behalf of that user instead,
+/// which requires domain-wide delegation to be enabled for the account.
+pub(crate) async fn request_service_account_token(
+    client: &Client,
+    credentials: &ServiceAccountKey,
+    scope: &Scope,
+    subject: Option<&str>,
+) -> Result<AccessToken, RefreshAccessTokenError> {
+    let private_key = format!("{}\n", credentials.private_key);
 
     let header = get_jwt_header();
-    let claim = get_jwt_claim(&client_email, &client.scope.to_string());
-    let signature = get_signature(&private_key, &header, &claim);
+    let claim = get_jwt_claim(
+        &credentials.client_email,
+        &scope.to_string(),
+        &client.token_url,
+        subject,
+    );
+    let signature = get_signature(&private_key, &header, &claim)?;
 
     let jwt = format!("{}.{}.{}", header, claim, signature);
 
-    let access_token = request_access_token(&client, &jwt).await?;
-    Ok(access_token)
+    request_access_token(
+        client,
+        &[
+            ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+            ("assertion", &jwt),
+        ],
+    )
+    .await
+}
+
+/// Request a new access token for a user from the Google API, using the
+/// refresh token the user granted the OAuth client.
+pub(crate) async fn request_user_token(
+    client: &Client,
+    client_id: &str,
+    client_secret: &str,
+    refresh_token: &str,
+) -> Result<AccessToken, RefreshAccessTokenError> {
+    request_access_token(
+        client,
+        &[
+            ("grant_type", "refresh_token"),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("refresh_token", refresh_token),
+        ],
+    )
+    .await
 }
 
 fn get_jwt_header() -> String {
@@ -42,16 +103,19 @@ fn get_jwt_header() -> String {
     buffer
 }
 
-fn get_jwt_claim(iss: &str, scope: &str) -> String {
+fn get_jwt_claim(iss: &str, scope: &str, aud: &str, sub: Option<&str>) -> String {
     let input = {
         let now = chrono::Utc::now().timestamp();
-        let claim = json!({
+        let mut claim = json!({
             "iss": iss.to_string(),
             "scope": scope.to_string(),
-            "aud": "https://oauth2.googleapis.com/token".to_string(),
+            "aud": aud.to_string(),
             "exp": now + (60 * 5), // Expires in 5 minutes.
             "iat": now,
         });
+        if let Some(sub) = sub {
+            claim["sub"] = json!(sub);
+        }
         serde_json::to_string(&claim).unwrap()
     };
 
@@ -61,49 +125,66 @@ fn get_jwt_claim(iss: &str, scope: &str) -> String {
     buffer
 }
 
-fn get_signature(private_key: &str, header: &str, claim: &str) -> String {
+fn get_signature(
+    private_key: &str,
+    header: &str,
+    claim: &str,
+) -> Result<String, RefreshAccessTokenError> {
     let input = {
-        let rsa = rsa::Rsa::private_key_from_pem(private_key.as_bytes()).unwrap();
-        let pkey = pkey::PKey::from_rsa(rsa).unwrap();
-        let mut signer = sign::Signer::new(hash::MessageDigest::sha256(), &pkey).unwrap();
+        let rsa = rsa::Rsa::private_key_from_pem(private_key.as_bytes())
+            .map_err(|err| RefreshAccessTokenError::BadCredentials(err.to_string()))?;
+        let pkey = pkey::PKey::from_rsa(rsa)
+            .map_err(|err| RefreshAccessTokenError::BadCredentials(err.to_string()))?;
 
         let data = format!("{}.{}", header, claim);
-        signer.update(data.as_bytes()).unwrap();
-
-        signer.sign_to_vec().unwrap()
+        sign::Signer::new(hash::MessageDigest::sha256(), &pkey)
+            .and_then(|mut signer| {
+                signer.update(data.as_bytes())?;
+                signer.sign_to_vec()
+            })
+            .map_err(|err| RefreshAccessTokenError::BadCredentials(err.to_string()))?
     };
 
     // Convert signature into a Base64 encoded string.
     let mut buffer = String::new();
     BASE64_URL_SAFE_NO_PAD.encode_string(&input, &mut buffer);
-    buffer
+    Ok(buffer)
 }
 
 async fn request_access_token(
     client: &Client,
-    jwt: &str,
+    form: &[(&str, &str)],
 ) -> Result<AccessToken, RefreshAccessTokenError> {
-    let body = format!(
-        "grant_type={}&assertion={}",
-        "urn:ietf:params:oauth:grant-type:jwt-bearer", jwt,
-    );
+    let request = client.request(Method::POST, &client.token_url).form(form);
 
-    let response = client
-        .http
-        .post("https://oauth2.googleapis.com/token")
-        .header("Content-Type", "application/x-www-form-urlencoded")
-        .body(body)
-        .send()
+    let response = retry::send(&client.retry, request)
         .await
-        .map_err(|err| RefreshAccessTokenError::RequestFailure(err.to_string()))?
+        .map_err(|err| RefreshAccessTokenError::RequestFailure(err.to_string()))?;
+
+    let status = response.status();
+    let body = response
         .text()
         .await
-        .expect("failed to get response body");
+        .map_err(|err| RefreshAccessTokenError::RequestFailure(err.to_string()))?;
+
+    if !status.is_success() {
+        return Err(match status {
+            // Google responds with `invalid_grant` when the key or refresh
+            // token was revoked, or the claim was rejected.
+            StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED => {
+                RefreshAccessTokenError::BadCredentials(body)
+            }
+            StatusCode::TOO_MANY_REQUESTS => RefreshAccessTokenError::RateLimited,
+            status if status.is_server_error() => RefreshAccessTokenError::Unavailable,
+            status => RefreshAccessTokenError::RequestFailure(format!("{status}: {body}")),
+        });
+    }
 
-    let mut access_token: AccessToken = serde_json::from_str(&response).unwrap();
+    let mut access_token: AccessToken = serde_json::from_str(&body)
+        .map_err(|err| RefreshAccessTokenError::InvalidResponse(err.to_string()))?;
     // Without knowing when the response was received, the duration until
     // the token expires is meaningless; instead of duration, use timestamp.
-    access_token.expires_at = chrono::Utc::now().timestamp() + access_token.expires_at;
+    access_token.expires_at += chrono::Utc::now().timestamp();
     Ok(access_token)
 }
 
@@ -112,6 +193,15 @@ async fn request_access_token(
 pub enum RefreshAccessTokenError {
     /// An error occurred while trying to request a new access token.
     RequestFailure(String),
+    /// The private key could not be used, or the token endpoint rejected it.
+    BadCredentials(String),
+    /// Too many tokens were requested recently, even after retrying.
+    RateLimited,
+    /// The token endpoint is down, or failed to handle the request, even after
+    /// retrying.
+    Unavailable,
+    /// The token endpoint responded with something other than a token.
+    InvalidResponse(String),
 }
 
 impl error::Error for RefreshAccessTokenError {}
@@ -124,6 +214,14 @@ impl fmt::Display for RefreshAccessTokenError {
                 "an error occurred while getting access token: {}",
                 reason
             ),
+            Self::BadCredentials(reason) => {
+                write!(f, "the service account key was rejected: {}", reason)
+            }
+            Self::RateLimited => write!(f, "too many access tokens were requested"),
+            Self::Unavailable => write!(f, "the token endpoint is unavailable"),
+            Self::InvalidResponse(reason) => {
+                write!(f, "the access token could not be parsed: {}", reason)
+            }
         }
     }
 }
@@ -0,0 +1,170 @@
+use async_trait::async_trait;
+use reqwest::RequestBuilder;
+
+use crate::access_token::{
+    request_service_account_token, request_user_token, RefreshAccessTokenError, TokenCache,
+};
+use crate::{Client, Scope, ServiceAccountKey};
+
+/// A way of authenticating requests to the Google API.
+#[async_trait]
+pub trait Auth: Send + Sync {
+    /// The URL access tokens are requested from, unless the client was given
+    /// another one; `None` uses Google's token endpoint.
+    fn token_url(&self) -> Option<&str> {
+        None
+    }
+
+    /// Get the credentials to send with the next request, using the client to
+    /// request a new access token if necessary.
+    async fn authorize(&self, client: &Client) -> Result<Authorization, RefreshAccessTokenError>;
+}
+
+/// The credentials sent with a request.
+#[derive(Debug, Clone)]
+pub enum Authorization {
+    /// An OAuth2 access token, sent in the `Authorization` header.
+    Bearer(String),
+    /// An API key, sent as the `key` query parameter.
+    ApiKey(String),
+}
+
+impl Authorization {
+    pub(crate) fn apply(&self, request: RequestBuilder) -> RequestBuilder {
+        match self {
+            Self::Bearer(token) => request.bearer_auth(token),
+            Self::ApiKey(key) => request.query(&[("key", key)]),
+        }
+    }
+}
+
+/// Authenticates as a service account, using its key to sign requests for
+/// access tokens.
+///
+/// Spreadsheets have to be shared with the service account, unless it acts
+/// on behalf of a user through domain-wide delegation (see
+/// [`ServiceAccount::subject`]).
+pub struct ServiceAccount {
+    credentials: ServiceAccountKey,
+    scope: Scope,
+    subject: Option<String>,
+    cache: TokenCache,
+}
+
+impl ServiceAccount {
+    pub fn new(credentials: ServiceAccountKey, scope: Scope) -> Self {
+        Self {
+            credentials,
+            scope,
+            subject: None,
+            cache: TokenCache::default(),
+        }
+    }
+
+    /// Act on behalf of a user of the Google Workspace domain (e.g.,
+    /// `owner@example.com`), with access to the spreadsheets they own.
+    pub fn subject(mut self, email: impl Into<String>) -> Self {
+        self.subject = Some(email.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Auth for ServiceAccount {
+    fn token_url(&self) -> Option<&str> {
+        Some(&self.credentials.token_uri)
+    }
+
+    async fn authorize(&self, client: &Client) -> Result<Authorization, RefreshAccessTokenError> {
+        if let Some(token) = self.cache.get() {
+            return Ok(Authorization::Bearer(token));
+        }
+
+        let token = request_service_account_token(
+            client,
+            &self.credentials,
+            &self.scope,
+            self.subject.as_deref(),
+        )
+        .await?;
+        Ok(Authorization::Bearer(self.cache.set(token)))
+    }
+}
+
+/// Authenticates as a user, using the refresh token they granted an OAuth2
+/// client to request access tokens.
+///
+/// The scopes are the ones the user consented to when granting the token.
+pub struct RefreshToken {
+    client_id: String,
+    client_secret: String,
+    refresh_token: String,
+    cache: TokenCache,
+}
+
+impl RefreshToken {
+    pub fn new(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        refresh_token: impl Into<String>,
+    ) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            refresh_token: refresh_token.into(),
+            cache: TokenCache::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl Auth for RefreshToken {
+    async fn authorize(&self, client: &Client) -> Result<Authorization, RefreshAccessTokenError> {
+        if let Some(token) = self.cache.get() {
+            return Ok(Authorization::Bearer(token));
+        }
+
+        let token = request_user_token(
+            client,
+            &self.client_id,
+            &self.client_secret,
+            &self.refresh_token,
+        )
+        .await?;
+        Ok(Authorization::Bearer(self.cache.set(token)))
+    }
+}
+
+/// Authenticates using an API key, which can only read spreadsheets that are
+/// shared publicly.
+pub struct ApiKey(String);
+
+impl ApiKey {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+}
+
+#[async_trait]
+impl Auth for ApiKey {
+    async fn authorize(&self, _: &Client) -> Result<Authorization, RefreshAccessTokenError> {
+        Ok(Authorization::ApiKey(self.0.clone()))
+    }
+}
+
+/// Authenticates using an access token that was already issued (e.g., by a
+/// fake server in tests); the token is never refreshed.
+pub struct StaticToken(String);
+
+impl StaticToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+}
+
+#[async_trait]
+impl Auth for StaticToken {
+    async fn authorize(&self, _: &Client) -> Result<Authorization, RefreshAccessTokenError> {
+        Ok(Authorization::Bearer(self.0.clone()))
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+
+/// The value of a single cell, as read with
+/// [`ValueRenderOption::UnformattedValue`](crate::ValueRenderOption).
+///
+/// Formatted values are always text, since they are rendered the way they
+/// are shown in the spreadsheet (e.g., `$1,200.00`).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum CellValue {
+    Bool(bool),
+    /// A number, including dates and times read as serial numbers.
+    Number(f64),
+    /// Text, or an error (e.g., `#REF!`).
+    Text(String),
+}
+
+impl CellValue {
+    /// Check whether the cell contains nothing; the API returns empty cells
+    /// as empty text.
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Text(text) if text.is_empty())
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the date and time of a serial number (see
+    /// [`DateTimeRenderOption::SerialNumber`](crate::DateTimeRenderOption)).
+    ///
+    /// Serial numbers count the days since December 30, 1899, with the time
+    /// as the fraction of the day.
+    pub fn as_datetime(&self) -> Option<chrono::NaiveDateTime> {
+        let serial = self.as_f64()?;
+        let epoch = chrono::NaiveDate::from_ymd_opt(1899, 12, 30)?.and_hms_opt(0, 0, 0)?;
+        // Round to the nearest second, since times are rarely stored exactly.
+        let seconds = (serial * 86_400.0).round() as i64;
+
+        epoch.checked_add_signed(chrono::TimeDelta::try_seconds(seconds)?)
+    }
+
+    /// Get the date of a serial number, ignoring the time.
+    pub fn as_date(&self) -> Option<chrono::NaiveDate> {
+        self.as_datetime().map(|datetime| datetime.date())
+    }
+}
+
+impl Default for CellValue {
+    fn default() -> Self {
+        Self::Text(String::new())
+    }
+}
+
+impl fmt::Display for CellValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{}", value.to_string().to_uppercase()),
+            Self::Number(number) => write!(f, "{number}"),
+            Self::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
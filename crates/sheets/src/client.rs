@@ -1,40 +1,133 @@
-use std::error;
+use std::time::Duration;
 
-use crate::access_token::{refresh_access_token, AccessToken};
+use reqwest::{Method, RequestBuilder};
+
+use crate::access_token::RefreshAccessTokenError;
+use crate::auth::{Auth, Authorization, ServiceAccount};
 use crate::credentials::ServiceAccountKey;
+use crate::retry::RetryPolicy;
 use crate::scopes::Scope;
 
+/// The URL of the Google Sheets API that requests are sent to by default.
+pub const DEFAULT_API_URL: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+
+/// The URL of Google's token endpoint, used unless the authentication method
+/// (or the client) says otherwise.
+pub const DEFAULT_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+
 /// Represents the HTTP client that will be interacting with the Google API.
 pub struct Client {
     pub http: reqwest::Client,
-    pub credentials: ServiceAccountKey,
-    pub scope: Scope,
-    access_token: Option<AccessToken>,
+    /// The URL of the spreadsheets collection (e.g., [`DEFAULT_API_URL`]).
+    pub api_url: String,
+    /// The URL access tokens are requested from.
+    pub token_url: String,
+    /// How long to wait for each response before giving up on the attempt.
+    pub timeout: Option<Duration>,
+    /// How requests to the API and the token endpoint are retried.
+    pub retry: RetryPolicy,
+    auth: Box<dyn Auth>,
 }
 
 impl Client {
     pub fn new(credentials: ServiceAccountKey, scope: Scope) -> Self {
-        Self {
-            http: reqwest::Client::new(),
-            credentials,
-            scope,
-            access_token: None,
+        Self::builder(credentials, scope).build()
+    }
+
+    /// Configure a client that authenticates as the service account, but
+    /// sends requests somewhere other than Google, or using a different HTTP
+    /// client.
+    pub fn builder(credentials: ServiceAccountKey, scope: Scope) -> ClientBuilder {
+        ClientBuilder::new(ServiceAccount::new(credentials, scope))
+    }
+
+    /// Get the credentials to send with the next request.
+    pub async fn authorize(&self) -> Result<Authorization, RefreshAccessTokenError> {
+        self.auth.authorize(self).await
+    }
+
+    /// Start building a request, applying the timeout of the client.
+    pub(crate) fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        let request = self.http.request(method, url);
+
+        match self.timeout {
+            Some(timeout) => request.timeout(timeout),
+            None => request,
         }
     }
+}
 
-    pub async fn get_access_token(&mut self) -> Result<String, Box<dyn error::Error>> {
-        let access_token_expires_soon = self.access_token.as_ref().is_some_and(|token| {
-            let now = chrono::Utc::now().timestamp();
-            // `60 * n` converts `n` minutes into seconds. If `n` is 10,
-            // we are checking if the token expires within the next 10 minutes.
-            now >= (token.expires_at - (60 * 10))
-        });
+/// Creates a [`Client`], using Google's endpoints unless told otherwise.
+pub struct ClientBuilder {
+    auth: Box<dyn Auth>,
+    http: Option<reqwest::Client>,
+    api_url: Option<String>,
+    token_url: Option<String>,
+    timeout: Option<Duration>,
+    retry: RetryPolicy,
+}
 
-        if self.access_token.is_none() || access_token_expires_soon {
-            let access_token = refresh_access_token(&self).await?;
-            self.access_token = Some(access_token);
+impl ClientBuilder {
+    /// Configure a client that authenticates using the method (e.g.,
+    /// [`RefreshToken`](crate::RefreshToken)).
+    pub fn new(auth: impl Auth + 'static) -> Self {
+        Self {
+            auth: Box::new(auth),
+            http: None,
+            api_url: None,
+            token_url: None,
+            timeout: None,
+            retry: RetryPolicy::default(),
         }
+    }
+
+    /// Send requests for spreadsheets to the URL instead of
+    /// [`DEFAULT_API_URL`] (e.g., `http://localhost:8080/v4/spreadsheets`).
+    pub fn api_url(mut self, url: impl Into<String>) -> Self {
+        self.api_url = Some(url.into());
+        self
+    }
+
+    /// Request access tokens from the URL instead of the one chosen by the
+    /// authentication method (e.g., the `token_uri` of a service account).
+    pub fn token_url(mut self, url: impl Into<String>) -> Self {
+        self.token_url = Some(url.into());
+        self
+    }
+
+    /// Send requests using the client, instead of a new one.
+    pub fn http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = Some(http);
+        self
+    }
 
-        Ok(self.access_token.as_ref().unwrap().value.clone())
+    /// Give up on an attempt if no response is received within the duration.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn build(self) -> Client {
+        let api_url = self.api_url.as_deref().unwrap_or(DEFAULT_API_URL);
+        let token_url = self
+            .token_url
+            .as_deref()
+            .or(self.auth.token_url())
+            .unwrap_or(DEFAULT_TOKEN_URL)
+            .to_string();
+
+        Client {
+            http: self.http.unwrap_or_default(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token_url,
+            timeout: self.timeout,
+            retry: self.retry,
+            auth: self.auth,
+        }
     }
 }
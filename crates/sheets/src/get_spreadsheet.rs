@@ -0,0 +1,161 @@
+use std::{error, fmt};
+
+use reqwest::{Method, StatusCode};
+
+use crate::{retry, Client, RefreshAccessTokenError};
+
+/// The properties of a spreadsheet and its sheets (i.e., tabs), without any
+/// of their values.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Spreadsheet {
+    pub spreadsheet_id: String,
+    pub properties: SpreadsheetProperties,
+    #[serde(default)]
+    pub sheets: Vec<Sheet>,
+}
+
+impl Spreadsheet {
+    /// Get the titles of the sheets, in the order they appear.
+    pub fn sheet_titles(&self) -> impl Iterator<Item = &str> {
+        self.sheets
+            .iter()
+            .map(|sheet| sheet.properties.title.as_str())
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpreadsheetProperties {
+    pub title: String,
+    pub locale: Option<String>,
+    pub time_zone: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct Sheet {
+    pub properties: SheetProperties,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SheetProperties {
+    pub sheet_id: i64,
+    /// The name of the sheet, as shown on its tab (e.g., `January`).
+    pub title: String,
+    /// The position of the sheet, starting at 0.
+    #[serde(default)]
+    pub index: u32,
+    #[serde(default)]
+    pub hidden: bool,
+    /// The size of the sheet; only sheets containing a grid (rather than a
+    /// chart) have one.
+    pub grid_properties: Option<GridProperties>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GridProperties {
+    #[serde(default)]
+    pub row_count: u32,
+    #[serde(default)]
+    pub column_count: u32,
+    #[serde(default)]
+    pub frozen_row_count: u32,
+    #[serde(default)]
+    pub frozen_column_count: u32,
+}
+
+/// Get the properties of a spreadsheet and its sheets.
+pub async fn get_spreadsheet(
+    client: &Client,
+    spreadsheet_id: &str,
+) -> Result<Spreadsheet, GetSpreadsheetError> {
+    // Only request the properties; otherwise, the response includes the
+    // formatting of every sheet.
+    let url = format!(
+        "{}/{}?fields=spreadsheetId,properties,sheets.properties",
+        client.api_url, spreadsheet_id
+    );
+
+    let authorization = client.authorize().await?;
+    let request = authorization.apply(client.request(Method::GET, &url));
+
+    let response = retry::send(&client.retry, request)
+        .await
+        .map_err(|err| GetSpreadsheetError::RequestFailure(err.to_string()))?;
+
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|err| GetSpreadsheetError::RequestFailure(err.to_string()))?;
+
+    if !status.is_success() {
+        return Err(match status {
+            StatusCode::UNAUTHORIZED => GetSpreadsheetError::BadCredentials(body),
+            StatusCode::FORBIDDEN => GetSpreadsheetError::MissingPermissions,
+            StatusCode::NOT_FOUND => GetSpreadsheetError::NotFound,
+            StatusCode::TOO_MANY_REQUESTS => GetSpreadsheetError::RateLimited,
+            status if status.is_server_error() => GetSpreadsheetError::Unavailable,
+            status => GetSpreadsheetError::RequestFailure(format!("{status}: {body}")),
+        });
+    }
+
+    let spreadsheet: Spreadsheet = serde_json::from_str(&body)
+        .map_err(|err| GetSpreadsheetError::InvalidResponse(err.to_string()))?;
+    Ok(spreadsheet)
+}
+
+#[derive(Debug)]
+pub enum GetSpreadsheetError {
+    RequestFailure(String),
+    MissingPermissions,
+    /// The credentials could not be used to get an access token, or the
+    /// access token was rejected.
+    BadCredentials(String),
+    /// The spreadsheet does not exist.
+    NotFound,
+    /// Too many requests were made recently, even after retrying.
+    RateLimited,
+    /// The API is down, or failed to handle the request, even after retrying.
+    Unavailable,
+    /// The response body could not be parsed as a spreadsheet.
+    InvalidResponse(String),
+}
+
+impl error::Error for GetSpreadsheetError {}
+
+impl fmt::Display for GetSpreadsheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RequestFailure(reason) => {
+                write!(f, "failed to get spreadsheet: {}", reason)
+            }
+            Self::MissingPermissions => {
+                write!(f, "missing required permissions to view this resource")
+            }
+            Self::BadCredentials(reason) => {
+                write!(f, "the credentials were rejected: {}", reason)
+            }
+            Self::NotFound => write!(f, "the spreadsheet does not exist"),
+            Self::RateLimited => write!(f, "too many requests were made to the sheets api"),
+            Self::Unavailable => write!(f, "the sheets api is unavailable"),
+            Self::InvalidResponse(reason) => {
+                write!(f, "failed to parse spreadsheet: {}", reason)
+            }
+        }
+    }
+}
+
+impl From<RefreshAccessTokenError> for GetSpreadsheetError {
+    fn from(err: RefreshAccessTokenError) -> Self {
+        match err {
+            RefreshAccessTokenError::RequestFailure(reason) => Self::RequestFailure(reason),
+            RefreshAccessTokenError::BadCredentials(reason) => Self::BadCredentials(reason),
+            RefreshAccessTokenError::RateLimited => Self::RateLimited,
+            RefreshAccessTokenError::Unavailable => Self::Unavailable,
+            RefreshAccessTokenError::InvalidResponse(reason) => Self::InvalidResponse(reason),
+        }
+    }
+}
@@ -1,22 +1,28 @@
 use std::{error, fmt};
 
-use reqwest::StatusCode;
+use reqwest::{Method, StatusCode};
 
-use crate::Client;
+use crate::range::encode_path_segment;
+use crate::{retry, Client, RefreshAccessTokenError};
 
 #[derive(Debug, serde::Deserialize)]
 pub struct ValueRange<T> {
     pub range: String,
     #[serde(rename = "majorDimension")]
     pub major_dimension: Dimension,
-    #[serde(bound(deserialize = "T: serde::Deserialize<'de>"))]
+    /// Empty if the range contains no values; the API omits the field then.
+    #[serde(
+        default = "Vec::new",
+        bound(deserialize = "T: serde::Deserialize<'de>")
+    )]
     pub values: Vec<T>,
 }
 
 /// Indicates which dimension an operation should apply to.
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
 pub enum Dimension {
     /// Operates on the rows of a sheet.
+    #[default]
     #[serde(rename = "ROWS")]
     Rows,
     /// Operates on the columns of a sheet.
@@ -24,36 +30,142 @@ pub enum Dimension {
     Columns,
 }
 
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rows => write!(f, "ROWS"),
+            Self::Columns => write!(f, "COLUMNS"),
+        }
+    }
+}
+
+/// How values are rendered in the response.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ValueRenderOption {
+    /// Values are text, formatted the way they are shown in the spreadsheet
+    /// (e.g., `$1,200.00`).
+    #[default]
+    FormattedValue,
+    /// Values are not formatted (e.g., `1200`); numbers are read as numbers.
+    UnformattedValue,
+    /// Formulas are read instead of what they evaluate to.
+    Formula,
+}
+
+impl fmt::Display for ValueRenderOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FormattedValue => write!(f, "FORMATTED_VALUE"),
+            Self::UnformattedValue => write!(f, "UNFORMATTED_VALUE"),
+            Self::Formula => write!(f, "FORMULA"),
+        }
+    }
+}
+
+/// How dates and times are rendered in the response, unless values are
+/// formatted (see [`ValueRenderOption::FormattedValue`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DateTimeRenderOption {
+    /// Dates and times are numbers (see
+    /// [`CellValue::as_datetime`](crate::CellValue::as_datetime)).
+    #[default]
+    SerialNumber,
+    /// Dates and times are text, formatted using the format of the cell.
+    FormattedString,
+}
+
+impl fmt::Display for DateTimeRenderOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SerialNumber => write!(f, "SERIAL_NUMBER"),
+            Self::FormattedString => write!(f, "FORMATTED_STRING"),
+        }
+    }
+}
+
+/// How values are read by [`get_values_with_options`]; the defaults match
+/// the ones of the API.
+#[derive(Debug, Clone, Default)]
+pub struct GetValuesOptions {
+    /// Whether each value of the response is a row or a column.
+    pub major_dimension: Dimension,
+    pub value_render_option: ValueRenderOption,
+    pub date_time_render_option: DateTimeRenderOption,
+}
+
+impl GetValuesOptions {
+    /// Read numbers as numbers and dates as serial numbers, regardless of how
+    /// they are formatted (e.g., into rows of [`CellValue`](crate::CellValue)).
+    pub fn unformatted() -> Self {
+        Self {
+            major_dimension: Dimension::Rows,
+            value_render_option: ValueRenderOption::UnformattedValue,
+            date_time_render_option: DateTimeRenderOption::SerialNumber,
+        }
+    }
+}
+
+/// Get the values of a range (e.g., `Sheet1!A1:B2`, or an
+/// [`A1Range`](crate::A1Range) as a string), formatted the way they are shown
+/// in the spreadsheet.
 pub async fn get_values<T: for<'de> serde::Deserialize<'de>>(
-    client: &mut Client,
+    client: &Client,
     spreadsheet_id: &str,
     range: &str,
 ) -> Result<ValueRange<T>, GetValuesError> {
-    static BASE_URL: &'static str = "https://sheets.googleapis.com/v4/spreadsheets";
-    let url = format!("{BASE_URL}/{}/values/{}", spreadsheet_id, range);
+    get_values_with_options(client, spreadsheet_id, range, &GetValuesOptions::default()).await
+}
 
-    let access_token = client
-        .get_access_token()
+pub async fn get_values_with_options<T: for<'de> serde::Deserialize<'de>>(
+    client: &Client,
+    spreadsheet_id: &str,
+    range: &str,
+    options: &GetValuesOptions,
+) -> Result<ValueRange<T>, GetValuesError> {
+    let url = format!(
+        "{}/{}/values/{}",
+        client.api_url,
+        spreadsheet_id,
+        encode_path_segment(range)
+    );
+
+    let authorization = client.authorize().await?;
+
+    let request = authorization
+        .apply(client.request(Method::GET, &url))
+        .query(&[
+            ("majorDimension", options.major_dimension.to_string()),
+            ("valueRenderOption", options.value_render_option.to_string()),
+            (
+                "dateTimeRenderOption",
+                options.date_time_render_option.to_string(),
+            ),
+        ]);
+
+    let response = retry::send(&client.retry, request)
         .await
-        .expect("failed to get access_token");
+        .map_err(|err| GetValuesError::RequestFailure(err.to_string()))?;
 
-    let response = client
-        .http
-        .get(url)
-        .header("Authorization", format!("Bearer {access_token}"))
-        .send()
+    let status = response.status();
+    let body = response
+        .text()
         .await
         .map_err(|err| GetValuesError::RequestFailure(err.to_string()))?;
 
-    response
-        .error_for_status_ref()
-        .map_err(|err| match err.status() {
-            Some(StatusCode::FORBIDDEN) => GetValuesError::MissingPermissions,
-            _ => unreachable!(),
-        })?;
+    if !status.is_success() {
+        return Err(match status {
+            StatusCode::BAD_REQUEST => GetValuesError::InvalidRange(body),
+            StatusCode::UNAUTHORIZED => GetValuesError::BadCredentials(body),
+            StatusCode::FORBIDDEN => GetValuesError::MissingPermissions,
+            StatusCode::NOT_FOUND => GetValuesError::NotFound,
+            StatusCode::TOO_MANY_REQUESTS => GetValuesError::RateLimited,
+            status if status.is_server_error() => GetValuesError::Unavailable,
+            status => GetValuesError::RequestFailure(format!("{status}: {body}")),
+        });
+    }
 
-    let body = response.text().await.expect("failed to get response body");
-    let values: ValueRange<T> = serde_json::from_str(&body).expect("failed to parse response body");
+    let values: ValueRange<T> = serde_json::from_str(&body)
+        .map_err(|err| GetValuesError::InvalidResponse(err.to_string()))?;
     Ok(values)
 }
 
@@ -61,6 +173,20 @@ pub async fn get_values<T: for<'de> serde::Deserialize<'de>>(
 pub enum GetValuesError {
     RequestFailure(String),
     MissingPermissions,
+    /// The credentials could not be used to get an access token, or the
+    /// access token was rejected.
+    BadCredentials(String),
+    /// The spreadsheet does not exist.
+    NotFound,
+    /// The range could not be parsed, or names a sheet (tab) that does not
+    /// exist.
+    InvalidRange(String),
+    /// Too many requests were made recently, even after retrying.
+    RateLimited,
+    /// The API is down, or failed to handle the request, even after retrying.
+    Unavailable,
+    /// The response body could not be parsed as the requested values.
+    InvalidResponse(String),
 }
 
 impl error::Error for GetValuesError {}
@@ -74,22 +200,28 @@ impl fmt::Display for GetValuesError {
             Self::MissingPermissions => {
                 write!(f, "missing required permissions to view this resource")
             }
+            Self::BadCredentials(reason) => {
+                write!(f, "the credentials were rejected: {}", reason)
+            }
+            Self::NotFound => write!(f, "the spreadsheet does not exist"),
+            Self::InvalidRange(reason) => write!(f, "the range is not valid: {}", reason),
+            Self::RateLimited => write!(f, "too many requests were made to the sheets api"),
+            Self::Unavailable => write!(f, "the sheets api is unavailable"),
+            Self::InvalidResponse(reason) => {
+                write!(f, "failed to parse spreadsheet values: {}", reason)
+            }
         }
     }
 }
 
-#[cfg(feature = "axum")]
-impl axum::response::IntoResponse for GetValuesError {
-    fn into_response(self) -> axum::response::Response {
-        let status_code = match &self {
-            Self::RequestFailure(..) => axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-            Self::MissingPermissions => axum::http::StatusCode::FORBIDDEN,
-        };
-        let detail = self.to_string();
-
-        axum::response::IntoResponse::into_response((
-            status_code,
-            axum::Json(serde_json::json!({"detail": detail})),
-        ))
+impl From<RefreshAccessTokenError> for GetValuesError {
+    fn from(err: RefreshAccessTokenError) -> Self {
+        match err {
+            RefreshAccessTokenError::RequestFailure(reason) => Self::RequestFailure(reason),
+            RefreshAccessTokenError::BadCredentials(reason) => Self::BadCredentials(reason),
+            RefreshAccessTokenError::RateLimited => Self::RateLimited,
+            RefreshAccessTokenError::Unavailable => Self::Unavailable,
+            RefreshAccessTokenError::InvalidResponse(reason) => Self::InvalidResponse(reason),
+        }
     }
 }
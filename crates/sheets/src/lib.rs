@@ -1,10 +1,31 @@
 mod access_token;
+mod auth;
+mod cell_value;
 mod client;
 mod credentials;
+mod get_spreadsheet;
 mod get_values;
+mod range;
+mod retry;
+mod row_reader;
 mod scopes;
+mod update_values;
 
-pub use client::Client;
+pub use access_token::RefreshAccessTokenError;
+pub use auth::{ApiKey, Auth, Authorization, RefreshToken, ServiceAccount, StaticToken};
+pub use cell_value::CellValue;
+pub use client::{Client, ClientBuilder, DEFAULT_API_URL, DEFAULT_TOKEN_URL};
 pub use credentials::ServiceAccountKey;
-pub use get_values::{get_values, Dimension, GetValuesError, ValueRange};
+pub use get_spreadsheet::{
+    get_spreadsheet, GetSpreadsheetError, GridProperties, Sheet, SheetProperties, Spreadsheet,
+    SpreadsheetProperties,
+};
+pub use get_values::{
+    get_values, get_values_with_options, DateTimeRenderOption, Dimension, GetValuesError,
+    GetValuesOptions, ValueRange, ValueRenderOption,
+};
+pub use range::{column_letters, A1Range};
+pub use retry::RetryPolicy;
+pub use row_reader::{RowReader, RowWindow, DEFAULT_WINDOW};
 pub use scopes::Scope;
+pub use update_values::{update_values, UpdateValuesError};
@@ -0,0 +1,139 @@
+use std::fmt;
+
+/// A range of a sheet in A1 notation (e.g., `'Jan 2025'!A2:G`), quoting the
+/// name of the sheet so that names with spaces or apostrophes still work.
+///
+/// Columns and rows start at 1. Without columns or rows, the range is the
+/// whole sheet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct A1Range {
+    sheet: String,
+    columns: Option<(u32, u32)>,
+    /// The first row, and the last row if the range does not continue to the
+    /// end of the sheet.
+    rows: Option<(u32, Option<u32>)>,
+}
+
+impl A1Range {
+    /// Select the whole sheet with the name (i.e., the title of its tab).
+    pub fn new(sheet: impl Into<String>) -> Self {
+        Self {
+            sheet: sheet.into(),
+            columns: None,
+            rows: None,
+        }
+    }
+
+    /// Only select the columns from the first to the last, inclusive (e.g.,
+    /// `1` and `7` for `A:G`).
+    pub fn columns(mut self, first: u32, last: u32) -> Self {
+        self.columns = Some((first, last));
+        self
+    }
+
+    /// Only select the rows from the first to the last, inclusive.
+    pub fn rows(mut self, first: u32, last: u32) -> Self {
+        self.rows = Some((first, Some(last)));
+        self
+    }
+
+    /// Only select the rows from the first to the end of the sheet.
+    ///
+    /// A1 notation can only leave the end of the rows open for a range of
+    /// columns, so this is ignored unless columns are selected too.
+    pub fn rows_from(mut self, first: u32) -> Self {
+        self.rows = Some((first, None));
+        self
+    }
+
+    pub fn sheet(&self) -> &str {
+        &self.sheet
+    }
+}
+
+impl fmt::Display for A1Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", quote_sheet_name(&self.sheet))?;
+
+        match (self.columns, self.rows) {
+            (None, None) | (None, Some((_, None))) => Ok(()),
+            (Some((first, last)), None) => {
+                write!(f, "!{}:{}", column_letters(first), column_letters(last))
+            }
+            (None, Some((first, Some(last)))) => write!(f, "!{first}:{last}"),
+            (Some((first_column, last_column)), Some((first_row, last_row))) => {
+                write!(
+                    f,
+                    "!{}{first_row}:{}",
+                    column_letters(first_column),
+                    column_letters(last_column)
+                )?;
+                match last_row {
+                    Some(last_row) => write!(f, "{last_row}"),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Get the letters of a column, starting at 1 (e.g., `1` is `A` and `27` is
+/// `AA`).
+pub fn column_letters(column: u32) -> String {
+    let mut letters = Vec::new();
+    let mut column = column;
+
+    while column > 0 {
+        let remainder = (column - 1) % 26;
+        letters.push(char::from(b'A' + remainder as u8));
+        column = (column - 1) / 26;
+    }
+
+    letters.into_iter().rev().collect()
+}
+
+/// Quote the name of a sheet, unless it only contains letters and
+/// underscores; apostrophes within the name are escaped by doubling them.
+///
+/// Names with digits are quoted too, so they cannot be mistaken for a cell
+/// (e.g., a sheet named `A1`).
+fn quote_sheet_name(name: &str) -> String {
+    let is_plain = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic() || c == '_');
+
+    if is_plain {
+        name.to_string()
+    } else {
+        format!("'{}'", name.replace('\'', "''"))
+    }
+}
+
+/// Encode a range for use as a segment of a URL's path (e.g., `Jan 2025!A:G`
+/// becomes `Jan%202025!A:G`).
+pub(crate) fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~'
+            | b'!'
+            | b'$'
+            | b'\''
+            | b'('
+            | b')'
+            | b'*'
+            | b','
+            | b';'
+            | b':'
+            | b'@' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    encoded
+}
@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::{header, RequestBuilder, Response, StatusCode};
+
+/// Decides whether, and after how long, a failed request is sent again.
+///
+/// Requests are retried when the API responds with `429 Too Many Requests` or
+/// a server error, or when the request could not be sent at all. The delay
+/// doubles after every attempt, starting at `base_delay`, unless the response
+/// says how long to wait using the `Retry-After` header.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// The number of times a request is sent before giving up; `1` means
+    /// requests are never retried.
+    pub max_attempts: u32,
+    /// How long to wait before the first retry.
+    pub base_delay: Duration,
+    /// The longest time to wait between attempts, even if the API asks for
+    /// longer using `Retry-After`.
+    pub max_delay: Duration,
+    /// Whether to wait a random duration of up to the delay instead, so that
+    /// requests that failed together are not retried together.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that sends every request only once.
+    pub fn never() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Get how long to wait after the `attempt`th attempt failed.
+    fn get_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(self.max_delay);
+        }
+
+        let delay = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(attempt - 1))
+            .min(self.max_delay);
+
+        if self.jitter {
+            delay.mul_f64(rand::thread_rng().gen())
+        } else {
+            delay
+        }
+    }
+}
+
+/// Send a request, retrying it according to the policy.
+///
+/// The last response is returned even if it was unsuccessful, so the caller
+/// can decide what its status means.
+pub(crate) async fn send(
+    policy: &RetryPolicy,
+    request: RequestBuilder,
+) -> Result<Response, reqwest::Error> {
+    let mut request = request;
+    let mut attempt = 1;
+
+    loop {
+        // Requests only ever have a text body, so they can always be cloned.
+        let next = match attempt < policy.max_attempts {
+            true => request.try_clone(),
+            false => None,
+        };
+        let result = request.send().await;
+
+        let Some(next) = next else {
+            return result;
+        };
+
+        let retry_after = match &result {
+            Ok(response) if is_transient(response.status()) => get_retry_after(response),
+            Err(err) if err.is_connect() || err.is_timeout() => None,
+            _ => return result,
+        };
+
+        tokio::time::sleep(policy.get_delay(attempt, retry_after)).await;
+        request = next;
+        attempt += 1;
+    }
+}
+
+/// Whether a request that failed with the status might succeed if retried.
+fn is_transient(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Get how long the API asked to wait before retrying, from the number of
+/// seconds or the date in the `Retry-After` header.
+fn get_retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(header::RETRY_AFTER)?.to_str().ok()?;
+
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    // A date in the past means the request can be retried immediately.
+    Some(
+        (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
@@ -0,0 +1,124 @@
+use crate::{
+    get_values_with_options, A1Range, Client, Dimension, GetValuesError, GetValuesOptions,
+};
+
+/// The number of rows read at a time, unless told otherwise.
+pub const DEFAULT_WINDOW: u32 = 1000;
+
+/// Reads the rows of a range a window at a time, so that large sheets are
+/// never downloaded at once.
+///
+/// Reading stops at the first window that is not full, since the API leaves
+/// out empty rows at the end of a range; a gap of empty rows as large as the
+/// window is treated as the end of the sheet.
+#[derive(Debug, Clone)]
+pub struct RowReader {
+    spreadsheet_id: String,
+    range: A1Range,
+    next_row: u32,
+    window: u32,
+    options: GetValuesOptions,
+    finished: bool,
+}
+
+/// The rows read in a single window.
+#[derive(Debug)]
+pub struct RowWindow<T> {
+    /// The number of the first row of the window, starting at 1.
+    pub first_row: u32,
+    pub rows: Vec<T>,
+}
+
+impl<T> RowWindow<T> {
+    /// Get each row along with its number.
+    pub fn numbered(self) -> impl Iterator<Item = (u32, T)> {
+        (self.first_row..).zip(self.rows)
+    }
+}
+
+impl RowReader {
+    /// Read the rows of the range (e.g., the columns of a sheet), starting at
+    /// the first row.
+    ///
+    /// Any rows selected by the range are replaced by the windows.
+    pub fn new(spreadsheet_id: impl Into<String>, range: A1Range) -> Self {
+        Self {
+            spreadsheet_id: spreadsheet_id.into(),
+            range,
+            next_row: 1,
+            window: DEFAULT_WINDOW,
+            options: GetValuesOptions::default(),
+            finished: false,
+        }
+    }
+
+    /// Start reading at the row instead (e.g., the row after the last one
+    /// processed by a previous reader).
+    pub fn from_row(mut self, row: u32) -> Self {
+        self.next_row = row.max(1);
+        self
+    }
+
+    /// Read the number of rows at a time instead of [`DEFAULT_WINDOW`].
+    pub fn window(mut self, rows: u32) -> Self {
+        self.window = rows.max(1);
+        self
+    }
+
+    /// Read values using the options; windows are always read by row, so the
+    /// major dimension is ignored.
+    pub fn options(mut self, options: GetValuesOptions) -> Self {
+        self.options = GetValuesOptions {
+            major_dimension: Dimension::Rows,
+            ..options
+        };
+        self
+    }
+
+    /// The row the next window starts at; once finished, this is the row
+    /// after the last row with a value.
+    pub fn next_row(&self) -> u32 {
+        self.next_row
+    }
+
+    /// Read the next window of rows, or `None` once every row was read.
+    pub async fn next<T: for<'de> serde::Deserialize<'de>>(
+        &mut self,
+        client: &Client,
+    ) -> Result<Option<RowWindow<T>>, GetValuesError> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let first_row = self.next_row;
+        let last_row = first_row.saturating_add(self.window - 1);
+        let range = self.range.clone().rows(first_row, last_row);
+
+        let values = get_values_with_options::<T>(
+            client,
+            &self.spreadsheet_id,
+            &range.to_string(),
+            &self.options,
+        )
+        .await?
+        .values;
+
+        // Only the rows up to the last one with a value are returned.
+        let count = values.len() as u32;
+        if count < self.window {
+            self.finished = true;
+            self.next_row = first_row + count;
+        } else {
+            self.next_row = last_row + 1;
+        }
+
+        if values.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(RowWindow {
+            first_row,
+            rows: values,
+        }))
+    }
+}
@@ -2,15 +2,17 @@ use std::fmt;
 
 #[derive(Debug)]
 pub enum Scope {
+    Spreadsheets,
     SpreadsheetsReadOnly,
 }
 
 impl fmt::Display for Scope {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        static BASE_URL: &'static str = "https://www.googleapis.com/auth";
+        static BASE_URL: &str = "https://www.googleapis.com/auth";
 
         match self {
-            Self::SpreadsheetsReadOnly => write!(f, "{BASE_URL}/{}", "spreadsheets.readonly"),
+            Self::Spreadsheets => write!(f, "{BASE_URL}/spreadsheets"),
+            Self::SpreadsheetsReadOnly => write!(f, "{BASE_URL}/spreadsheets.readonly"),
         }
     }
 }
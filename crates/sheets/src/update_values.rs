@@ -0,0 +1,111 @@
+use std::{error, fmt};
+
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+
+use crate::range::encode_path_segment;
+use crate::{retry, Client, RefreshAccessTokenError};
+
+/// Replace the values in a range of a spreadsheet.
+///
+/// Values are parsed as if they were typed into the spreadsheet by a user
+/// (e.g., `=SUM(A1:A2)` becomes a formula and `$1.00` becomes currency).
+pub async fn update_values(
+    client: &Client,
+    spreadsheet_id: &str,
+    range: &str,
+    values: Vec<Vec<String>>,
+) -> Result<(), UpdateValuesError> {
+    let url = format!(
+        "{}/{}/values/{}?valueInputOption=USER_ENTERED",
+        client.api_url,
+        spreadsheet_id,
+        encode_path_segment(range)
+    );
+
+    let authorization = client.authorize().await?;
+
+    let body = json!({
+        "range": range,
+        "majorDimension": "ROWS",
+        "values": values,
+    });
+
+    let request = authorization
+        .apply(client.request(Method::PUT, &url))
+        .header("Content-Type", "application/json")
+        .body(body.to_string());
+
+    let response = retry::send(&client.retry, request)
+        .await
+        .map_err(|err| UpdateValuesError::RequestFailure(err.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+
+        return Err(match status {
+            StatusCode::UNAUTHORIZED => UpdateValuesError::BadCredentials(body),
+            StatusCode::FORBIDDEN => UpdateValuesError::MissingPermissions,
+            StatusCode::NOT_FOUND => UpdateValuesError::NotFound,
+            StatusCode::TOO_MANY_REQUESTS => UpdateValuesError::RateLimited,
+            status if status.is_server_error() => UpdateValuesError::Unavailable,
+            status => UpdateValuesError::RequestFailure(format!("{status}: {body}")),
+        });
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum UpdateValuesError {
+    RequestFailure(String),
+    MissingPermissions,
+    /// The credentials could not be used to get an access token, or the
+    /// access token was rejected.
+    BadCredentials(String),
+    /// The spreadsheet does not exist.
+    NotFound,
+    /// Too many requests were made recently, even after retrying.
+    RateLimited,
+    /// The API is down, or failed to handle the request, even after retrying.
+    Unavailable,
+    /// The access token could not be parsed.
+    InvalidResponse(String),
+}
+
+impl error::Error for UpdateValuesError {}
+
+impl fmt::Display for UpdateValuesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RequestFailure(reason) => {
+                write!(f, "failed to update spreadsheet values: {}", reason)
+            }
+            Self::MissingPermissions => {
+                write!(f, "missing required permissions to edit this resource")
+            }
+            Self::BadCredentials(reason) => {
+                write!(f, "the credentials were rejected: {}", reason)
+            }
+            Self::NotFound => write!(f, "the spreadsheet does not exist"),
+            Self::RateLimited => write!(f, "too many requests were made to the sheets api"),
+            Self::Unavailable => write!(f, "the sheets api is unavailable"),
+            Self::InvalidResponse(reason) => {
+                write!(f, "failed to parse access token: {}", reason)
+            }
+        }
+    }
+}
+
+impl From<RefreshAccessTokenError> for UpdateValuesError {
+    fn from(err: RefreshAccessTokenError) -> Self {
+        match err {
+            RefreshAccessTokenError::RequestFailure(reason) => Self::RequestFailure(reason),
+            RefreshAccessTokenError::BadCredentials(reason) => Self::BadCredentials(reason),
+            RefreshAccessTokenError::RateLimited => Self::RateLimited,
+            RefreshAccessTokenError::Unavailable => Self::Unavailable,
+            RefreshAccessTokenError::InvalidResponse(reason) => Self::InvalidResponse(reason),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Runs the client against a fake of the Sheets API and the token endpoint,
+//! using each method of authentication.
+
+mod common;
+
+use common::{
+    fake::{FakeGoogle, API_KEY, CLIENT_ID, CLIENT_SECRET, READ_WRITE_TOKEN, REFRESH_TOKEN},
+    get_credentials,
+};
+use sheets::{
+    get_values, update_values, ApiKey, Auth, ClientBuilder, GetValuesError, RefreshToken,
+    RetryPolicy, Scope, ServiceAccount, StaticToken, UpdateValuesError,
+};
+
+const SPREADSHEET_ID: &str = "spreadsheet";
+const RANGE: &str = "Sheet1!A1:B2";
+
+fn get_client(google: &FakeGoogle, auth: impl Auth + 'static) -> sheets::Client {
+    ClientBuilder::new(auth)
+        .api_url(google.api_url())
+        .token_url(google.token_url())
+        .retry(RetryPolicy::never())
+        .build()
+}
+
+/// Write to the spreadsheet, then read back what was written.
+async fn write_and_read(client: &sheets::Client) -> Vec<Vec<String>> {
+    let values = vec![vec!["a".to_string()]];
+    update_values(client, SPREADSHEET_ID, RANGE, values)
+        .await
+        .unwrap();
+
+    get_values(client, SPREADSHEET_ID, RANGE)
+        .await
+        .unwrap()
+        .values
+}
+
+#[tokio::test]
+async fn service_accounts_can_act_on_behalf_of_users() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+
+    let credentials = get_credentials(&google.server);
+    let auth = ServiceAccount::new(credentials, Scope::Spreadsheets).subject("owner@example.com");
+    let client = get_client(&google, auth);
+
+    assert_eq!(write_and_read(&client).await, vec![vec!["a"]]);
+
+    let claims = google.claims().await;
+    assert_eq!(claims.len(), 1);
+    assert_eq!(claims[0]["sub"], "owner@example.com");
+}
+
+#[tokio::test]
+async fn service_accounts_act_as_themselves_by_default() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+
+    let credentials = get_credentials(&google.server);
+    let auth = ServiceAccount::new(credentials, Scope::SpreadsheetsReadOnly);
+    let client = get_client(&google, auth);
+    get_values::<Vec<String>>(&client, SPREADSHEET_ID, RANGE)
+        .await
+        .unwrap();
+
+    let claims = google.claims().await;
+    assert!(claims[0].get("sub").is_none());
+}
+
+#[tokio::test]
+async fn refresh_tokens_are_exchanged_once() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+
+    let auth = RefreshToken::new(CLIENT_ID, CLIENT_SECRET, REFRESH_TOKEN);
+    let client = get_client(&google, auth);
+
+    assert_eq!(write_and_read(&client).await, vec![vec!["a"]]);
+    assert_eq!(google.count_requests("/token").await, 1);
+}
+
+#[tokio::test]
+async fn revoked_refresh_tokens_are_bad_credentials() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+
+    let auth = RefreshToken::new(CLIENT_ID, CLIENT_SECRET, "revoked");
+    let client = get_client(&google, auth);
+    let err = get_values::<Vec<String>>(&client, SPREADSHEET_ID, RANGE)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, GetValuesError::BadCredentials(..)), "{err:?}");
+}
+
+#[tokio::test]
+async fn api_keys_can_only_read_public_spreadsheets() {
+    let google = FakeGoogle::start().await;
+    google.set_values("public", RANGE, vec![vec!["a"]]);
+    google.publish("public");
+    google.add_spreadsheet("private");
+
+    let client = get_client(&google, ApiKey::new(API_KEY));
+
+    let range = get_values::<Vec<String>>(&client, "public", RANGE)
+        .await
+        .unwrap();
+    assert_eq!(range.values, vec![vec!["a"]]);
+
+    let err = get_values::<Vec<String>>(&client, "private", RANGE)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GetValuesError::MissingPermissions), "{err:?}");
+
+    let err = update_values(&client, "public", RANGE, vec![])
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, UpdateValuesError::BadCredentials(..)),
+        "{err:?}"
+    );
+
+    // API keys are never exchanged for tokens.
+    assert_eq!(google.count_requests("/token").await, 0);
+}
+
+#[tokio::test]
+async fn static_tokens_are_sent_as_is() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+
+    let client = get_client(&google, StaticToken::new(READ_WRITE_TOKEN));
+
+    assert_eq!(write_and_read(&client).await, vec![vec!["a"]]);
+    assert_eq!(google.count_requests("/token").await, 0);
+}
@@ -0,0 +1,273 @@
+//! Runs the client against a fake of the Sheets API and the token endpoint,
+//! configured using the builder.
+
+mod common;
+
+use std::time::Duration;
+
+use common::{fake::FakeGoogle, get_credentials};
+use serde_json::json;
+use sheets::{
+    get_spreadsheet, get_values, get_values_with_options, update_values, A1Range, CellValue,
+    Client, Dimension, GetValuesError, GetValuesOptions, RetryPolicy, Scope, UpdateValuesError,
+};
+use wiremock::{
+    matchers::{path, query_param},
+    Mock, ResponseTemplate,
+};
+
+const SPREADSHEET_ID: &str = "spreadsheet";
+const RANGE: &str = "Sheet1!A1:B2";
+
+fn get_client(google: &FakeGoogle, scope: Scope) -> Client {
+    Client::builder(get_credentials(&google.server), scope)
+        .api_url(google.api_url())
+        .retry(RetryPolicy::never())
+        .build()
+}
+
+async fn read(client: &Client) -> Result<Vec<Vec<String>>, GetValuesError> {
+    get_values(client, SPREADSHEET_ID, RANGE)
+        .await
+        .map(|range| range.values)
+}
+
+#[tokio::test]
+async fn values_are_read() {
+    let google = FakeGoogle::start().await;
+    google.set_values(SPREADSHEET_ID, RANGE, vec![vec!["a", "b"], vec!["c"]]);
+
+    let client = get_client(&google, Scope::SpreadsheetsReadOnly);
+
+    assert_eq!(
+        read(&client).await.unwrap(),
+        vec![vec!["a", "b"], vec!["c"]]
+    );
+}
+
+#[tokio::test]
+async fn values_are_written() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+
+    let client = get_client(&google, Scope::Spreadsheets);
+    let values = vec![vec!["1".to_string(), "2".to_string()]];
+    update_values(&client, SPREADSHEET_ID, RANGE, values)
+        .await
+        .unwrap();
+
+    assert_eq!(read(&client).await.unwrap(), vec![vec!["1", "2"]]);
+}
+
+#[tokio::test]
+async fn read_only_clients_cannot_write() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+
+    let client = get_client(&google, Scope::SpreadsheetsReadOnly);
+    let err = update_values(&client, SPREADSHEET_ID, RANGE, vec![])
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, UpdateValuesError::MissingPermissions),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn missing_spreadsheets_are_not_found() {
+    let google = FakeGoogle::start().await;
+
+    let client = get_client(&google, Scope::SpreadsheetsReadOnly);
+    let err = read(&client).await.unwrap_err();
+
+    assert!(matches!(err, GetValuesError::NotFound), "{err:?}");
+}
+
+#[tokio::test]
+async fn access_tokens_are_reused() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+
+    let client = get_client(&google, Scope::SpreadsheetsReadOnly);
+    read(&client).await.unwrap();
+    read(&client).await.unwrap();
+
+    assert_eq!(google.count_requests("/token").await, 1);
+}
+
+#[tokio::test]
+async fn token_url_overrides_the_credentials() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+
+    let mut credentials = get_credentials(&google.server);
+    // Nothing listens on the discard port.
+    credentials.token_uri = "http://127.0.0.1:9/token".to_string();
+
+    let client = Client::builder(credentials, Scope::SpreadsheetsReadOnly)
+        .api_url(google.api_url())
+        .token_url(google.token_url())
+        .retry(RetryPolicy::never())
+        .build();
+
+    read(&client).await.unwrap();
+}
+
+#[tokio::test]
+async fn the_http_client_is_used() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+
+    let http = reqwest::Client::builder()
+        .user_agent("sheets-test")
+        .build()
+        .unwrap();
+    let client = Client::builder(get_credentials(&google.server), Scope::Spreadsheets)
+        .api_url(google.api_url())
+        .http_client(http)
+        .build();
+    read(&client).await.unwrap();
+
+    let requests = google.server.received_requests().await.unwrap();
+    assert!(requests
+        .iter()
+        .all(|request| request.headers["User-Agent"] == "sheets-test"));
+}
+
+#[tokio::test]
+async fn slow_responses_time_out() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+    Mock::given(path(format!(
+        "/v4/spreadsheets/{SPREADSHEET_ID}/values/{RANGE}"
+    )))
+    .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+    .with_priority(1)
+    .mount(&google.server)
+    .await;
+
+    let client = Client::builder(get_credentials(&google.server), Scope::Spreadsheets)
+        .api_url(google.api_url())
+        .timeout(Duration::from_millis(200))
+        .retry(RetryPolicy::never())
+        .build();
+    let err = read(&client).await.unwrap_err();
+
+    assert!(matches!(err, GetValuesError::RequestFailure(..)), "{err:?}");
+}
+
+#[tokio::test]
+async fn spreadsheets_are_described() {
+    let google = FakeGoogle::start().await;
+    google.add_sheet(SPREADSHEET_ID, "January");
+    google.add_sheet(SPREADSHEET_ID, "Sept");
+
+    let client = get_client(&google, Scope::SpreadsheetsReadOnly);
+    let spreadsheet = get_spreadsheet(&client, SPREADSHEET_ID).await.unwrap();
+
+    assert_eq!(spreadsheet.spreadsheet_id, SPREADSHEET_ID);
+    assert_eq!(
+        spreadsheet.sheet_titles().collect::<Vec<_>>(),
+        vec!["January", "Sept"]
+    );
+    let grid = spreadsheet.sheets[1].properties.grid_properties.as_ref();
+    assert_eq!(grid.map(|grid| grid.column_count), Some(26));
+}
+
+#[tokio::test]
+async fn unformatted_values_are_typed() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+    // The fake only stores text, so respond like the API does for raw values.
+    Mock::given(path(format!(
+        "/v4/spreadsheets/{SPREADSHEET_ID}/values/{RANGE}"
+    )))
+    .and(query_param("valueRenderOption", "UNFORMATTED_VALUE"))
+    .and(query_param("dateTimeRenderOption", "SERIAL_NUMBER"))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+        "range": RANGE,
+        "majorDimension": "ROWS",
+        "values": [[45678.5, 1200, "Airbnb", true, ""]],
+    })))
+    .with_priority(1)
+    .mount(&google.server)
+    .await;
+
+    let client = get_client(&google, Scope::SpreadsheetsReadOnly);
+    let range = get_values_with_options::<Vec<CellValue>>(
+        &client,
+        SPREADSHEET_ID,
+        RANGE,
+        &GetValuesOptions::unformatted(),
+    )
+    .await
+    .unwrap();
+
+    let row = &range.values[0];
+    assert_eq!(
+        row[0].as_datetime().map(|datetime| datetime.to_string()),
+        Some("2025-01-21 12:00:00".to_string())
+    );
+    assert_eq!(row[1].as_f64(), Some(1200.0));
+    assert_eq!(row[2].as_str(), Some("Airbnb"));
+    assert_eq!(row[3].as_bool(), Some(true));
+    assert!(row[4].is_empty());
+}
+
+#[tokio::test]
+async fn values_are_formatted_by_default() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+
+    let client = get_client(&google, Scope::SpreadsheetsReadOnly);
+    read(&client).await.unwrap();
+
+    let requests = google.server.received_requests().await.unwrap();
+    let request = requests
+        .iter()
+        .find(|request| request.url.path().contains("/values/"))
+        .unwrap();
+    assert!(request
+        .url
+        .query_pairs()
+        .any(|(name, value)| name == "valueRenderOption" && value == "FORMATTED_VALUE"));
+}
+
+#[tokio::test]
+async fn ranges_with_special_characters_are_encoded() {
+    let google = FakeGoogle::start().await;
+    google.add_spreadsheet(SPREADSHEET_ID);
+
+    let range = A1Range::new("Owner's Jan #1").columns(1, 2).rows(1, 2);
+    let client = get_client(&google, Scope::Spreadsheets);
+    let values = vec![vec!["a".to_string(), "b".to_string()]];
+    update_values(&client, SPREADSHEET_ID, &range.to_string(), values)
+        .await
+        .unwrap();
+
+    let read = get_values::<Vec<String>>(&client, SPREADSHEET_ID, &range.to_string())
+        .await
+        .unwrap();
+    assert_eq!(read.range, "'Owner''s Jan #1'!A1:B2");
+    assert_eq!(read.values, vec![vec!["a", "b"]]);
+}
+
+#[tokio::test]
+async fn values_can_be_read_by_column() {
+    let google = FakeGoogle::start().await;
+    google.set_values(SPREADSHEET_ID, RANGE, vec![vec!["a", "b"], vec!["c", "d"]]);
+
+    let client = get_client(&google, Scope::SpreadsheetsReadOnly);
+    let options = GetValuesOptions {
+        major_dimension: Dimension::Columns,
+        ..GetValuesOptions::default()
+    };
+    let range = get_values_with_options::<Vec<String>>(&client, SPREADSHEET_ID, RANGE, &options)
+        .await
+        .unwrap();
+
+    assert_eq!(range.major_dimension, Dimension::Columns);
+    assert_eq!(range.values, vec![vec!["a", "c"], vec!["b", "d"]]);
+}
@@ -0,0 +1,428 @@
+//! A fake of the Google Sheets API and OAuth token endpoint, keeping the
+//! values of spreadsheets in memory.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use base64::prelude::*;
+use serde_json::{json, Value};
+use wiremock::{
+    matchers::{method, path, path_regex},
+    Mock, MockServer, Request, Respond, ResponseTemplate,
+};
+
+/// The access token issued for the read-only scope.
+pub const READ_ONLY_TOKEN: &str = "read-only-token";
+/// The access token issued for every other scope, and to users.
+pub const READ_WRITE_TOKEN: &str = "read-write-token";
+
+/// The OAuth2 client users granted refresh tokens to.
+pub const CLIENT_ID: &str = "client-id";
+pub const CLIENT_SECRET: &str = "client-secret";
+/// The refresh token granted by a user.
+pub const REFRESH_TOKEN: &str = "refresh-token";
+
+/// The API key that can read public spreadsheets.
+pub const API_KEY: &str = "api-key";
+
+type Spreadsheets = Arc<Mutex<HashMap<String, Spreadsheet>>>;
+
+pub struct FakeGoogle {
+    /// The server the fake is mounted on, for adding mocks that take priority
+    /// over the fake.
+    pub server: MockServer,
+    spreadsheets: Spreadsheets,
+}
+
+/// The values of every range written, by range.
+#[derive(Default)]
+struct Spreadsheet {
+    /// Whether anyone can read the spreadsheet (e.g., using an API key).
+    public: bool,
+    /// The titles of the sheets, in order.
+    sheets: Vec<String>,
+    ranges: HashMap<String, Vec<Vec<String>>>,
+    /// The rows of sheets, by the name of the sheet, for reading windows of
+    /// rows (e.g., `Sheet1!A11:C20`).
+    rows: HashMap<String, Vec<Vec<String>>>,
+}
+
+impl FakeGoogle {
+    pub async fn start() -> Self {
+        let server = MockServer::start().await;
+        let spreadsheets = Spreadsheets::default();
+
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(TokenEndpoint {
+                audience: format!("{}/token", server.uri()),
+            })
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex("^/v4/spreadsheets/[^/]+$"))
+            .respond_with(SpreadsheetEndpoint {
+                spreadsheets: Arc::clone(&spreadsheets),
+            })
+            .mount(&server)
+            .await;
+        Mock::given(path_regex("^/v4/spreadsheets/[^/]+/values/[^/]+$"))
+            .respond_with(ValuesEndpoint {
+                spreadsheets: Arc::clone(&spreadsheets),
+            })
+            .mount(&server)
+            .await;
+
+        Self {
+            server,
+            spreadsheets,
+        }
+    }
+
+    pub fn api_url(&self) -> String {
+        format!("{}/v4/spreadsheets", self.server.uri())
+    }
+
+    pub fn token_url(&self) -> String {
+        format!("{}/token", self.server.uri())
+    }
+
+    /// Create an empty spreadsheet.
+    pub fn add_spreadsheet(&self, spreadsheet_id: &str) {
+        self.spreadsheets
+            .lock()
+            .unwrap()
+            .entry(spreadsheet_id.to_string())
+            .or_default();
+    }
+
+    /// Add a sheet to the end of the spreadsheet.
+    pub fn add_sheet(&self, spreadsheet_id: &str, title: &str) {
+        self.spreadsheets
+            .lock()
+            .unwrap()
+            .entry(spreadsheet_id.to_string())
+            .or_default()
+            .sheets
+            .push(title.to_string());
+    }
+
+    /// Let anyone read the spreadsheet.
+    pub fn publish(&self, spreadsheet_id: &str) {
+        self.spreadsheets
+            .lock()
+            .unwrap()
+            .entry(spreadsheet_id.to_string())
+            .or_default()
+            .public = true;
+    }
+
+    pub fn set_values(&self, spreadsheet_id: &str, range: &str, values: Vec<Vec<&str>>) {
+        let values = values
+            .into_iter()
+            .map(|row| row.into_iter().map(str::to_string).collect())
+            .collect();
+
+        self.spreadsheets
+            .lock()
+            .unwrap()
+            .entry(spreadsheet_id.to_string())
+            .or_default()
+            .ranges
+            .insert(range.to_string(), values);
+    }
+
+    /// Set the rows of a sheet, starting at the first row.
+    pub fn set_rows(&self, spreadsheet_id: &str, sheet: &str, rows: Vec<Vec<&str>>) {
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(str::to_string).collect())
+            .collect();
+
+        self.spreadsheets
+            .lock()
+            .unwrap()
+            .entry(spreadsheet_id.to_string())
+            .or_default()
+            .rows
+            .insert(sheet.to_string(), rows);
+    }
+
+    /// Get the ranges of every request for values, in order.
+    pub async fn requested_ranges(&self) -> Vec<String> {
+        let requests = self.server.received_requests().await.unwrap();
+        requests
+            .iter()
+            .filter_map(|request| {
+                let (_, range) = request.url.path().split_once("/values/")?;
+                Some(decode_path_segment(range))
+            })
+            .collect()
+    }
+
+    /// Get the claim of every JWT exchanged for an access token.
+    pub async fn claims(&self) -> Vec<Value> {
+        let requests = self.server.received_requests().await.unwrap();
+        requests
+            .iter()
+            .filter(|request| request.url.path() == "/token")
+            .filter_map(|request| get_claim(&parse_form(&request.body)))
+            .collect()
+    }
+
+    /// Get the number of requests received by the path (e.g., `/token`).
+    pub async fn count_requests(&self, path: &str) -> usize {
+        let requests = self.server.received_requests().await.unwrap();
+        requests
+            .iter()
+            .filter(|request| request.url.path() == path)
+            .count()
+    }
+}
+
+/// Issues access tokens for signed JWTs, like Google does for service
+/// accounts (without verifying the signature), and for refresh tokens.
+struct TokenEndpoint {
+    audience: String,
+}
+
+impl Respond for TokenEndpoint {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let form = parse_form(&request.body);
+
+        let token = match form.get("grant_type").map(String::as_str) {
+            Some("urn:ietf:params:oauth:grant-type:jwt-bearer") => {
+                let Some(claim) = get_claim(&form) else {
+                    return invalid_grant("invalid assertion");
+                };
+                if claim["aud"] != self.audience.as_str() {
+                    return invalid_grant("invalid audience");
+                }
+
+                match claim["scope"].as_str() {
+                    Some(scope) if scope.ends_with(".readonly") => READ_ONLY_TOKEN,
+                    Some(_) => READ_WRITE_TOKEN,
+                    None => return invalid_grant("missing scope"),
+                }
+            }
+            Some("refresh_token") => {
+                let is_valid = form.get("client_id").map(String::as_str) == Some(CLIENT_ID)
+                    && form.get("client_secret").map(String::as_str) == Some(CLIENT_SECRET)
+                    && form.get("refresh_token").map(String::as_str) == Some(REFRESH_TOKEN);
+                if !is_valid {
+                    return invalid_grant("invalid refresh token");
+                }
+
+                READ_WRITE_TOKEN
+            }
+            _ => return invalid_grant("unsupported grant type"),
+        };
+
+        ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": token,
+            "expires_in": 3600,
+            "token_type": "Bearer",
+        }))
+    }
+}
+
+fn parse_form(body: &[u8]) -> HashMap<String, String> {
+    let body = String::from_utf8_lossy(body);
+    // Forms are encoded like query strings.
+    let url = reqwest::Url::parse(&format!("http://localhost/?{body}")).unwrap();
+    url.query_pairs().into_owned().collect()
+}
+
+/// Get the decoded claim of the JWT in a form, if valid.
+fn get_claim(form: &HashMap<String, String>) -> Option<Value> {
+    let claim = form.get("assertion")?.split('.').nth(1)?;
+    let claim = BASE64_URL_SAFE_NO_PAD.decode(claim).ok()?;
+    serde_json::from_slice(&claim).ok()
+}
+
+fn invalid_grant(description: &str) -> ResponseTemplate {
+    ResponseTemplate::new(400).set_body_json(json!({
+        "error": "invalid_grant",
+        "error_description": description,
+    }))
+}
+
+/// Returns the properties of a spreadsheet and its sheets.
+struct SpreadsheetEndpoint {
+    spreadsheets: Spreadsheets,
+}
+
+impl Respond for SpreadsheetEndpoint {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        if request.headers.get("Authorization").is_none() {
+            return ResponseTemplate::new(401);
+        }
+
+        let spreadsheet_id = request.url.path().rsplit('/').next().unwrap();
+        let spreadsheets = self.spreadsheets.lock().unwrap();
+        let Some(spreadsheet) = spreadsheets.get(spreadsheet_id) else {
+            return ResponseTemplate::new(404);
+        };
+
+        let sheets: Vec<Value> = spreadsheet
+            .sheets
+            .iter()
+            .enumerate()
+            .map(|(index, title)| {
+                json!({"properties": {
+                    "sheetId": index * 100,
+                    "title": title,
+                    "index": index,
+                    "sheetType": "GRID",
+                    "gridProperties": {"rowCount": 1000, "columnCount": 26},
+                }})
+            })
+            .collect();
+
+        ResponseTemplate::new(200).set_body_json(json!({
+            "spreadsheetId": spreadsheet_id,
+            "properties": {"title": spreadsheet_id, "locale": "en_US"},
+            "sheets": sheets,
+        }))
+    }
+}
+
+/// Reads and writes the values of ranges, without interpreting the ranges;
+/// values can only be read from exactly the range they were written to.
+struct ValuesEndpoint {
+    spreadsheets: Spreadsheets,
+}
+
+impl Respond for ValuesEndpoint {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let token = request
+            .headers
+            .get("Authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        let key = request
+            .url
+            .query_pairs()
+            .find(|(name, _)| name == "key")
+            .map(|(_, value)| value.into_owned());
+        let access = match (token, key.as_deref()) {
+            (Some(READ_ONLY_TOKEN), _) => Access::Read,
+            (Some(READ_WRITE_TOKEN), _) => Access::Write,
+            (None, Some(API_KEY)) => Access::Public,
+            _ => return ResponseTemplate::new(401),
+        };
+
+        let segments: Vec<&str> = request.url.path().split('/').collect();
+        let spreadsheet_id = segments[3];
+        let range = &decode_path_segment(segments[5]);
+
+        let mut spreadsheets = self.spreadsheets.lock().unwrap();
+        let Some(spreadsheet) = spreadsheets.get_mut(spreadsheet_id) else {
+            return ResponseTemplate::new(404);
+        };
+        if access == Access::Public && !spreadsheet.public {
+            return ResponseTemplate::new(403);
+        }
+
+        match request.method.as_str() {
+            "GET" => {
+                let dimension = request
+                    .url
+                    .query_pairs()
+                    .find(|(name, _)| name == "majorDimension")
+                    .map_or("ROWS".to_string(), |(_, value)| value.into_owned());
+
+                let mut body = json!({"range": range, "majorDimension": dimension});
+                let values = spreadsheet
+                    .ranges
+                    .get(range)
+                    .cloned()
+                    .or_else(|| get_window(&spreadsheet.rows, range));
+
+                // Like the API, empty ranges have no values.
+                if let Some(values) = values.filter(|values| !values.is_empty()) {
+                    body["values"] = match dimension.as_str() {
+                        "COLUMNS" => json!(transpose(&values)),
+                        _ => json!(values),
+                    };
+                }
+
+                ResponseTemplate::new(200).set_body_json(body)
+            }
+            // API keys cannot be used to change anything.
+            "PUT" if access == Access::Public => ResponseTemplate::new(401),
+            "PUT" if access == Access::Read => ResponseTemplate::new(403),
+            "PUT" => {
+                let Ok(body) = serde_json::from_slice::<Value>(&request.body) else {
+                    return ResponseTemplate::new(400);
+                };
+                let Ok(values) = serde_json::from_value(body["values"].clone()) else {
+                    return ResponseTemplate::new(400);
+                };
+                spreadsheet.ranges.insert(range.to_string(), values);
+
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "spreadsheetId": spreadsheet_id,
+                    "updatedRange": range,
+                }))
+            }
+            _ => ResponseTemplate::new(405),
+        }
+    }
+}
+
+/// Get the rows of a window of a sheet (e.g., `Sheet1!A11:C20`), ignoring
+/// the columns; like the API, empty rows at the end are left out.
+fn get_window(sheets: &HashMap<String, Vec<Vec<String>>>, range: &str) -> Option<Vec<Vec<String>>> {
+    let (sheet, cells) = range.rsplit_once('!')?;
+    let (first, last) = cells.split_once(':')?;
+    let row = |cell: &str| -> Option<usize> {
+        cell.trim_start_matches(|c: char| c.is_ascii_alphabetic())
+            .parse()
+            .ok()
+    };
+    let (first, last) = (row(first)?, row(last)?);
+
+    let rows = sheets.get(sheet)?;
+    let mut window: Vec<Vec<String>> = rows
+        .iter()
+        .skip(first - 1)
+        .take(last + 1 - first)
+        .cloned()
+        .collect();
+    while window.last().is_some_and(Vec::is_empty) {
+        window.pop();
+    }
+
+    Some(window)
+}
+
+/// Decode a percent-encoded segment of a path (e.g., a range).
+fn decode_path_segment(segment: &str) -> String {
+    // Forms are percent-encoded too, and pluses are always encoded in paths.
+    parse_form(format!("segment={segment}").as_bytes())["segment"].to_string()
+}
+
+/// Turn rows into columns, leaving out the cells missing from short rows.
+fn transpose(rows: &[Vec<String>]) -> Vec<Vec<String>> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+    (0..width)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column).cloned())
+                .collect()
+        })
+        .collect()
+}
+
+/// What the credentials of a request allow.
+#[derive(PartialEq)]
+enum Access {
+    /// Reading public spreadsheets (i.e., using an API key).
+    Public,
+    Read,
+    Write,
+}
@@ -0,0 +1,46 @@
+//! Helpers shared by the tests that run against a mock server.
+
+#![allow(dead_code)]
+
+pub mod fake;
+
+use openssl::rsa::Rsa;
+use serde_json::json;
+use sheets::ServiceAccountKey;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+/// Get a service account key that requests tokens from the mock server.
+pub fn get_credentials(server: &MockServer) -> ServiceAccountKey {
+    let rsa = Rsa::generate(2048).unwrap();
+    let private_key = String::from_utf8(rsa.private_key_to_pem().unwrap()).unwrap();
+
+    ServiceAccountKey {
+        r#type: "service_account".to_string(),
+        project_id: "project".to_string(),
+        private_key_id: "key".to_string(),
+        private_key,
+        client_email: "robot@project.iam.gserviceaccount.com".to_string(),
+        client_id: "1".to_string(),
+        auth_uri: format!("{}/auth", server.uri()),
+        token_uri: format!("{}/token", server.uri()),
+        auth_provider_x509_cert_url: String::new(),
+        client_x509_cert_url: String::new(),
+        universe_domain: "googleapis.com".to_string(),
+    }
+}
+
+/// Respond to every token request with a valid access token.
+pub async fn mock_token(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "token",
+            "expires_in": 3600,
+            "token_type": "Bearer",
+        })))
+        .mount(server)
+        .await;
+}
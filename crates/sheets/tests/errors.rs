@@ -0,0 +1,177 @@
+//! Checks that failed requests to the Sheets API and the token endpoint are
+//! returned as errors describing what went wrong, instead of panicking.
+
+mod common;
+
+use common::{get_credentials, mock_token};
+use serde_json::json;
+use sheets::{
+    get_values, update_values, Client, GetValuesError, RetryPolicy, Scope, UpdateValuesError,
+};
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const SPREADSHEET_ID: &str = "spreadsheet";
+const RANGE: &str = "Sheet1!A1:B2";
+
+/// Checks whether an error is the one expected for a case.
+type Expected<E> = fn(&E) -> bool;
+
+fn get_client(server: &MockServer) -> Client {
+    Client::builder(get_credentials(server), Scope::Spreadsheets)
+        .api_url(server.uri())
+        // Retries are covered by their own tests.
+        .retry(RetryPolicy::never())
+        .build()
+}
+
+async fn mock_values(server: &MockServer, response: ResponseTemplate) {
+    Mock::given(path(format!("/{SPREADSHEET_ID}/values/{RANGE}")))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+async fn get_values_with(response: ResponseTemplate) -> Result<Vec<Vec<String>>, GetValuesError> {
+    let server = MockServer::start().await;
+    mock_token(&server).await;
+    mock_values(&server, response).await;
+
+    let client = get_client(&server);
+    get_values(&client, SPREADSHEET_ID, RANGE)
+        .await
+        .map(|range| range.values)
+}
+
+#[tokio::test]
+async fn get_values_returns_values() {
+    let response = ResponseTemplate::new(200).set_body_json(json!({
+        "range": RANGE,
+        "majorDimension": "ROWS",
+        "values": [["a", "b"], ["c", "d"]],
+    }));
+
+    let values = get_values_with(response).await.unwrap();
+    assert_eq!(values, vec![vec!["a", "b"], vec!["c", "d"]]);
+}
+
+#[tokio::test]
+async fn get_values_returns_nothing_for_an_empty_range() {
+    let response = ResponseTemplate::new(200).set_body_json(json!({
+        "range": RANGE,
+        "majorDimension": "ROWS",
+    }));
+
+    let values = get_values_with(response).await.unwrap();
+    assert!(values.is_empty());
+}
+
+#[tokio::test]
+async fn get_values_maps_statuses_to_errors() {
+    let cases: [(u16, Expected<GetValuesError>); 6] = [
+        (400, |err| matches!(err, GetValuesError::InvalidRange(..))),
+        (401, |err| matches!(err, GetValuesError::BadCredentials(..))),
+        (403, |err| matches!(err, GetValuesError::MissingPermissions)),
+        (404, |err| matches!(err, GetValuesError::NotFound)),
+        (429, |err| matches!(err, GetValuesError::RateLimited)),
+        (503, |err| matches!(err, GetValuesError::Unavailable)),
+    ];
+
+    for (status, is_expected) in cases {
+        let err = get_values_with(ResponseTemplate::new(status))
+            .await
+            .unwrap_err();
+        assert!(is_expected(&err), "{status} returned {err:?}");
+    }
+}
+
+#[tokio::test]
+async fn get_values_rejects_an_invalid_body() {
+    let response = ResponseTemplate::new(200).set_body_string("<html>not json</html>");
+
+    let err = get_values_with(response).await.unwrap_err();
+    assert!(
+        matches!(err, GetValuesError::InvalidResponse(..)),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn get_values_maps_token_errors() {
+    let cases: [(ResponseTemplate, Expected<GetValuesError>); 4] = [
+        (
+            ResponseTemplate::new(400).set_body_json(json!({"error": "invalid_grant"})),
+            |err| matches!(err, GetValuesError::BadCredentials(..)),
+        ),
+        (ResponseTemplate::new(429), |err| {
+            matches!(err, GetValuesError::RateLimited)
+        }),
+        (ResponseTemplate::new(500), |err| {
+            matches!(err, GetValuesError::Unavailable)
+        }),
+        (
+            ResponseTemplate::new(200).set_body_json(json!({"token_type": "Bearer"})),
+            |err| matches!(err, GetValuesError::InvalidResponse(..)),
+        ),
+    ];
+
+    for (response, is_expected) in cases {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(response)
+            .mount(&server)
+            .await;
+
+        let client = get_client(&server);
+        let err = get_values::<Vec<String>>(&client, SPREADSHEET_ID, RANGE)
+            .await
+            .unwrap_err();
+        assert!(is_expected(&err), "{err:?}");
+    }
+}
+
+#[tokio::test]
+async fn get_values_rejects_an_invalid_private_key() {
+    let server = MockServer::start().await;
+
+    let mut credentials = get_credentials(&server);
+    credentials.private_key = "not a key".to_string();
+    let client = Client::builder(credentials, Scope::Spreadsheets)
+        .api_url(server.uri())
+        .build();
+
+    let err = get_values::<Vec<String>>(&client, SPREADSHEET_ID, RANGE)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GetValuesError::BadCredentials(..)), "{err:?}");
+    // The key is rejected before anything is sent.
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn update_values_maps_statuses_to_errors() {
+    let cases: [(u16, Expected<UpdateValuesError>); 4] = [
+        (403, |err| {
+            matches!(err, UpdateValuesError::MissingPermissions)
+        }),
+        (404, |err| matches!(err, UpdateValuesError::NotFound)),
+        (429, |err| matches!(err, UpdateValuesError::RateLimited)),
+        (502, |err| matches!(err, UpdateValuesError::Unavailable)),
+    ];
+
+    for (status, is_expected) in cases {
+        let server = MockServer::start().await;
+        mock_token(&server).await;
+        mock_values(&server, ResponseTemplate::new(status)).await;
+
+        let client = get_client(&server);
+        let values = vec![vec!["a".to_string()]];
+        let err = update_values(&client, SPREADSHEET_ID, RANGE, values)
+            .await
+            .unwrap_err();
+        assert!(is_expected(&err), "{status} returned {err:?}");
+    }
+}
@@ -0,0 +1,43 @@
+//! Checks how ranges are written in A1 notation.
+
+use sheets::{column_letters, A1Range};
+
+#[test]
+fn columns_are_lettered() {
+    assert_eq!(column_letters(1), "A");
+    assert_eq!(column_letters(7), "G");
+    assert_eq!(column_letters(26), "Z");
+    assert_eq!(column_letters(27), "AA");
+    assert_eq!(column_letters(703), "AAA");
+}
+
+#[test]
+fn plain_sheet_names_are_not_quoted() {
+    assert_eq!(A1Range::new("January").to_string(), "January");
+    assert_eq!(
+        A1Range::new("January").columns(1, 7).to_string(),
+        "January!A:G"
+    );
+}
+
+#[test]
+fn other_sheet_names_are_quoted() {
+    assert_eq!(A1Range::new("Jan 2025").to_string(), "'Jan 2025'");
+    assert_eq!(A1Range::new("A1").to_string(), "'A1'");
+    assert_eq!(
+        A1Range::new("Owner's Expenses").columns(1, 8).to_string(),
+        "'Owner''s Expenses'!A:H"
+    );
+}
+
+#[test]
+fn rows_can_be_bounded_or_open() {
+    let range = A1Range::new("Expenses").columns(1, 9);
+
+    assert_eq!(range.clone().rows(2, 501).to_string(), "Expenses!A2:I501");
+    assert_eq!(range.rows_from(2).to_string(), "Expenses!A2:I");
+    assert_eq!(
+        A1Range::new("Expenses").rows(2, 3).to_string(),
+        "Expenses!2:3"
+    );
+}
@@ -0,0 +1,148 @@
+//! Checks that requests failing with transient errors are retried according
+//! to the retry policy of the client.
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{get_credentials, mock_token};
+use serde_json::json;
+use sheets::{get_values, Client, GetValuesError, RetryPolicy, Scope};
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const SPREADSHEET_ID: &str = "spreadsheet";
+const RANGE: &str = "Sheet1!A1:B2";
+
+fn get_client(server: &MockServer, max_attempts: u32) -> Client {
+    Client::builder(get_credentials(server), Scope::SpreadsheetsReadOnly)
+        .api_url(server.uri())
+        .retry(RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            ..RetryPolicy::default()
+        })
+        .build()
+}
+
+fn values_path() -> String {
+    format!("/{SPREADSHEET_ID}/values/{RANGE}")
+}
+
+fn values() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "range": RANGE,
+        "majorDimension": "ROWS",
+        "values": [["a"]],
+    }))
+}
+
+/// Respond to the first `times` requests for values with `response`, and to
+/// every request after that with values.
+async fn mock_values_failing(server: &MockServer, response: ResponseTemplate, times: u64) {
+    Mock::given(path(values_path()))
+        .respond_with(response)
+        .up_to_n_times(times)
+        .with_priority(1)
+        .mount(server)
+        .await;
+    Mock::given(path(values_path()))
+        .respond_with(values())
+        .mount(server)
+        .await;
+}
+
+async fn count_requests(server: &MockServer, path: &str) -> usize {
+    let requests = server.received_requests().await.unwrap();
+    requests
+        .iter()
+        .filter(|request| request.url.path() == path)
+        .count()
+}
+
+#[tokio::test]
+async fn transient_failures_are_retried() {
+    let server = MockServer::start().await;
+    mock_token(&server).await;
+    mock_values_failing(&server, ResponseTemplate::new(503), 2).await;
+
+    let client = get_client(&server, 3);
+    let range = get_values::<Vec<String>>(&client, SPREADSHEET_ID, RANGE)
+        .await
+        .unwrap();
+
+    assert_eq!(range.values, vec![vec!["a"]]);
+    assert_eq!(count_requests(&server, &values_path()).await, 3);
+}
+
+#[tokio::test]
+async fn failures_are_returned_after_the_last_attempt() {
+    let server = MockServer::start().await;
+    mock_token(&server).await;
+    mock_values_failing(&server, ResponseTemplate::new(429), 3).await;
+
+    let client = get_client(&server, 3);
+    let err = get_values::<Vec<String>>(&client, SPREADSHEET_ID, RANGE)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, GetValuesError::RateLimited), "{err:?}");
+    assert_eq!(count_requests(&server, &values_path()).await, 3);
+}
+
+#[tokio::test]
+async fn other_failures_are_not_retried() {
+    let server = MockServer::start().await;
+    mock_token(&server).await;
+    mock_values_failing(&server, ResponseTemplate::new(403), 1).await;
+
+    let client = get_client(&server, 3);
+    let err = get_values::<Vec<String>>(&client, SPREADSHEET_ID, RANGE)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, GetValuesError::MissingPermissions), "{err:?}");
+    assert_eq!(count_requests(&server, &values_path()).await, 1);
+}
+
+#[tokio::test]
+async fn retry_after_is_respected() {
+    let server = MockServer::start().await;
+    mock_token(&server).await;
+    let response = ResponseTemplate::new(429).insert_header("Retry-After", "1");
+    mock_values_failing(&server, response, 1).await;
+
+    let client = get_client(&server, 2);
+    let start = Instant::now();
+    get_values::<Vec<String>>(&client, SPREADSHEET_ID, RANGE)
+        .await
+        .unwrap();
+
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn token_requests_are_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/token"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    mock_token(&server).await;
+    Mock::given(path(values_path()))
+        .respond_with(values())
+        .mount(&server)
+        .await;
+
+    let client = get_client(&server, 2);
+    get_values::<Vec<String>>(&client, SPREADSHEET_ID, RANGE)
+        .await
+        .unwrap();
+
+    assert_eq!(count_requests(&server, "/token").await, 2);
+}
@@ -0,0 +1,91 @@
+//! Reads large sheets a window of rows at a time from a fake of the Sheets
+//! API.
+
+mod common;
+
+use common::{fake::FakeGoogle, get_credentials};
+use sheets::{A1Range, Client, RetryPolicy, RowReader, Scope};
+
+const SPREADSHEET_ID: &str = "spreadsheet";
+
+fn get_client(google: &FakeGoogle) -> Client {
+    Client::builder(get_credentials(&google.server), Scope::SpreadsheetsReadOnly)
+        .api_url(google.api_url())
+        .retry(RetryPolicy::never())
+        .build()
+}
+
+/// Read every remaining window, returning each row along with its number.
+async fn read_all(reader: &mut RowReader, client: &Client) -> Vec<(u32, Vec<String>)> {
+    let mut rows = Vec::new();
+    while let Some(window) = reader.next::<Vec<String>>(client).await.unwrap() {
+        rows.extend(window.numbered());
+    }
+
+    rows
+}
+
+#[tokio::test]
+async fn rows_are_read_a_window_at_a_time() {
+    let google = FakeGoogle::start().await;
+    let rows: Vec<String> = (1..=25).map(|row| row.to_string()).collect();
+    google.set_rows(
+        SPREADSHEET_ID,
+        "Expenses",
+        rows.iter().map(|row| vec![row.as_str()]).collect(),
+    );
+
+    let client = get_client(&google);
+    let mut reader =
+        RowReader::new(SPREADSHEET_ID, A1Range::new("Expenses").columns(1, 2)).window(10);
+    let read = read_all(&mut reader, &client).await;
+
+    assert_eq!(read.len(), 25);
+    assert_eq!(read[0], (1, vec!["1".to_string()]));
+    assert_eq!(read[24], (25, vec!["25".to_string()]));
+    assert_eq!(reader.next_row(), 26);
+    assert_eq!(
+        google.requested_ranges().await,
+        vec!["Expenses!A1:B10", "Expenses!A11:B20", "Expenses!A21:B30"]
+    );
+}
+
+#[tokio::test]
+async fn reading_resumes_after_the_last_row() {
+    let google = FakeGoogle::start().await;
+    google.set_rows(SPREADSHEET_ID, "Expenses", vec![vec!["1"], vec!["2"]]);
+
+    let client = get_client(&google);
+    let range = A1Range::new("Expenses").columns(1, 2);
+    let mut reader = RowReader::new(SPREADSHEET_ID, range.clone()).window(10);
+    read_all(&mut reader, &client).await;
+    let next_row = reader.next_row();
+
+    google.set_rows(
+        SPREADSHEET_ID,
+        "Expenses",
+        vec![vec!["1"], vec!["2"], vec!["3"]],
+    );
+    let mut reader = RowReader::new(SPREADSHEET_ID, range)
+        .window(10)
+        .from_row(next_row);
+    let read = read_all(&mut reader, &client).await;
+
+    assert_eq!(read, vec![(3, vec!["3".to_string()])]);
+    assert_eq!(reader.next_row(), 4);
+}
+
+#[tokio::test]
+async fn full_windows_are_followed_by_another_read() {
+    let google = FakeGoogle::start().await;
+    google.set_rows(SPREADSHEET_ID, "Expenses", vec![vec!["1"], vec!["2"]]);
+
+    let client = get_client(&google);
+    let mut reader =
+        RowReader::new(SPREADSHEET_ID, A1Range::new("Expenses").columns(1, 1)).window(2);
+    let read = read_all(&mut reader, &client).await;
+
+    assert_eq!(read.len(), 2);
+    assert_eq!(reader.next_row(), 3);
+    assert_eq!(google.requested_ranges().await.len(), 2);
+}
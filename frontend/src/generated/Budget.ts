@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BudgetPeriod } from "./BudgetPeriod";
+
+/**
+ * A limit on how much can be spent on a property.
+ */
+export type Budget = { id: string, property_id: string, 
+/**
+ * The category of expenses the budget applies to; `None` applies the
+ * budget to every expense.
+ */
+category: string | null, period: BudgetPeriod, amount: number, 
+/**
+ * The fractions of the budget (e.g., `0.8` for 80%) that trigger an
+ * alert when spending reaches them.
+ */
+thresholds: Array<number>, };
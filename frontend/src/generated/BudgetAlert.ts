@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Triggered the first time spending reaches one of a budget's thresholds.
+ */
+export type BudgetAlert = { budget_id: string, property_id: string, category: string | null, 
+/**
+ * The period the alert was triggered for (e.g., `2025-03` or `2025`).
+ */
+period: string, threshold: number, spent: number, amount: number, triggered_at: string, };
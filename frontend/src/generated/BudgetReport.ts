@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BudgetAlert } from "./BudgetAlert";
+import type { BudgetStatus } from "./BudgetStatus";
+
+/**
+ * Compares a property's expenses with its budgets.
+ */
+export type BudgetReport = { year: number, month: number, 
+/**
+ * Monthly budgets are compared with the month's expenses; annual budgets
+ * are compared with every expense in the year up to the end of the month.
+ */
+budgets: Array<BudgetStatus>, 
+/**
+ * The alerts that have been triggered for the report's periods.
+ */
+alerts: Array<BudgetAlert>, };
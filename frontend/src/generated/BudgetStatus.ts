@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Budget } from "./Budget";
+
+/**
+ * How much of a budget has been spent.
+ */
+export type BudgetStatus = { budget: Budget, spent: number, remaining: number, 
+/**
+ * The fraction of the budget that has been spent (e.g., `1.5` is 150%).
+ */
+utilization: number, over_budget: boolean, 
+/**
+ * The thresholds that spending has reached.
+ */
+thresholds_reached: Array<number>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The kinds of changes detected in the spreadsheets.
+ */
+export type ChangeKind = "reservation_added" | "reservation_changed" | "reservation_removed" | "expense_added";
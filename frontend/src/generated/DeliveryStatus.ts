@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What happened when trying to deliver a notification.
+ */
+export type DeliveryStatus = "sent" | "failed" | "skipped";
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Expense } from "./Expense";
+import type { Reservation } from "./Reservation";
+
+/**
+ * A change to a property, as delivered to webhooks.
+ */
+export type Event = { id: string, property_id: string, 
+/**
+ * The ID of the user that owns the property.
+ */
+user_id: string, occurred_at: string, } & ({ "type": "reservation_added", 
+/**
+ * The name of the sheet (tab) the reservation was added to.
+ */
+sheet: string, reservation: Reservation, } | { "type": "reservation_changed", sheet: string, before: Reservation, after: Reservation, } | { "type": "reservation_removed", sheet: string, reservation: Reservation, } | { "type": "expense_added", expense: Expense, });
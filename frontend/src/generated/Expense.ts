@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Expense = { amount: number, description: string, timestamp: string, receipt_link: string, merchant: string, buyers_name: string, 
+/**
+ * The category of the expense (e.g., "Supplies"); empty if not set.
+ */
+category: string, 
+/**
+ * The row number of the expense within the expense sheet (starting at 1).
+ */
+row: number, };
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How rows of the shared expense sheet are matched to a property.
+ *
+ * Rows with a property code only match the property with that code (or ID);
+ * older rows without one are matched by the name in the `Property` column,
+ * ignoring case.
+ */
+export type ExpenseMatching = { 
+/**
+ * The code entered in the `Property Code` column (e.g., `LAKE-01`); it
+ * does not change when the property is renamed.
+ */
+code: string | null, 
+/**
+ * Other names the property was entered as (e.g., its name before it was
+ * renamed, or a common misspelling).
+ */
+aliases: Array<string>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Suggested } from "./Suggested";
+
+/**
+ * Values for an expense suggested from the text on its receipt.
+ */
+export type ExpenseSuggestion = { amount: Suggested<number> | null, timestamp: Suggested<string> | null, merchant: Suggested<string> | null, };
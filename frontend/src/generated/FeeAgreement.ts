@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FeeRule } from "./FeeRule";
+
+/**
+ * A management fee rule, and the period of time it applies to.
+ */
+export type FeeAgreement = { rule: FeeRule, 
+/**
+ * The first day the agreement applies to.
+ */
+effective_from: string, 
+/**
+ * The last day the agreement applies to; `None` if it has not ended.
+ */
+effective_until: string | null, };
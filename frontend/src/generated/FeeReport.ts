@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FeeAgreement } from "./FeeAgreement";
+import type { ReservationFee } from "./ReservationFee";
+
+/**
+ * Compares the management fees in the spreadsheet with the fee agreement.
+ */
+export type FeeReport = { year: number, month: number, 
+/**
+ * The agreement in effect for the month, if any.
+ */
+agreement: FeeAgreement | null, reservations: Array<ReservationFee>, 
+/**
+ * The total management fee entered in the spreadsheet.
+ */
+actual: number, 
+/**
+ * The total management fee calculated from the fee agreement.
+ */
+expected: number | null, mismatch: boolean, };
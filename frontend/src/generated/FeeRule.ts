@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How the management fee is calculated.
+ */
+export type FeeRule = { "type": "percent_of_revenue", rate: number, } | { "type": "percent_of_revenue_minus_cleaning", rate: number, cleaning_fee: number, } | { "type": "flat_monthly", amount: number, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Financial totals for one or more properties over a period of time.
+ */
+export type FinancialSummary = { revenue: number, expenses: number, 
+/**
+ * Net profit from reservations (after management fees) minus expenses.
+ */
+net_profit: number, nights_booked: number, nights_available: number, 
+/**
+ * The fraction of available nights that were booked (0.0 to 1.0).
+ */
+occupancy: number, };
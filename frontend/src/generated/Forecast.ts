@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MonthlyForecast } from "./MonthlyForecast";
+
+/**
+ * The projected net profit of a property for every month of a year.
+ */
+export type Forecast = { year: number, 
+/**
+ * The previous years used to calculate the seasonal baseline.
+ */
+history: Array<number>, months: Array<MonthlyForecast>, };
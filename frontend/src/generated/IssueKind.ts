@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The kinds of data quality issues detected in a spreadsheet.
+ */
+export type IssueKind = "overlapping_stay" | "check_out_before_check_in" | "payout_before_check_in" | "net_profit_mismatch";
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The tab of a property's spreadsheet that holds a month's reservations.
+ */
+export type MonthSheet = { 
+/**
+ * The month, from 1 (January) to 12 (December).
+ */
+month: number, 
+/**
+ * The title of the tab found for the month (e.g., `Sept`), if any.
+ */
+sheet: string | null, };
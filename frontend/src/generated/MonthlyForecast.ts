@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The projected net profit of a property for a single month.
+ */
+export type MonthlyForecast = { month: number, 
+/**
+ * Net profit from reservations that have already checked in.
+ */
+actual: number, 
+/**
+ * Net profit from reservations that check in after today.
+ */
+booked: number, 
+/**
+ * The average net profit for this month in previous years.
+ */
+baseline: number, projected: number, 
+/**
+ * The lower bound of the projection's confidence band.
+ */
+lower: number, 
+/**
+ * The upper bound of the projection's confidence band.
+ */
+upper: number, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BudgetPeriod } from "./BudgetPeriod";
+
+/**
+ * The information required to create a new budget.
+ */
+export type NewBudget = { category: string | null, period: BudgetPeriod, amount: number, 
+/**
+ * Defaults to 80% and 100% if not provided.
+ */
+thresholds: Array<number> | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The information required to record a new payout.
+ */
+export type NewPayout = { year: number, month: number, amount: number, paid_on: string, reference: string | null, note: string | null, };
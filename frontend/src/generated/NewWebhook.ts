@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChangeKind } from "./ChangeKind";
+
+/**
+ * The information required to register a new webhook.
+ */
+export type NewWebhook = { url: string, events: Array<ChangeKind>, property_id: string | null, };
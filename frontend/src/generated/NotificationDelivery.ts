@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DeliveryStatus } from "./DeliveryStatus";
+import type { NotificationKind } from "./NotificationKind";
+
+/**
+ * A record of an attempt to deliver a notification to a user.
+ */
+export type NotificationDelivery = { id: string, user_id: string, kind: NotificationKind, to: string | null, subject: string, status: DeliveryStatus, 
+/**
+ * The reason the notification could not be delivered, if it failed.
+ */
+error: string | null, created_at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The kinds of notifications a user can choose to receive.
+ */
+export type NotificationKind = "statement_ready" | "new_reservation" | "large_expense" | "budget_overrun";
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Which notifications a user wants to receive, and where.
+ */
+export type NotificationPreferences = { 
+/**
+ * Where to send notifications; defaults to the user's first email
+ * address if not provided.
+ */
+email: string | null, statement_ready: boolean, new_reservation: boolean, large_expense: boolean, budget_overrun: boolean, 
+/**
+ * Expenses of at least this amount are considered large.
+ */
+large_expense_threshold: number, };
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A payment made to the owner of a property.
+ */
+export type Payout = { id: string, property_id: string, 
+/**
+ * The year of the statement period the payout is for.
+ */
+year: number, 
+/**
+ * The month of the statement period the payout is for.
+ */
+month: number, amount: number, paid_on: string, 
+/**
+ * An identifier for the payment (e.g., a check or transfer number).
+ */
+reference: string | null, note: string | null, recorded_at: string, 
+/**
+ * When the payout was voided; voided payouts are kept for history, but
+ * are not included in the reconciliation.
+ */
+voided_at: string | null, void_reason: string | null, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FinancialSummary } from "./FinancialSummary";
+import type { PropertySummary } from "./PropertySummary";
+
+/**
+ * The combined financial summary for every property a user owns.
+ */
+export type Portfolio = { year: number, properties: Array<PropertySummary>, 
+/**
+ * Totals for every property that was summarized successfully.
+ */
+total: FinancialSummary, };
@@ -0,0 +1,28 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The body of every error response, as described by RFC 7807 (problem
+ * details for HTTP APIs).
+ */
+export type Problem = { 
+/**
+ * Always `about:blank`; the kind of error is identified by `code`.
+ */
+type: string, 
+/**
+ * The reason phrase of the status code (e.g., `Not Found`).
+ */
+title: string, status: number, 
+/**
+ * A description of the error, meant to be read by people.
+ */
+detail: string, 
+/**
+ * Identifies the kind of error (e.g., `property.not_found`); codes do
+ * not change, so clients can rely on them instead of `detail`.
+ */
+code: string, 
+/**
+ * The ID of the request, also returned in the `X-Request-Id` header.
+ */
+request_id?: string, };
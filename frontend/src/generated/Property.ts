@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ExpenseMatching } from "./ExpenseMatching";
+import type { FeeAgreement } from "./FeeAgreement";
+
+export type Property = { id: string, 
+/**
+ * The ID of the user that owns the property.
+ */
+user_id: string, name: string, address: string | null, 
+/**
+ * The management fee agreements between the owner and Bojano Homes.
+ */
+fee_agreements: Array<FeeAgreement>, expense_matching: ExpenseMatching, };
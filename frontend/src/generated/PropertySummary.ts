@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FinancialSummary } from "./FinancialSummary";
+import type { Property } from "./Property";
+
+/**
+ * The financial summary for a single property within a portfolio.
+ */
+export type PropertySummary = { property: Property, 
+/**
+ * The summary for the property, if it was computed successfully.
+ */
+summary: FinancialSummary | null, 
+/**
+ * The reason the summary could not be computed, if any.
+ */
+error: string | null, };
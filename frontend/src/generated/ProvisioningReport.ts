@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Property } from "./Property";
+
+/**
+ * Whether everything needed to track a year has been set up.
+ */
+export type ProvisioningReport = { year: number, 
+/**
+ * Whether the shared expense sheet for the year has been registered.
+ */
+has_expense_sheet: boolean, 
+/**
+ * The properties without a spreadsheet for the year.
+ */
+missing_spreadsheets: Array<Property>, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Information about a receipt uploaded for an expense.
+ */
+export type Receipt = { id: string, property_id: string, 
+/**
+ * The year of the expense sheet containing the expense.
+ */
+year: number, 
+/**
+ * The row number of the expense within the expense sheet.
+ */
+row: number, content_type: string, 
+/**
+ * The size of the receipt in bytes.
+ */
+size: number, file_name: string | null, 
+/**
+ * Where the receipt can be viewed by the owner of the property.
+ */
+url: string, uploaded_at: string, };
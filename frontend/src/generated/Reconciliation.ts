@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReconciliationPeriod } from "./ReconciliationPeriod";
+
+/**
+ * Compares what the owner is owed with what was paid over a year.
+ */
+export type Reconciliation = { property_id: string, year: number, periods: Array<ReconciliationPeriod>, owed: number, paid: number, 
+/**
+ * The balance still owed to the owner at the start of the year (carried
+ * over from the end of the previous year).
+ */
+opening_balance: number, 
+/**
+ * The balance still owed to the owner at the end of the year.
+ */
+balance: number, };
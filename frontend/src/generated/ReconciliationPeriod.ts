@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Compares what the owner is owed with what was paid for a single month.
+ */
+export type ReconciliationPeriod = { month: number, 
+/**
+ * Net profit from reservations minus expenses.
+ */
+owed: number, 
+/**
+ * The total of every payout recorded for the month (excluding voided).
+ */
+paid: number, 
+/**
+ * The running balance still owed to the owner at the end of the month.
+ */
+balance: number, };
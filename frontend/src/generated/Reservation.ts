@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ValidationIssue } from "./ValidationIssue";
+
+export type Reservation = { platform: string, payout_date: string, check_in: string, check_out: string, revenue: number, management_fee: number, net_profit: number, 
+/**
+ * Data quality issues found in the reservation's row, if any.
+ */
+warnings?: Array<ValidationIssue>, };
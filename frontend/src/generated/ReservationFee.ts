@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The management fee for a single reservation.
+ */
+export type ReservationFee = { 
+/**
+ * The name of the sheet (tab) the reservation was read from.
+ */
+sheet: string, 
+/**
+ * The row number of the reservation within its sheet (starting at 1).
+ */
+row: number, check_in: string, revenue: number, 
+/**
+ * The management fee entered in the spreadsheet.
+ */
+actual: number, 
+/**
+ * The management fee calculated from the fee agreement, if the agreement
+ * charges per reservation.
+ */
+expected: number | null, mismatch: boolean, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MonthSheet } from "./MonthSheet";
+
+/**
+ * How the tabs of a property's spreadsheet for a year match the months.
+ */
+export type SheetReport = { property_id: string, year: number, months: Array<MonthSheet>, 
+/**
+ * The names of the months without a tab (e.g., `September`).
+ */
+missing: Array<string>, 
+/**
+ * The titles of the tabs that do not match any month.
+ */
+unmatched: Array<string>, };
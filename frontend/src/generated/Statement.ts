@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Expense } from "./Expense";
+import type { FinancialSummary } from "./FinancialSummary";
+import type { Reservation } from "./Reservation";
+
+/**
+ * The financial statement for a property over a single month.
+ */
+export type Statement = { property_id: string, year: number, month: number, summary: FinancialSummary, reservations: Array<Reservation>, expenses: Array<Expense>, generated_at: string, };
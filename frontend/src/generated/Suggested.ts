@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A value suggested for a field, and how confident the suggestion is.
+ */
+export type Suggested<T> = { value: T, 
+/**
+ * How likely the value is to be correct (0.0 to 1.0).
+ */
+confidence: number, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A row of the shared expense sheet that does not belong to any property.
+ */
+export type UnmatchedExpense = { 
+/**
+ * The row number within the expense sheet (starting at 1).
+ */
+row: number, 
+/**
+ * The property, as entered in the `Property` column.
+ */
+property: string, 
+/**
+ * The property code, as entered in the `Property Code` column.
+ */
+property_code: string | null, amount: number | null, description: string, timestamp: string | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UnmatchedExpense } from "./UnmatchedExpense";
+
+/**
+ * Every row of a year's expense sheet that does not belong to any property.
+ */
+export type UnmatchedExpenseReport = { year: number, expenses: Array<UnmatchedExpense>, };
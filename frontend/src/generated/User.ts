@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EmailAddress } from "./EmailAddress";
+import type { PhoneNumber } from "./PhoneNumber";
+
+export type User = { id: string, first_name: string | null, last_name: string | null, email_addresses: Array<EmailAddress>, phone_numbers: Array<PhoneNumber>, };
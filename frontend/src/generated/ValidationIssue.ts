@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IssueKind } from "./IssueKind";
+import type { Severity } from "./Severity";
+
+/**
+ * A data quality issue found in a single row of a spreadsheet.
+ */
+export type ValidationIssue = { severity: Severity, kind: IssueKind, 
+/**
+ * The name of the sheet (tab) containing the row.
+ */
+sheet: string, 
+/**
+ * The row number within the sheet (starting at 1).
+ */
+row: number, detail: string, };
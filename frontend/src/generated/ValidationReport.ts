@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ValidationIssue } from "./ValidationIssue";
+
+/**
+ * Every data quality issue found in a property's spreadsheet for a year.
+ */
+export type ValidationReport = { property_id: string, year: number, issues: Array<ValidationIssue>, };
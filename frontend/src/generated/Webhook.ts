@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChangeKind } from "./ChangeKind";
+
+/**
+ * An outgoing webhook that events are delivered to.
+ */
+export type Webhook = { id: string, url: string, 
+/**
+ * The kinds of events delivered to the webhook; every kind is delivered
+ * if empty.
+ */
+events: Array<ChangeKind>, 
+/**
+ * Only deliver events for this property, if provided.
+ */
+property_id: string | null, 
+/**
+ * Used to sign deliveries; only included when the webhook is created.
+ */
+secret?: string, created_at: string, };
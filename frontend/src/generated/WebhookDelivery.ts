@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WebhookDeliveryStatus } from "./WebhookDeliveryStatus";
+
+/**
+ * The delivery of a single event to a webhook.
+ */
+export type WebhookDelivery = { id: string, webhook_id: string, event_id: string, status: WebhookDeliveryStatus, attempts: number, 
+/**
+ * The reason the last attempt failed, if it did.
+ */
+last_error: string | null, next_attempt_at: string | null, delivered_at: string | null, };
@@ -1,8 +1,8 @@
-export interface Property {
-  id: string;
-  name: string;
-  address: string;
-}
+import type { Expense as ExpensePayload } from "~/generated/Expense";
+import type { Property } from "~/generated/Property";
+import type { Reservation as ReservationPayload } from "~/generated/Reservation";
+
+export type { Property };
 
 interface Cache {
   properties: Property[] | null;
@@ -62,15 +62,6 @@ export interface Expense {
   buyersName: string;
 }
 
-interface ExpensePayload {
-  amount: number;
-  description: string;
-  timestamp: string;
-  receipt_link: string;
-  merchant: string;
-  buyers_name: string;
-}
-
 export async function getMonthlyExpenses(
   userId: string,
   propertyId: string,
@@ -91,13 +82,14 @@ export async function getMonthlyExpenses(
     throw new Error(body.detail);
   }
 
-  const data = (body as ExpensePayload[]).map((e) => ({
+  const data: Expense[] = (body as ExpensePayload[]).map((e) => ({
     amount: e.amount,
     description: e.description,
     timestamp: new Date(e.timestamp),
     receiptLink: e.receipt_link,
+    merchant: e.merchant,
     buyersName: e.buyers_name,
-  })) as Expense[];
+  }));
 
   return data;
 }
@@ -114,16 +106,6 @@ export interface Reservation {
   netProfit: number;
 }
 
-interface ReservationPayload {
-  platform: string;
-  payout_date: string;
-  check_in: string;
-  check_out: string;
-  revenue: number;
-  management_fee: number;
-  net_profit: number;
-}
-
 export async function getMonthlyReservations(
   userId: string,
   propertyId: string,
@@ -144,14 +126,14 @@ export async function getMonthlyReservations(
     throw new Error(body.detail);
   }
 
-  const data = (body as ReservationPayload[]).map((r) => ({
+  const data: Reservation[] = (body as ReservationPayload[]).map((r) => ({
     platform: r.platform,
     checkIn: new Date(r.check_in),
     checkOut: new Date(r.check_out),
     revenue: r.revenue,
     managementFee: r.management_fee,
     netProfit: r.net_profit,
-  })) as Reservation[];
+  }));
 
   return data;
 }
@@ -171,7 +153,7 @@ export async function getAnnualReservations(
     throw new Error(body.detail);
   }
 
-  const data = (body as ReservationPayload[][]).map((month) =>
+  const data: Reservation[][] = (body as ReservationPayload[][]).map((month) =>
     month.map((r) => ({
       platform: r.platform,
       checkIn: new Date(r.check_in),
@@ -180,7 +162,7 @@ export async function getAnnualReservations(
       managementFee: r.management_fee,
       netProfit: r.net_profit,
     }))
-  ) as Reservation[][];
+  );
 
   return data;
 }
@@ -0,0 +1,164 @@
+//! Detects which rows of a sheet changed between two snapshots.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A single row of a sheet, as it was when the snapshot was taken.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnapshotRow<T> {
+    /// The row number within the sheet (starting at 1).
+    pub row: u32,
+    /// Identifies the same record across snapshots, even after its values
+    /// change (e.g., the platform and check-in date of a reservation).
+    pub key: String,
+    /// The hash of the row's values.
+    pub hash: String,
+    pub values: T,
+}
+
+impl<T: Serialize> SnapshotRow<T> {
+    pub fn new(row: u32, key: String, values: T) -> Self {
+        // Values are plain strings and numbers, so serializing them cannot fail.
+        let serialized = serde_json::to_vec(&values).unwrap();
+        let hash = hex::encode(Sha256::digest(serialized));
+
+        Self {
+            row,
+            key,
+            hash,
+            values,
+        }
+    }
+}
+
+/// The rows that differ between two snapshots of the same sheet.
+#[derive(Debug)]
+pub struct RowDiff<'a, T> {
+    pub added: Vec<&'a SnapshotRow<T>>,
+    /// Rows with the same key in both snapshots, but different values; each
+    /// pair is the previous row followed by the current one.
+    pub changed: Vec<(&'a SnapshotRow<T>, &'a SnapshotRow<T>)>,
+    pub removed: Vec<&'a SnapshotRow<T>>,
+}
+
+/// Compare the rows of two snapshots.
+///
+/// Rows are matched by their values rather than their position, so inserting
+/// or sorting rows does not report every row below as changed.
+pub fn diff_rows<'a, T>(
+    previous: &'a [SnapshotRow<T>],
+    current: &'a [SnapshotRow<T>],
+) -> RowDiff<'a, T> {
+    let mut remaining: Vec<&SnapshotRow<T>> = previous.iter().collect();
+    let mut unmatched: Vec<&SnapshotRow<T>> = Vec::new();
+
+    for row in current {
+        match remaining.iter().position(|other| other.hash == row.hash) {
+            Some(index) => {
+                remaining.swap_remove(index);
+            }
+            None => unmatched.push(row),
+        }
+    }
+
+    let mut diff = RowDiff {
+        added: Vec::new(),
+        changed: Vec::new(),
+        removed: Vec::new(),
+    };
+
+    for row in unmatched {
+        match remaining.iter().position(|other| other.key == row.key) {
+            Some(index) => diff.changed.push((remaining.swap_remove(index), row)),
+            None => diff.added.push(row),
+        }
+    }
+
+    diff.removed = remaining;
+    diff.removed.sort_by_key(|row| row.row);
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A row keyed by its first value.
+    fn row(row: u32, values: (&str, f32)) -> SnapshotRow<(String, f32)> {
+        SnapshotRow::new(row, values.0.to_string(), (values.0.to_string(), values.1))
+    }
+
+    fn rows(values: &[(&str, f32)]) -> Vec<SnapshotRow<(String, f32)>> {
+        (2..)
+            .zip(values)
+            .map(|(index, values)| row(index, *values))
+            .collect()
+    }
+
+    fn keys<'a>(rows: &[&'a SnapshotRow<(String, f32)>]) -> Vec<&'a str> {
+        rows.iter().map(|row| row.key.as_str()).collect()
+    }
+
+    #[test]
+    fn reordered_rows_are_unchanged() {
+        let previous = rows(&[("a", 1.0), ("b", 2.0), ("c", 3.0)]);
+        let current = rows(&[("c", 3.0), ("a", 1.0), ("b", 2.0)]);
+
+        let diff = diff_rows(&previous, &current);
+        assert!(diff.added.is_empty());
+        assert!(diff.changed.is_empty());
+        assert!(diff.removed.is_empty());
+    }
+
+    #[test]
+    fn edits_to_a_row_are_changes() {
+        let previous = rows(&[("a", 1.0), ("b", 2.0)]);
+        let current = rows(&[("a", 1.0), ("b", 2.5)]);
+
+        let diff = diff_rows(&previous, &current);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+
+        let (before, after) = diff.changed[0];
+        assert_eq!((before.values.1, after.values.1), (2.0, 2.5));
+    }
+
+    #[test]
+    fn rows_are_added_and_removed() {
+        let previous = rows(&[("a", 1.0), ("b", 2.0)]);
+        let current = rows(&[("b", 2.0), ("c", 3.0)]);
+
+        let diff = diff_rows(&previous, &current);
+        assert_eq!(keys(&diff.added), ["c"]);
+        assert_eq!(keys(&diff.removed), ["a"]);
+        assert!(diff.changed.is_empty());
+    }
+
+    #[test]
+    fn duplicate_rows_are_matched_once() {
+        let previous = rows(&[("a", 1.0), ("a", 1.0)]);
+
+        // A third copy of the same row is an addition.
+        let current = rows(&[("a", 1.0), ("a", 1.0), ("a", 1.0)]);
+        let diff = diff_rows(&previous, &current);
+        assert_eq!(keys(&diff.added), ["a"]);
+        assert!(diff.changed.is_empty());
+        assert!(diff.removed.is_empty());
+
+        // Removing one of the copies is a removal.
+        let current = rows(&[("a", 1.0)]);
+        let diff = diff_rows(&previous, &current);
+        assert!(diff.added.is_empty());
+        assert_eq!(keys(&diff.removed), ["a"]);
+
+        // Editing one of the copies only changes that copy.
+        let current = rows(&[("a", 1.0), ("a", 5.0)]);
+        let diff = diff_rows(&previous, &current);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].1.values.1, 5.0);
+    }
+}
@@ -2,10 +2,171 @@
 
 use std::{error, fmt};
 
-use axum::response::IntoResponse;
+use axum::{
+    extract::{
+        multipart::MultipartRejection,
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+    },
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+    Json,
+};
+use models::Problem;
 use reqwest::StatusCode;
+use sheets::{GetSpreadsheetError, GetValuesError, UpdateValuesError};
 
-use crate::http_error;
+use crate::request_id;
+
+/// An error returned by the API.
+///
+/// Every error is returned as problem details (RFC 7807), with a `code` that
+/// identifies the kind of error (e.g., `property.not_found`). Codes are named
+/// after what failed, followed by why; the errors of the service functions
+/// are converted into an `ApiError` to choose them.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub code: &'static str,
+    pub detail: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, detail: impl fmt::Display) -> Self {
+        Self {
+            status,
+            code,
+            detail: detail.to_string(),
+        }
+    }
+}
+
+impl error::Error for ApiError {}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.detail, self.code)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let problem = Problem {
+            kind: "about:blank".to_string(),
+            title: self
+                .status
+                .canonical_reason()
+                .unwrap_or_default()
+                .to_string(),
+            status: self.status.as_u16(),
+            detail: self.detail,
+            code: self.code.to_string(),
+            request_id: request_id::current(),
+        };
+
+        let mut response = (self.status, Json(problem)).into_response();
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/problem+json"),
+        );
+
+        response
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(err: PathRejection) -> Self {
+        Self::new(err.status(), "request.invalid_path", err.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(err: QueryRejection) -> Self {
+        Self::new(err.status(), "request.invalid_query", err.body_text())
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(err: JsonRejection) -> Self {
+        Self::new(err.status(), "request.invalid_body", err.body_text())
+    }
+}
+
+impl From<MultipartRejection> for ApiError {
+    fn from(err: MultipartRejection) -> Self {
+        Self::new(err.status(), "request.invalid_form", err.body_text())
+    }
+}
+
+impl From<GetValuesError> for ApiError {
+    fn from(err: GetValuesError) -> Self {
+        let (status, code) = get_values_status(&err);
+        Self::new(status, code, err)
+    }
+}
+
+fn get_values_status(err: &GetValuesError) -> (StatusCode, &'static str) {
+    match err {
+        GetValuesError::RequestFailure(..) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "sheets.request_failed")
+        }
+        GetValuesError::MissingPermissions => (StatusCode::FORBIDDEN, "sheets.permission_denied"),
+        GetValuesError::BadCredentials(..) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "sheets.bad_credentials")
+        }
+        GetValuesError::NotFound => (StatusCode::NOT_FOUND, "sheets.not_found"),
+        GetValuesError::InvalidRange(..) => (StatusCode::BAD_GATEWAY, "sheets.invalid_range"),
+        GetValuesError::RateLimited => (StatusCode::TOO_MANY_REQUESTS, "sheets.rate_limited"),
+        GetValuesError::Unavailable => (StatusCode::SERVICE_UNAVAILABLE, "sheets.unavailable"),
+        GetValuesError::InvalidResponse(..) => (StatusCode::BAD_GATEWAY, "sheets.invalid_response"),
+    }
+}
+
+fn get_spreadsheet_status(err: &GetSpreadsheetError) -> (StatusCode, &'static str) {
+    match err {
+        GetSpreadsheetError::RequestFailure(..) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "sheets.request_failed")
+        }
+        GetSpreadsheetError::MissingPermissions => {
+            (StatusCode::FORBIDDEN, "sheets.permission_denied")
+        }
+        GetSpreadsheetError::BadCredentials(..) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "sheets.bad_credentials")
+        }
+        GetSpreadsheetError::NotFound => (StatusCode::NOT_FOUND, "sheets.not_found"),
+        GetSpreadsheetError::RateLimited => (StatusCode::TOO_MANY_REQUESTS, "sheets.rate_limited"),
+        GetSpreadsheetError::Unavailable => (StatusCode::SERVICE_UNAVAILABLE, "sheets.unavailable"),
+        GetSpreadsheetError::InvalidResponse(..) => {
+            (StatusCode::BAD_GATEWAY, "sheets.invalid_response")
+        }
+    }
+}
+
+impl From<UpdateValuesError> for ApiError {
+    fn from(err: UpdateValuesError) -> Self {
+        let (status, code) = update_values_status(&err);
+        Self::new(status, code, err)
+    }
+}
+
+fn update_values_status(err: &UpdateValuesError) -> (StatusCode, &'static str) {
+    match err {
+        UpdateValuesError::RequestFailure(..) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "sheets.request_failed")
+        }
+        UpdateValuesError::MissingPermissions => {
+            (StatusCode::FORBIDDEN, "sheets.permission_denied")
+        }
+        UpdateValuesError::BadCredentials(..) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "sheets.bad_credentials")
+        }
+        UpdateValuesError::NotFound => (StatusCode::NOT_FOUND, "sheets.not_found"),
+        UpdateValuesError::RateLimited => (StatusCode::TOO_MANY_REQUESTS, "sheets.rate_limited"),
+        UpdateValuesError::Unavailable => (StatusCode::SERVICE_UNAVAILABLE, "sheets.unavailable"),
+        UpdateValuesError::InvalidResponse(..) => {
+            (StatusCode::BAD_GATEWAY, "sheets.invalid_response")
+        }
+    }
+}
 
 /// An error occurred while trying to get a user from the database.
 #[derive(Debug)]
@@ -18,6 +179,12 @@ pub enum UserError {
     InvalidApiKey,
     /// The ID provided was of the correct format, but did not match a user.
     NotFound(String),
+    /// Too many requests were made to Clerk recently.
+    RateLimited,
+    /// Clerk is down, or failed to handle the request.
+    Unavailable,
+    /// Clerk responded with something other than a user.
+    InvalidResponse(String),
 }
 
 impl error::Error for UserError {}
@@ -29,21 +196,74 @@ impl fmt::Display for UserError {
             Self::BadId(id) => write!(f, "malformed user id: {id}"),
             Self::InvalidApiKey => write!(f, "the api key used was rejected"),
             Self::NotFound(id) => write!(f, "no user with id {id}"),
+            Self::RateLimited => write!(f, "too many requests were made to clerk"),
+            Self::Unavailable => write!(f, "clerk is unavailable"),
+            Self::InvalidResponse(reason) => write!(f, "failed to parse user: {reason}"),
         }
     }
 }
 
+impl From<UserError> for ApiError {
+    fn from(err: UserError) -> Self {
+        let (status, code) = match &err {
+            UserError::RequestFailure(..) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "user.request_failed")
+            }
+            UserError::BadId(..) => (StatusCode::BAD_REQUEST, "user.bad_id"),
+            UserError::InvalidApiKey => (StatusCode::INTERNAL_SERVER_ERROR, "user.invalid_api_key"),
+            UserError::NotFound(..) => (StatusCode::NOT_FOUND, "user.not_found"),
+            UserError::RateLimited => (StatusCode::TOO_MANY_REQUESTS, "user.rate_limited"),
+            UserError::Unavailable => (StatusCode::SERVICE_UNAVAILABLE, "user.unavailable"),
+            UserError::InvalidResponse(..) => (StatusCode::BAD_GATEWAY, "user.invalid_response"),
+        };
+
+        Self::new(status, code, err)
+    }
+}
+
 impl IntoResponse for UserError {
-    fn into_response(self) -> axum::response::Response {
-        let status_code = match &self {
-            Self::RequestFailure(..) => StatusCode::INTERNAL_SERVER_ERROR,
-            Self::BadId(..) => StatusCode::BAD_REQUEST,
-            Self::InvalidApiKey => StatusCode::INTERNAL_SERVER_ERROR,
-            Self::NotFound(..) => StatusCode::NOT_FOUND,
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
+    }
+}
+
+/// An error occurred while trying to get an expense sheet from the database.
+#[derive(Debug)]
+pub enum ExpenseSheetError {
+    /// An unexpected error occurred while trying to get the data.
+    RequestFailure(String),
+    /// No expense sheet has been registered for the year.
+    NotFound(i32),
+}
+
+impl error::Error for ExpenseSheetError {}
+
+impl fmt::Display for ExpenseSheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RequestFailure(reason) => write!(f, "{}", reason),
+            Self::NotFound(year) => write!(f, "expense sheet not found for year {year}"),
+        }
+    }
+}
+
+impl From<ExpenseSheetError> for ApiError {
+    fn from(err: ExpenseSheetError) -> Self {
+        let (status, code) = match &err {
+            ExpenseSheetError::RequestFailure(..) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "expense_sheet.request_failed",
+            ),
+            ExpenseSheetError::NotFound(..) => (StatusCode::NOT_FOUND, "expense_sheet.not_found"),
         };
-        let detail = self.to_string();
 
-        http_error!(status_code, detail)
+        Self::new(status, code, err)
+    }
+}
+
+impl IntoResponse for ExpenseSheetError {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
     }
 }
 
@@ -56,6 +276,10 @@ pub enum PropertyError {
     BadId(String),
     /// The ID provided was of the correct format, but did not match a property.
     NotFound(String),
+    /// Another property already uses the expense sheet code.
+    DuplicateCode(String),
+    /// The fee agreements provided are invalid (e.g., they overlap).
+    InvalidFeeAgreements(String),
 }
 
 impl error::Error for PropertyError {}
@@ -66,20 +290,35 @@ impl fmt::Display for PropertyError {
             Self::RequestFailure(reason) => write!(f, "{}", reason),
             Self::BadId(id) => write!(f, "malformed property id: {id}"),
             Self::NotFound(id) => write!(f, "no property with id {id}"),
+            Self::DuplicateCode(code) => {
+                write!(f, "another property already uses the code {code}")
+            }
+            Self::InvalidFeeAgreements(reason) => write!(f, "invalid fee agreements: {reason}"),
         }
     }
 }
 
-impl IntoResponse for PropertyError {
-    fn into_response(self) -> axum::response::Response {
-        let status_code = match &self {
-            Self::RequestFailure(..) => StatusCode::INTERNAL_SERVER_ERROR,
-            Self::BadId(..) => StatusCode::BAD_REQUEST,
-            Self::NotFound(..) => StatusCode::NOT_FOUND,
+impl From<PropertyError> for ApiError {
+    fn from(err: PropertyError) -> Self {
+        let (status, code) = match &err {
+            PropertyError::RequestFailure(..) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "property.request_failed")
+            }
+            PropertyError::BadId(..) => (StatusCode::BAD_REQUEST, "property.bad_id"),
+            PropertyError::NotFound(..) => (StatusCode::NOT_FOUND, "property.not_found"),
+            PropertyError::DuplicateCode(..) => (StatusCode::CONFLICT, "property.duplicate_code"),
+            PropertyError::InvalidFeeAgreements(..) => {
+                (StatusCode::BAD_REQUEST, "property.invalid_fee_agreements")
+            }
         };
-        let detail = self.to_string();
 
-        http_error!(status_code, detail)
+        Self::new(status, code, err)
+    }
+}
+
+impl IntoResponse for PropertyError {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
     }
 }
 
@@ -87,6 +326,12 @@ impl IntoResponse for PropertyError {
 pub enum ExpenseError {
     /// An unexpected error occurred while trying to get the data.
     RequestFailure(String),
+    /// No expense sheet has been registered for the year.
+    SheetNotFound(i32),
+    /// The expense sheet could not be read.
+    Sheets(GetValuesError),
+    /// A row of the expense sheet could not be parsed.
+    InvalidRow(u32, String),
 }
 
 impl error::Error for ExpenseError {}
@@ -95,18 +340,36 @@ impl fmt::Display for ExpenseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::RequestFailure(reason) => write!(f, "{}", reason),
+            Self::SheetNotFound(year) => write!(f, "no expense sheet for {year}"),
+            Self::Sheets(err) => write!(f, "{err}"),
+            Self::InvalidRow(row, reason) => write!(f, "row {row} of the expense sheet: {reason}"),
         }
     }
 }
 
-impl IntoResponse for ExpenseError {
-    fn into_response(self) -> axum::response::Response {
-        let status_code = match &self {
-            Self::RequestFailure(..) => StatusCode::INTERNAL_SERVER_ERROR,
-        };
-        let detail = self.to_string();
+fn get_expense_status(err: &ExpenseError) -> (StatusCode, &'static str) {
+    match err {
+        ExpenseError::RequestFailure(..) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "expense.request_failed")
+        }
+        ExpenseError::SheetNotFound(..) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "expense.sheet_not_found")
+        }
+        ExpenseError::Sheets(err) => get_values_status(err),
+        ExpenseError::InvalidRow(..) => (StatusCode::BAD_GATEWAY, "expense.invalid_row"),
+    }
+}
 
-        http_error!(status_code, detail)
+impl From<ExpenseError> for ApiError {
+    fn from(err: ExpenseError) -> Self {
+        let (status, code) = get_expense_status(&err);
+        Self::new(status, code, err)
+    }
+}
+
+impl IntoResponse for ExpenseError {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
     }
 }
 
@@ -119,6 +382,14 @@ pub enum ReservationError {
     SpreadsheetNotFound(i32, String),
     /// An invalid value was provided for month.
     InvalidMonth,
+    /// The property's spreadsheet could not be read.
+    Sheets(GetValuesError),
+    /// The tabs of the property's spreadsheet could not be listed.
+    Metadata(GetSpreadsheetError),
+    /// The property's spreadsheet does not have a tab for the month.
+    SheetMissing(i32, String),
+    /// A row of a month's sheet could not be parsed.
+    InvalidRow(String, u32, String),
 }
 
 impl error::Error for ReservationError {}
@@ -133,19 +404,504 @@ impl fmt::Display for ReservationError {
                 year, id
             ),
             Self::InvalidMonth => write!(f, "invalid value provided for month"),
+            Self::Sheets(err) => write!(f, "{err}"),
+            Self::Metadata(err) => write!(f, "{err}"),
+            Self::SheetMissing(year, sheet) => {
+                write!(f, "the {year} spreadsheet does not have a tab for {sheet}")
+            }
+            Self::InvalidRow(sheet, row, reason) => write!(f, "row {row} of {sheet}: {reason}"),
         }
     }
 }
 
+fn get_reservation_status(err: &ReservationError) -> (StatusCode, &'static str) {
+    match err {
+        ReservationError::RequestFailure(..) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "reservation.request_failed",
+        ),
+        ReservationError::SpreadsheetNotFound(..) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "reservation.spreadsheet_not_found",
+        ),
+        ReservationError::InvalidMonth => (StatusCode::BAD_REQUEST, "reservation.invalid_month"),
+        ReservationError::Sheets(err) => get_values_status(err),
+        ReservationError::Metadata(err) => get_spreadsheet_status(err),
+        ReservationError::SheetMissing(..) => (StatusCode::NOT_FOUND, "reservation.sheet_missing"),
+        ReservationError::InvalidRow(..) => (StatusCode::BAD_GATEWAY, "reservation.invalid_row"),
+    }
+}
+
+impl From<ReservationError> for ApiError {
+    fn from(err: ReservationError) -> Self {
+        let (status, code) = get_reservation_status(&err);
+        Self::new(status, code, err)
+    }
+}
+
 impl IntoResponse for ReservationError {
-    fn into_response(self) -> axum::response::Response {
-        let status_code = match &self {
-            Self::RequestFailure(..) => StatusCode::INTERNAL_SERVER_ERROR,
-            Self::SpreadsheetNotFound(..) => StatusCode::INTERNAL_SERVER_ERROR,
-            Self::InvalidMonth => StatusCode::BAD_REQUEST,
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
+    }
+}
+
+/// An error occurred while trying to summarize a property's finances.
+#[derive(Debug)]
+pub enum SummaryError {
+    /// The year is outside the range of supported dates.
+    InvalidYear(i32),
+    /// The property's reservations could not be read.
+    Reservation(ReservationError),
+    /// The property's expenses could not be read.
+    Expense(ExpenseError),
+}
+
+impl error::Error for SummaryError {}
+
+impl fmt::Display for SummaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidYear(year) => write!(f, "invalid value provided for year: {year}"),
+            Self::Reservation(err) => write!(f, "{err}"),
+            Self::Expense(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl From<SummaryError> for ApiError {
+    fn from(err: SummaryError) -> Self {
+        let (status, code) = match &err {
+            SummaryError::InvalidYear(..) => (StatusCode::BAD_REQUEST, "summary.invalid_year"),
+            SummaryError::Reservation(err) => get_reservation_status(err),
+            SummaryError::Expense(err) => get_expense_status(err),
+        };
+
+        Self::new(status, code, err)
+    }
+}
+
+impl From<ReservationError> for SummaryError {
+    fn from(err: ReservationError) -> Self {
+        Self::Reservation(err)
+    }
+}
+
+impl From<ExpenseError> for SummaryError {
+    fn from(err: ExpenseError) -> Self {
+        Self::Expense(err)
+    }
+}
+
+impl IntoResponse for SummaryError {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
+    }
+}
+
+/// An error occurred while trying to manage the payouts for a property.
+#[derive(Debug)]
+pub enum PayoutError {
+    /// An unexpected error occurred while trying to get or update the data.
+    RequestFailure(String),
+    /// The payout ID provided was malformed.
+    BadId(String),
+    /// The ID provided was of the correct format, but did not match a payout.
+    NotFound(String),
+    /// The payout was already voided.
+    AlreadyVoided(String),
+    /// An invalid value was provided for month.
+    InvalidMonth,
+    /// The amount paid was not a positive number.
+    InvalidAmount,
+    /// The reservations that determine what is owed could not be read.
+    Reservation(ReservationError),
+    /// The expenses that determine what is owed could not be read.
+    Expense(ExpenseError),
+}
+
+impl error::Error for PayoutError {}
+
+impl fmt::Display for PayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RequestFailure(reason) => write!(f, "{}", reason),
+            Self::BadId(id) => write!(f, "malformed payout id: {id}"),
+            Self::NotFound(id) => write!(f, "no payout with id {id}"),
+            Self::AlreadyVoided(id) => write!(f, "payout with id {id} was already voided"),
+            Self::InvalidMonth => write!(f, "invalid value provided for month"),
+            Self::InvalidAmount => write!(f, "the amount paid must be greater than zero"),
+            Self::Reservation(err) => write!(f, "{err}"),
+            Self::Expense(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl From<PayoutError> for ApiError {
+    fn from(err: PayoutError) -> Self {
+        let (status, code) = match &err {
+            PayoutError::RequestFailure(..) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "payout.request_failed")
+            }
+            PayoutError::BadId(..) => (StatusCode::BAD_REQUEST, "payout.bad_id"),
+            PayoutError::NotFound(..) => (StatusCode::NOT_FOUND, "payout.not_found"),
+            PayoutError::AlreadyVoided(..) => (StatusCode::CONFLICT, "payout.already_voided"),
+            PayoutError::InvalidMonth => (StatusCode::BAD_REQUEST, "payout.invalid_month"),
+            PayoutError::InvalidAmount => (StatusCode::BAD_REQUEST, "payout.invalid_amount"),
+            PayoutError::Reservation(err) => get_reservation_status(err),
+            PayoutError::Expense(err) => get_expense_status(err),
+        };
+
+        Self::new(status, code, err)
+    }
+}
+
+impl From<ReservationError> for PayoutError {
+    fn from(err: ReservationError) -> Self {
+        Self::Reservation(err)
+    }
+}
+
+impl From<ExpenseError> for PayoutError {
+    fn from(err: ExpenseError) -> Self {
+        Self::Expense(err)
+    }
+}
+
+impl IntoResponse for PayoutError {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
+    }
+}
+
+/// An error occurred while trying to upload or view a receipt.
+#[derive(Debug)]
+pub enum ReceiptError {
+    /// An unexpected error occurred while trying to get or store the data.
+    RequestFailure(String),
+    /// The receipt ID provided was malformed.
+    BadId(String),
+    /// The ID provided was of the correct format, but did not match a receipt.
+    NotFound(String),
+    /// The request did not include a file.
+    MissingFile,
+    /// The file is not an image or PDF.
+    UnsupportedType,
+    /// The file is larger than the maximum size allowed (in bytes).
+    TooLarge(usize),
+    /// No expense for the property was found at the specified row.
+    ExpenseNotFound(i32, u32),
+    /// Text recognition is not installed on the server.
+    OcrUnavailable,
+    /// Text recognition took too long (e.g., because the server is busy).
+    OcrTimedOut,
+    /// The receipt could not be linked in the expense sheet.
+    Sheets(UpdateValuesError),
+}
+
+impl error::Error for ReceiptError {}
+
+impl fmt::Display for ReceiptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RequestFailure(reason) => write!(f, "{}", reason),
+            Self::BadId(id) => write!(f, "malformed receipt id: {id}"),
+            Self::NotFound(id) => write!(f, "no receipt with id {id}"),
+            Self::MissingFile => write!(f, "expected a file named 'receipt'"),
+            Self::UnsupportedType => {
+                write!(f, "unsupported file type (expected JPEG, PNG, WebP or PDF)")
+            }
+            Self::TooLarge(max) => write!(f, "file exceeds the maximum size of {max} bytes"),
+            Self::ExpenseNotFound(year, row) => {
+                write!(f, "no expense for this property in {year} at row {row}")
+            }
+            Self::OcrUnavailable => write!(f, "text recognition is not available"),
+            Self::OcrTimedOut => write!(f, "text recognition took too long"),
+            Self::Sheets(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl From<ReceiptError> for ApiError {
+    fn from(err: ReceiptError) -> Self {
+        let (status, code) = match &err {
+            ReceiptError::RequestFailure(..) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "receipt.request_failed")
+            }
+            ReceiptError::BadId(..) => (StatusCode::BAD_REQUEST, "receipt.bad_id"),
+            ReceiptError::NotFound(..) => (StatusCode::NOT_FOUND, "receipt.not_found"),
+            ReceiptError::MissingFile => (StatusCode::BAD_REQUEST, "receipt.missing_file"),
+            ReceiptError::UnsupportedType => (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "receipt.unsupported_type",
+            ),
+            ReceiptError::TooLarge(..) => (StatusCode::PAYLOAD_TOO_LARGE, "receipt.too_large"),
+            ReceiptError::ExpenseNotFound(..) => {
+                (StatusCode::NOT_FOUND, "receipt.expense_not_found")
+            }
+            ReceiptError::OcrUnavailable => {
+                (StatusCode::SERVICE_UNAVAILABLE, "receipt.ocr_unavailable")
+            }
+            ReceiptError::OcrTimedOut => (StatusCode::SERVICE_UNAVAILABLE, "receipt.ocr_timed_out"),
+            ReceiptError::Sheets(err) => update_values_status(err),
+        };
+
+        Self::new(status, code, err)
+    }
+}
+
+impl IntoResponse for ReceiptError {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
+    }
+}
+
+/// An error occurred while trying to manage the budgets for a property.
+#[derive(Debug)]
+pub enum BudgetError {
+    /// An unexpected error occurred while trying to get or update the data.
+    RequestFailure(String),
+    /// The budget ID provided was malformed.
+    BadId(String),
+    /// The ID provided was of the correct format, but did not match a budget.
+    NotFound(String),
+    /// The amount or thresholds provided were not positive numbers.
+    InvalidAmount,
+    /// An invalid value was provided for month.
+    InvalidMonth,
+}
+
+impl error::Error for BudgetError {}
+
+impl fmt::Display for BudgetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RequestFailure(reason) => write!(f, "{}", reason),
+            Self::BadId(id) => write!(f, "malformed budget id: {id}"),
+            Self::NotFound(id) => write!(f, "no budget with id {id}"),
+            Self::InvalidAmount => write!(f, "amount and thresholds must be positive numbers"),
+            Self::InvalidMonth => write!(f, "invalid value provided for month"),
+        }
+    }
+}
+
+impl From<BudgetError> for ApiError {
+    fn from(err: BudgetError) -> Self {
+        let (status, code) = match &err {
+            BudgetError::RequestFailure(..) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "budget.request_failed")
+            }
+            BudgetError::BadId(..) => (StatusCode::BAD_REQUEST, "budget.bad_id"),
+            BudgetError::NotFound(..) => (StatusCode::NOT_FOUND, "budget.not_found"),
+            BudgetError::InvalidAmount => (StatusCode::BAD_REQUEST, "budget.invalid_amount"),
+            BudgetError::InvalidMonth => (StatusCode::BAD_REQUEST, "budget.invalid_month"),
         };
-        let detail = self.to_string();
 
-        http_error!(status_code, detail)
+        Self::new(status, code, err)
+    }
+}
+
+impl IntoResponse for BudgetError {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
+    }
+}
+
+/// An error occurred while trying to manage a user's notifications.
+#[derive(Debug)]
+pub enum NotificationError {
+    /// An unexpected error occurred while trying to get or update the data.
+    RequestFailure(String),
+}
+
+impl error::Error for NotificationError {}
+
+impl fmt::Display for NotificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RequestFailure(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl From<NotificationError> for ApiError {
+    fn from(err: NotificationError) -> Self {
+        let (status, code) = match &err {
+            NotificationError::RequestFailure(..) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "notification.request_failed",
+            ),
+        };
+
+        Self::new(status, code, err)
+    }
+}
+
+impl IntoResponse for NotificationError {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
+    }
+}
+
+/// An error occurred while trying to generate or get a monthly statement.
+#[derive(Debug)]
+pub enum StatementError {
+    /// An unexpected error occurred while trying to get or update the data.
+    RequestFailure(String),
+    /// A statement has not been generated for the year and month.
+    NotFound(i32, u8),
+    /// An invalid value was provided for month.
+    InvalidMonth,
+}
+
+impl error::Error for StatementError {}
+
+impl fmt::Display for StatementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RequestFailure(reason) => write!(f, "{}", reason),
+            Self::NotFound(year, month) => {
+                write!(f, "statement not found for {year}-{month:02}")
+            }
+            Self::InvalidMonth => write!(f, "invalid value provided for month"),
+        }
+    }
+}
+
+impl From<StatementError> for ApiError {
+    fn from(err: StatementError) -> Self {
+        let (status, code) = match &err {
+            StatementError::RequestFailure(..) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "statement.request_failed",
+            ),
+            StatementError::NotFound(..) => (StatusCode::NOT_FOUND, "statement.not_found"),
+            StatementError::InvalidMonth => (StatusCode::BAD_REQUEST, "statement.invalid_month"),
+        };
+
+        Self::new(status, code, err)
+    }
+}
+
+impl IntoResponse for StatementError {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
+    }
+}
+
+/// An error occurred while trying to manage webhooks or deliver events.
+#[derive(Debug)]
+pub enum WebhookError {
+    /// An unexpected error occurred while trying to get or update the data.
+    RequestFailure(String),
+    /// The webhook ID provided was malformed.
+    BadId(String),
+    /// The ID provided was of the correct format, but did not match a webhook.
+    NotFound(String),
+    /// The URL provided is not a valid HTTP(S) URL.
+    InvalidUrl(String),
+}
+
+impl error::Error for WebhookError {}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RequestFailure(reason) => write!(f, "{}", reason),
+            Self::BadId(id) => write!(f, "malformed webhook id: {id}"),
+            Self::NotFound(id) => write!(f, "no webhook with id {id}"),
+            Self::InvalidUrl(url) => write!(f, "invalid webhook url: {url}"),
+        }
+    }
+}
+
+impl From<WebhookError> for ApiError {
+    fn from(err: WebhookError) -> Self {
+        let (status, code) = match &err {
+            WebhookError::RequestFailure(..) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "webhook.request_failed")
+            }
+            WebhookError::BadId(..) => (StatusCode::BAD_REQUEST, "webhook.bad_id"),
+            WebhookError::NotFound(..) => (StatusCode::NOT_FOUND, "webhook.not_found"),
+            WebhookError::InvalidUrl(..) => (StatusCode::BAD_REQUEST, "webhook.invalid_url"),
+        };
+
+        Self::new(status, code, err)
+    }
+}
+
+impl IntoResponse for WebhookError {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upstream_failures_map_to_statuses() {
+        let cases = [
+            (
+                ApiError::from(GetValuesError::RateLimited),
+                429,
+                "sheets.rate_limited",
+            ),
+            (
+                ApiError::from(GetValuesError::Unavailable),
+                503,
+                "sheets.unavailable",
+            ),
+            (
+                ApiError::from(GetValuesError::NotFound),
+                404,
+                "sheets.not_found",
+            ),
+            (
+                ApiError::from(GetValuesError::BadCredentials("invalid_grant".into())),
+                500,
+                "sheets.bad_credentials",
+            ),
+            (
+                ApiError::from(GetValuesError::InvalidResponse("eof".into())),
+                502,
+                "sheets.invalid_response",
+            ),
+            (
+                ApiError::from(GetValuesError::InvalidRange("bad range".into())),
+                502,
+                "sheets.invalid_range",
+            ),
+            (
+                ApiError::from(ReservationError::SheetMissing(2025, "September".into())),
+                404,
+                "reservation.sheet_missing",
+            ),
+            (
+                ApiError::from(UpdateValuesError::RateLimited),
+                429,
+                "sheets.rate_limited",
+            ),
+            (
+                ApiError::from(UserError::RateLimited),
+                429,
+                "user.rate_limited",
+            ),
+            (
+                ApiError::from(UserError::Unavailable),
+                503,
+                "user.unavailable",
+            ),
+            (
+                ApiError::from(UserError::InvalidResponse("eof".into())),
+                502,
+                "user.invalid_response",
+            ),
+        ];
+
+        for (err, status, code) in cases {
+            assert_eq!(err.status.as_u16(), status, "{err}");
+            assert_eq!(err.code, code);
+        }
     }
 }
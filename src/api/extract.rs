@@ -0,0 +1,144 @@
+//! Extractors that reject requests with problem details (see [`ApiError`]),
+//! instead of the plain text rejections of the extractors they wrap.
+
+use axum::{
+    async_trait,
+    extract::{FromRequest, FromRequestParts, Request},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+use super::error::ApiError;
+
+/// Wraps [`axum::extract::Path`].
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Path), rejection(ApiError))]
+pub struct Path<T>(pub T);
+
+/// Wraps [`axum::extract::Query`].
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(ApiError))]
+pub struct Query<T>(pub T);
+
+/// Wraps [`axum::Json`], which is also used to respond with JSON.
+#[derive(FromRequest)]
+#[from_request(via(axum::Json), rejection(ApiError))]
+pub struct Json<T>(pub T);
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+/// Wraps [`axum::extract::Multipart`].
+pub struct Multipart(pub axum::extract::Multipart);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequest<S> for Multipart {
+    type Rejection = ApiError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        axum::extract::Multipart::from_request(request, state)
+            .await
+            .map(Self)
+            .map_err(ApiError::from)
+    }
+}
+
+/// Respond to requests that do not match any route.
+pub async fn fallback() -> ApiError {
+    ApiError::new(
+        axum::http::StatusCode::NOT_FOUND,
+        "route.not_found",
+        "no route matches the request",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        routing::{get, post},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn get_router() -> Router {
+        Router::new()
+            .route(
+                "/years/:year",
+                get(|Path(year): Path<i32>| async move { year.to_string() }),
+            )
+            .route(
+                "/echo",
+                post(|Json(value): Json<Vec<i32>>| async move { Json(value) }),
+            )
+            .fallback(fallback)
+    }
+
+    async fn send(request: Request<Body>) -> (StatusCode, Option<String>, String) {
+        let response = get_router().oneshot(request).await.unwrap();
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map(|value| value.to_str().unwrap().to_string());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        (
+            status,
+            content_type,
+            String::from_utf8(body.to_vec()).unwrap(),
+        )
+    }
+
+    fn get_request(uri: &str) -> Request<Body> {
+        Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn valid_requests_are_extracted() {
+        let (status, _, body) = send(get_request("/years/2025")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "2025");
+
+        let request = Request::post("/echo")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from("[1,2]"))
+            .unwrap();
+        let (status, _, body) = send(request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "[1,2]");
+    }
+
+    #[tokio::test]
+    async fn rejections_are_problems() {
+        let (status, content_type, body) = send(get_request("/years/next")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(content_type.as_deref(), Some("application/problem+json"));
+        assert!(body.contains("\"request.invalid_path\""), "{body}");
+
+        let request = Request::post("/echo")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from("{\"not\": \"a list\"}"))
+            .unwrap();
+        let (status, content_type, body) = send(request).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(content_type.as_deref(), Some("application/problem+json"));
+        assert!(body.contains("\"request.invalid_body\""), "{body}");
+    }
+
+    #[tokio::test]
+    async fn unknown_routes_are_problems() {
+        let (status, content_type, body) = send(get_request("/nowhere")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(content_type.as_deref(), Some("application/problem+json"));
+        assert!(body.contains("\"route.not_found\""), "{body}");
+    }
+}
@@ -0,0 +1,271 @@
+//! Calculates management fees from the fee agreements stored on a property.
+
+use super::model::{FeeAgreement, FeeRule, Reservation};
+
+/// The largest difference allowed between the management fee in the sheet
+/// and the management fee calculated from the fee agreement.
+pub const FEE_TOLERANCE: f32 = 0.01;
+
+/// Get the agreement in effect on the specified date.
+///
+/// Agreements are checked not to overlap when they are stored (see
+/// [`validate_fee_agreements`]), but older properties may still have
+/// overlapping agreements; if more than one agreement applies, the one that
+/// took effect most recently is used.
+pub fn get_fee_agreement(
+    agreements: &[FeeAgreement],
+    date: chrono::NaiveDate,
+) -> Option<&FeeAgreement> {
+    agreements
+        .iter()
+        .filter(|agreement| {
+            agreement.effective_from <= date
+                && agreement.effective_until.is_none_or(|until| date <= until)
+        })
+        .max_by_key(|agreement| agreement.effective_from)
+}
+
+/// Check that a property's fee agreements can be stored, returning the reason
+/// they cannot otherwise.
+///
+/// Rates must be between 0 and 1, amounts cannot be negative, agreements
+/// cannot end before they take effect, and no two agreements can apply on the
+/// same day (an agreement without an end date applies to every day after it
+/// takes effect).
+pub fn validate_fee_agreements(agreements: &[FeeAgreement]) -> Result<(), String> {
+    for agreement in agreements {
+        let from = agreement.effective_from;
+
+        let is_valid_rule = match agreement.rule {
+            FeeRule::PercentOfRevenue { rate } => is_valid_rate(rate),
+            FeeRule::PercentOfRevenueMinusCleaning { rate, cleaning_fee } => {
+                is_valid_rate(rate) && is_valid_amount(cleaning_fee)
+            }
+            FeeRule::FlatMonthly { amount } => is_valid_amount(amount),
+        };
+
+        if !is_valid_rule {
+            return Err(format!(
+                "the agreement effective from {from} has a rate outside of 0 to 1, or a negative amount"
+            ));
+        }
+
+        if agreement.effective_until.is_some_and(|until| until < from) {
+            return Err(format!(
+                "the agreement effective from {from} ends before it takes effect"
+            ));
+        }
+    }
+
+    let mut sorted: Vec<&FeeAgreement> = agreements.iter().collect();
+    sorted.sort_by_key(|agreement| agreement.effective_from);
+
+    for pair in sorted.windows(2) {
+        let (previous, next) = (pair[0], pair[1]);
+
+        if previous
+            .effective_until
+            .is_none_or(|until| until >= next.effective_from)
+        {
+            return Err(format!(
+                "the agreements effective from {} and {} overlap",
+                previous.effective_from, next.effective_from
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn is_valid_rate(rate: f32) -> bool {
+    (0.0..=1.0).contains(&rate)
+}
+
+fn is_valid_amount(amount: f32) -> bool {
+    amount.is_finite() && amount >= 0.0
+}
+
+/// Get the management fee for a reservation, or `None` if the rule does not
+/// charge per reservation (e.g., a flat monthly fee).
+pub fn get_reservation_fee(rule: &FeeRule, reservation: &Reservation) -> Option<f32> {
+    match rule {
+        FeeRule::PercentOfRevenue { rate } => Some(round_cents(reservation.revenue * rate)),
+        FeeRule::PercentOfRevenueMinusCleaning { rate, cleaning_fee } => {
+            let revenue = (reservation.revenue - cleaning_fee).max(0.0);
+            Some(round_cents(revenue * rate))
+        }
+        FeeRule::FlatMonthly { .. } => None,
+    }
+}
+
+/// Check whether two fees differ by more than [`FEE_TOLERANCE`].
+pub fn is_mismatch(actual: f32, expected: f32) -> bool {
+    (actual - expected).abs() > FEE_TOLERANCE
+}
+
+fn round_cents(amount: f32) -> f32 {
+    (amount * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn agreement(rate: f32, from: NaiveDate, until: Option<NaiveDate>) -> FeeAgreement {
+        FeeAgreement {
+            rule: FeeRule::PercentOfRevenue { rate },
+            effective_from: from,
+            effective_until: until,
+        }
+    }
+
+    fn reservation(revenue: f32) -> Reservation {
+        let check_in = date(2025, 1, 10).and_hms_opt(0, 0, 0).unwrap();
+
+        Reservation {
+            platform: "airbnb".to_string(),
+            payout_date: check_in,
+            check_in,
+            check_out: check_in,
+            revenue,
+            management_fee: 0.0,
+            net_profit: 0.0,
+            sheet: "January".to_string(),
+            row: 2,
+            warnings: Vec::new(),
+        }
+    }
+
+    fn get_rate(agreement: Option<&FeeAgreement>) -> Option<f32> {
+        agreement.map(|agreement| match agreement.rule {
+            FeeRule::PercentOfRevenue { rate } => rate,
+            _ => unreachable!(),
+        })
+    }
+
+    #[test]
+    fn agreements_apply_from_their_first_to_their_last_day() {
+        let agreements = [
+            agreement(0.2, date(2024, 1, 1), Some(date(2024, 12, 31))),
+            agreement(0.25, date(2025, 1, 1), None),
+        ];
+        let rate_on = |day| get_rate(get_fee_agreement(&agreements, day));
+
+        assert_eq!(rate_on(date(2023, 12, 31)), None);
+        assert_eq!(rate_on(date(2024, 1, 1)), Some(0.2));
+        assert_eq!(rate_on(date(2024, 12, 31)), Some(0.2));
+        assert_eq!(rate_on(date(2025, 1, 1)), Some(0.25));
+        assert_eq!(rate_on(date(2030, 6, 15)), Some(0.25));
+    }
+
+    #[test]
+    fn gaps_between_agreements_have_no_agreement() {
+        let agreements = [
+            agreement(0.2, date(2024, 1, 1), Some(date(2024, 6, 30))),
+            agreement(0.25, date(2024, 8, 1), None),
+        ];
+
+        assert!(get_fee_agreement(&agreements, date(2024, 7, 15)).is_none());
+        assert!(get_fee_agreement(&agreements, date(2024, 8, 1)).is_some());
+    }
+
+    #[test]
+    fn overlapping_agreements_prefer_the_most_recent() {
+        let agreements = [
+            agreement(0.25, date(2025, 1, 1), None),
+            agreement(0.2, date(2024, 1, 1), None),
+        ];
+
+        assert_eq!(
+            get_rate(get_fee_agreement(&agreements, date(2024, 12, 31))),
+            Some(0.2)
+        );
+        assert_eq!(
+            get_rate(get_fee_agreement(&agreements, date(2025, 1, 1))),
+            Some(0.25)
+        );
+    }
+
+    #[test]
+    fn fees_are_rounded_to_the_cent() {
+        let rule = FeeRule::PercentOfRevenue { rate: 0.15 };
+        assert_eq!(get_reservation_fee(&rule, &reservation(333.4)), Some(50.01));
+        assert_eq!(get_reservation_fee(&rule, &reservation(0.0)), Some(0.0));
+
+        let rule = FeeRule::PercentOfRevenueMinusCleaning {
+            rate: 0.2,
+            cleaning_fee: 100.0,
+        };
+        assert_eq!(get_reservation_fee(&rule, &reservation(600.0)), Some(100.0));
+        assert_eq!(get_reservation_fee(&rule, &reservation(100.0)), Some(0.0));
+        assert_eq!(get_reservation_fee(&rule, &reservation(80.0)), Some(0.0));
+
+        let rule = FeeRule::FlatMonthly { amount: 250.0 };
+        assert_eq!(get_reservation_fee(&rule, &reservation(600.0)), None);
+    }
+
+    #[test]
+    fn fees_within_the_tolerance_match() {
+        assert!(!is_mismatch(100.0, 100.0));
+        assert!(!is_mismatch(100.0, 100.005));
+        assert!(is_mismatch(100.0, 100.02));
+        assert!(is_mismatch(100.02, 100.0));
+    }
+
+    #[test]
+    fn valid_agreements_are_accepted() {
+        assert_eq!(validate_fee_agreements(&[]), Ok(()));
+
+        let agreements = [
+            agreement(0.0, date(2023, 1, 1), Some(date(2023, 1, 1))),
+            agreement(1.0, date(2023, 1, 2), Some(date(2024, 12, 31))),
+            FeeAgreement {
+                rule: FeeRule::FlatMonthly { amount: 0.0 },
+                effective_from: date(2025, 1, 1),
+                effective_until: None,
+            },
+        ];
+        assert_eq!(validate_fee_agreements(&agreements), Ok(()));
+    }
+
+    #[test]
+    fn invalid_agreements_are_rejected() {
+        let invalid = [
+            vec![agreement(1.5, date(2025, 1, 1), None)],
+            vec![agreement(-0.1, date(2025, 1, 1), None)],
+            vec![agreement(f32::NAN, date(2025, 1, 1), None)],
+            vec![FeeAgreement {
+                rule: FeeRule::PercentOfRevenueMinusCleaning {
+                    rate: 0.2,
+                    cleaning_fee: -50.0,
+                },
+                effective_from: date(2025, 1, 1),
+                effective_until: None,
+            }],
+            vec![agreement(0.2, date(2025, 1, 2), Some(date(2025, 1, 1)))],
+            // The first agreement has not ended when the second takes effect.
+            vec![
+                agreement(0.25, date(2025, 1, 1), None),
+                agreement(0.2, date(2024, 1, 1), None),
+            ],
+            // Both agreements apply on the last day of the first.
+            vec![
+                agreement(0.2, date(2024, 1, 1), Some(date(2024, 12, 31))),
+                agreement(0.25, date(2024, 12, 31), None),
+            ],
+        ];
+
+        for agreements in invalid {
+            assert!(
+                validate_fee_agreements(&agreements).is_err(),
+                "{agreements:?}"
+            );
+        }
+    }
+}
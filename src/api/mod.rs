@@ -1,8 +1,13 @@
 //! Implementation details for the backend API.
 
-mod error;
-mod model;
+mod changes;
+pub(crate) mod error;
+mod extract;
+mod fees;
+pub(crate) mod model;
 mod routes;
-mod service;
+pub(crate) mod service;
+mod suggestion;
+mod validation;
 
 pub use routes::get_router;
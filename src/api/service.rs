@@ -2,19 +2,38 @@
 
 use std::str::FromStr;
 
-use axum::response::Response;
 use chrono::Datelike;
 use futures::TryStreamExt;
 use mongodb::bson::doc;
 use mongodb::bson::oid::ObjectId;
 use reqwest::StatusCode;
 use serde::Deserialize;
-use sheets::{self, ValueRange};
+use sheets::{self, A1Range, CellValue, GetValuesOptions, RowReader, ValueRange};
 
-use crate::http_error;
+use crate::audit::{self, AuditAction};
 
-use super::error::{ExpenseError, PropertyError, ReservationError, UserError};
-use super::model::{Expense, Month, Property, Reservation, User};
+use crate::notifications::{Message, Notification, NotificationKind, Notifier};
+use crate::ocr::{self, OcrError};
+use crate::storage::{Storage, StorageError};
+use crate::webhooks;
+
+use super::changes::{diff_rows, SnapshotRow};
+use super::error::{
+    BudgetError, ExpenseError, ExpenseSheetError, NotificationError, PayoutError, PropertyError,
+    ReceiptError, ReservationError, StatementError, SummaryError, UserError, WebhookError,
+};
+use super::fees::{get_fee_agreement, get_reservation_fee, is_mismatch, validate_fee_agreements};
+use super::model::{
+    Budget, BudgetAlert, BudgetPeriod, BudgetReport, BudgetStatus, Change, ChangeKind,
+    DeliveryStatus, Event, Expense, ExpenseMatching, ExpenseSuggestion, FeeAgreement, FeeReport,
+    FeeRule, FinancialSummary, Forecast, Month, MonthSheet, MonthlyForecast, NewBudget, NewPayout,
+    NewWebhook, NotificationDelivery, NotificationPreferences, Payout, Portfolio, Property,
+    PropertySummary, ProvisioningReport, Receipt, Reconciliation, ReconciliationPeriod,
+    Reservation, ReservationFee, SheetReport, Statement, UnmatchedExpense, UnmatchedExpenseReport,
+    User, ValidationReport, VoidPayout, Webhook, WebhookDelivery, WebhookDeliveryStatus,
+};
+use super::suggestion::suggest_expense;
+use super::validation::validate_reservations;
 
 #[derive(Debug, serde::Deserialize)]
 struct ExpenseSheetDocument {
@@ -25,26 +44,62 @@ struct ExpenseSheetDocument {
 pub async fn get_expense_sheet_id_by_year(
     year: i32,
     database: &mongodb::Database,
-) -> Result<String, Response> {
+) -> Result<String, ExpenseSheetError> {
     let document: ExpenseSheetDocument = database
         .collection("expense_sheet")
         .find_one(doc! {"year": year})
         .await
-        .map_err(|err| http_error!(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
-        .ok_or_else(|| {
-            http_error!(
-                StatusCode::NOT_FOUND,
-                format!("expense sheet not found for year {year}")
-            )
-        })?;
+        .map_err(|err| ExpenseSheetError::RequestFailure(err.to_string()))?
+        .ok_or(ExpenseSheetError::NotFound(year))?;
 
     Ok(document.id)
 }
 
+/// The URL of the Clerk Backend API.
+const CLERK_API_URL: &str = "https://api.clerk.com/v1";
+
+/// Create the indexes the service functions rely on, if they do not exist yet.
+pub async fn create_indexes(database: &mongodb::Database) -> Result<(), mongodb::error::Error> {
+    // Each threshold of a budget is only triggered once per period.
+    database
+        .collection::<BudgetAlertDocument>("budget_alert")
+        .create_index(
+            mongodb::IndexModel::builder()
+                .keys(doc! {"budget_id": 1, "period": 1, "threshold": 1})
+                .options(
+                    mongodb::options::IndexOptions::builder()
+                        .unique(true)
+                        .build(),
+                )
+                .build(),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Check whether a write failed because it would have duplicated the key of a
+/// unique index.
+fn is_duplicate_key_error(err: &mongodb::error::Error) -> bool {
+    const DUPLICATE_KEY: i32 = 11000;
+
+    match err.kind.as_ref() {
+        mongodb::error::ErrorKind::Write(mongodb::error::WriteFailure::WriteError(err)) => {
+            err.code == DUPLICATE_KEY
+        }
+        _ => false,
+    }
+}
+
 /// Get information about a user via user ID.
 pub async fn get_user_by_id(id: &str, key: &str) -> Result<User, UserError> {
-    static BASE_URL: &'static str = "https://api.clerk.com/v1/users";
-    let url = format!("{BASE_URL}/{id}");
+    get_user_by_id_from(CLERK_API_URL, id, key).await
+}
+
+/// Get information about a user via user ID from the Clerk API at `api_url`
+/// (e.g., a mock server in tests).
+async fn get_user_by_id_from(api_url: &str, id: &str, key: &str) -> Result<User, UserError> {
+    let url = format!("{api_url}/users/{id}");
 
     let response = reqwest::Client::new()
         .get(url)
@@ -59,11 +114,17 @@ pub async fn get_user_by_id(id: &str, key: &str) -> Result<User, UserError> {
             Some(StatusCode::BAD_REQUEST) => UserError::BadId(id.to_string()),
             Some(StatusCode::UNAUTHORIZED) => UserError::InvalidApiKey,
             Some(StatusCode::NOT_FOUND) => UserError::NotFound(id.to_string()),
-            _ => unreachable!("all status codes should be handled"),
+            Some(StatusCode::TOO_MANY_REQUESTS) => UserError::RateLimited,
+            Some(status) if status.is_server_error() => UserError::Unavailable,
+            _ => UserError::RequestFailure(err.to_string()),
         })?;
 
-    let body = response.text().await.expect("failed to get response body");
-    let user: User = serde_json::from_str(&body).expect("expected body to be valid JSON");
+    let body = response
+        .text()
+        .await
+        .map_err(|err| UserError::RequestFailure(err.to_string()))?;
+    let user: User =
+        serde_json::from_str(&body).map_err(|err| UserError::InvalidResponse(err.to_string()))?;
 
     Ok(user)
 }
@@ -72,7 +133,12 @@ pub async fn get_user_by_id(id: &str, key: &str) -> Result<User, UserError> {
 struct PropertyDocument {
     #[serde(rename = "_id")]
     id: ObjectId,
+    user_id: String,
     name: String,
+    #[serde(default)]
+    fee_agreements: Vec<FeeAgreement>,
+    #[serde(default)]
+    expense_matching: ExpenseMatching,
 }
 
 /// Get all of the properties that belong to the specified user.
@@ -89,14 +155,49 @@ pub async fn get_properties_by_user(
     let documents = cursor
         .try_collect::<Vec<PropertyDocument>>()
         .await
-        .expect("failed to deserialize property document");
+        .map_err(|err| PropertyError::RequestFailure(err.to_string()))?;
 
     let properties: Vec<Property> = documents
         .iter()
         .map(|property| Property {
             id: property.id.to_string(),
+            user_id: property.user_id.to_string(),
             name: property.name.to_string(),
             address: None,
+            fee_agreements: property.fee_agreements.clone(),
+            expense_matching: property.expense_matching.clone(),
+        })
+        .collect();
+
+    Ok(properties)
+}
+
+/// Get every property, regardless of its owner.
+///
+/// This should only be used by administrators and background jobs.
+pub async fn get_all_properties(
+    database: &mongodb::Database,
+) -> Result<Vec<Property>, PropertyError> {
+    let cursor = database
+        .collection("property")
+        .find(doc! {})
+        .await
+        .map_err(|err| PropertyError::RequestFailure(err.to_string()))?;
+
+    let documents = cursor
+        .try_collect::<Vec<PropertyDocument>>()
+        .await
+        .map_err(|err| PropertyError::RequestFailure(err.to_string()))?;
+
+    let properties: Vec<Property> = documents
+        .into_iter()
+        .map(|property| Property {
+            id: property.id.to_string(),
+            user_id: property.user_id,
+            name: property.name,
+            address: None,
+            fee_agreements: property.fee_agreements,
+            expense_matching: property.expense_matching,
         })
         .collect();
 
@@ -110,7 +211,7 @@ pub async fn get_property_by_id(
     database: &mongodb::Database,
 ) -> Result<Property, PropertyError> {
     let property_id: ObjectId =
-        ObjectId::from_str(&id).map_err(|_| PropertyError::BadId(id.to_string()))?;
+        ObjectId::from_str(id).map_err(|_| PropertyError::BadId(id.to_string()))?;
 
     let document: PropertyDocument = database
         .collection("property")
@@ -121,69 +222,358 @@ pub async fn get_property_by_id(
 
     Ok(Property {
         id: document.id.to_string(),
+        user_id: document.user_id.to_string(),
+        name: document.name.to_string(),
+        address: None,
+        fee_agreements: document.fee_agreements,
+        expense_matching: document.expense_matching,
+    })
+}
+
+/// Get information about a property via property ID, regardless of its owner.
+///
+/// This should only be used by administrators; users should only be able to
+/// access their own properties through [`get_property_by_id`].
+pub async fn get_property_by_id_as_admin(
+    id: &str,
+    database: &mongodb::Database,
+) -> Result<Property, PropertyError> {
+    let property_id: ObjectId =
+        ObjectId::from_str(id).map_err(|_| PropertyError::BadId(id.to_string()))?;
+
+    let document: PropertyDocument = database
+        .collection("property")
+        .find_one(doc! {"_id": property_id})
+        .await
+        .map_err(|err| PropertyError::RequestFailure(err.to_string()))?
+        .ok_or_else(|| PropertyError::NotFound(id.to_string()))?;
+
+    Ok(Property {
+        id: document.id.to_string(),
+        user_id: document.user_id.to_string(),
         name: document.name.to_string(),
         address: None,
+        fee_agreements: document.fee_agreements,
+        expense_matching: document.expense_matching,
     })
 }
 
-#[derive(Debug, Default, serde::Deserialize)]
+/// Replace how rows of the shared expense sheet are matched to a property.
+pub async fn set_expense_matching(
+    property: &Property,
+    matching: &ExpenseMatching,
+    database: &mongodb::Database,
+) -> Result<(), PropertyError> {
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let collection = database.collection::<PropertyDocument>("property");
+
+    // Rows with a code only match a single property, so codes must be unique.
+    if let Some(code) = &matching.code {
+        let duplicate = collection
+            .find_one(doc! {"_id": {"$ne": property_id}, "expense_matching.code": code})
+            .await
+            .map_err(|err| PropertyError::RequestFailure(err.to_string()))?;
+        if duplicate.is_some() {
+            return Err(PropertyError::DuplicateCode(code.to_string()));
+        }
+    }
+
+    let matching = mongodb::bson::to_bson(matching)
+        .map_err(|err| PropertyError::RequestFailure(err.to_string()))?;
+
+    collection
+        .update_one(
+            doc! {"_id": property_id},
+            doc! {"$set": {"expense_matching": matching}},
+        )
+        .await
+        .map_err(|err| PropertyError::RequestFailure(err.to_string()))?;
+
+    audit::record(
+        AuditAction::Update,
+        "expense_matching",
+        None,
+        Some(&property.id),
+        database,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Replace the management fee agreements stored on a property.
+pub async fn set_fee_agreements(
+    property: &Property,
+    agreements: &[FeeAgreement],
+    database: &mongodb::Database,
+) -> Result<(), PropertyError> {
+    validate_fee_agreements(agreements).map_err(PropertyError::InvalidFeeAgreements)?;
+
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let agreements = mongodb::bson::to_bson(agreements)
+        .map_err(|err| PropertyError::RequestFailure(err.to_string()))?;
+
+    database
+        .collection::<PropertyDocument>("property")
+        .update_one(
+            doc! {"_id": property_id},
+            doc! {"$set": {"fee_agreements": agreements}},
+        )
+        .await
+        .map_err(|err| PropertyError::RequestFailure(err.to_string()))?;
+
+    audit::record(
+        AuditAction::Update,
+        "fee_agreements",
+        None,
+        Some(&property.id),
+        database,
+    )
+    .await;
+
+    Ok(())
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 struct ExpenseValues(
-    String,                     // [0]: Timestamp
-    #[allow(dead_code)] String, // [1]: Date
-    String,                     // [2]: Property (name)
-    String,                     // [3]: Amount
-    String,                     // [4]: Description
-    #[serde(default)] String,   // [5]: Receipt (link)
-    #[serde(default)] String,   // [6]: Merchant
-    #[serde(default)] String,   // [7]: Name (of the employee that purchased the item)
+    CellValue,                     // [0]: Timestamp
+    #[allow(dead_code)] CellValue, // [1]: Date
+    CellValue,                     // [2]: Property (name)
+    CellValue,                     // [3]: Amount
+    CellValue,                     // [4]: Description
+    #[serde(default)] CellValue,   // [5]: Receipt (link)
+    #[serde(default)] CellValue,   // [6]: Merchant
+    #[serde(default)] CellValue,   // [7]: Name (of the employee that purchased the item)
+    #[serde(default)] CellValue,   // [8]: Category
+    #[serde(default)] CellValue,   // [9]: Property Code
 );
 
 pub async fn get_expenses_by_year(
     property: &Property,
     year: i32,
-    sheets_client: &mut sheets::Client,
+    sheets_client: &sheets::Client,
     database: &mongodb::Database,
 ) -> Result<Vec<Expense>, ExpenseError> {
-    let expense_sheet_id = get_expense_sheet_id_by_year(year, &database)
+    let (rows, _) =
+        get_expense_rows(property, year, FIRST_EXPENSE_ROW, sheets_client, database).await?;
+
+    let expenses = rows
+        .iter()
+        .map(|(row, values)| parse_expense(*row, values))
+        .collect::<Result<Vec<Expense>, ExpenseError>>()?;
+
+    Ok(expenses)
+}
+
+/// The first row of the expense sheet after the header row.
+const FIRST_EXPENSE_ROW: u32 = 2;
+
+/// The number of rows of the expense sheet read at a time.
+const EXPENSE_WINDOW: u32 = 500;
+
+/// The columns of the expense sheet, from `Timestamp` to `Property Code`.
+const EXPENSE_COLUMNS: (u32, u32) = (1, 10);
+
+/// The index of the `Property` column, holding the name of the property.
+const EXPENSE_NAME_COLUMN: usize = 2;
+
+/// The index of the `Property Code` column (see [`ExpenseMatching::code`]).
+const EXPENSE_CODE_COLUMN: usize = 9;
+
+/// Get the rows of the year's expense sheet that belong to the property,
+/// starting at the row, along with their numbers.
+///
+/// Also returns the row after the last one in the sheet, where the next read
+/// can start.
+async fn get_expense_rows(
+    property: &Property,
+    year: i32,
+    from_row: u32,
+    sheets_client: &sheets::Client,
+    database: &mongodb::Database,
+) -> Result<(Vec<(u32, ExpenseValues)>, u32), ExpenseError> {
+    read_expense_rows(year, from_row, sheets_client, database, |values| {
+        is_property_expense(property, values)
+    })
+    .await
+}
+
+/// Get the rows of the year's expense sheet that are kept, starting at the
+/// row, along with their numbers.
+///
+/// The sheet is shared by every property, so it is read a window of rows at
+/// a time, and only the rows that are kept are parsed.
+async fn read_expense_rows(
+    year: i32,
+    from_row: u32,
+    sheets_client: &sheets::Client,
+    database: &mongodb::Database,
+    keep: impl Fn(&serde_json::Value) -> bool,
+) -> Result<(Vec<(u32, ExpenseValues)>, u32), ExpenseError> {
+    let expense_sheet_id = get_expense_sheet_id_by_year(year, database)
         .await
-        .map_err(|_| {
-            ExpenseError::RequestFailure(format!("failed to get id for {year}'s expense sheet"))
+        .map_err(|err| match err {
+            ExpenseSheetError::NotFound(year) => ExpenseError::SheetNotFound(year),
+            ExpenseSheetError::RequestFailure(..) => {
+                ExpenseError::RequestFailure(format!("failed to get id for {year}'s expense sheet"))
+            }
         })?;
 
-    let result: ValueRange<ExpenseValues> =
-        sheets::get_values(sheets_client, &expense_sheet_id, "Expenses!A:H")
-            .await
-            .map_err(|err| ExpenseError::RequestFailure(err.to_string()))?;
+    let (first_column, last_column) = EXPENSE_COLUMNS;
+    let range = A1Range::new("Expenses").columns(first_column, last_column);
+    let mut reader = RowReader::new(expense_sheet_id, range)
+        .from_row(from_row)
+        .window(EXPENSE_WINDOW)
+        .options(GetValuesOptions::unformatted());
 
-    let expenses: Vec<Expense> = result
-        .values
-        .iter()
-        .skip(1) // Skip header row.
-        .filter_map(|values| {
-            if values.2.to_lowercase() != property.name {
-                return None;
+    let mut rows = Vec::new();
+    while let Some(window) = reader
+        .next::<serde_json::Value>(sheets_client)
+        .await
+        .map_err(ExpenseError::Sheets)?
+    {
+        for (row, values) in window.numbered() {
+            if !keep(&values) {
+                continue;
             }
 
-            let expense = Expense {
-                amount: normalize_price(&values.3)
-                    .parse()
-                    .expect("failed to parse expense amount"),
-                description: values.4.trim().to_string(),
-                timestamp: try_parse_timestamp(&values.0).expect("failed to parse timestamp"),
-                buyers_name: values.7.trim().to_string(),
-                merchant: values.6.trim().to_string(),
-                receipt_link: values.5.trim().to_string(),
-            };
+            let values: ExpenseValues = serde_json::from_value(values).map_err(|err| {
+                ExpenseError::Sheets(sheets::GetValuesError::InvalidResponse(format!(
+                    "row {row}: {err}"
+                )))
+            })?;
+            rows.push((row, values));
+        }
+    }
+
+    Ok((rows, reader.next_row()))
+}
+
+/// Check whether a row of the expense sheet belongs to the property, without
+/// parsing the rest of the row (see [`ExpenseMatching`]).
+fn is_property_expense(property: &Property, values: &serde_json::Value) -> bool {
+    if let Some(code) = get_expense_cell(values, EXPENSE_CODE_COLUMN) {
+        let matching_code = property.expense_matching.code.as_deref();
+        return matching_code
+            .is_some_and(|matching_code| matching_code.eq_ignore_ascii_case(&code))
+            || property.id == code;
+    }
+
+    let Some(name) = get_expense_cell(values, EXPENSE_NAME_COLUMN) else {
+        return false;
+    };
+    let name = name.to_lowercase();
+
+    std::iter::once(&property.name)
+        .chain(&property.expense_matching.aliases)
+        .any(|other| other.trim().to_lowercase() == name)
+}
+
+/// Get the trimmed text of a cell of an unparsed row, if it is not empty;
+/// numbers are read as text, since codes can be entered as numbers.
+fn get_expense_cell(values: &serde_json::Value, index: usize) -> Option<String> {
+    let text = match values.get(index)? {
+        serde_json::Value::String(text) => text.trim().to_string(),
+        serde_json::Value::Number(number) => number.to_string(),
+        _ => return None,
+    };
+
+    (!text.is_empty()).then_some(text)
+}
+
+/// Find the rows of a year's expense sheet that do not belong to any
+/// property, such as rows entered with a misspelled name.
+pub async fn get_unmatched_expenses(
+    year: i32,
+    sheets_client: &sheets::Client,
+    database: &mongodb::Database,
+) -> Result<UnmatchedExpenseReport, ExpenseError> {
+    let properties = get_all_properties(database)
+        .await
+        .map_err(|err| ExpenseError::RequestFailure(err.to_string()))?;
+
+    let (rows, _) = read_expense_rows(year, FIRST_EXPENSE_ROW, sheets_client, database, |values| {
+        // Rows that were left empty are not expenses.
+        let is_empty = values
+            .as_array()
+            .is_none_or(|cells| cells.iter().all(|cell| cell == ""));
+
+        !is_empty
+            && !properties
+                .iter()
+                .any(|property| is_property_expense(property, values))
+    })
+    .await?;
 
-            Some(expense)
+    let expenses = rows
+        .into_iter()
+        .map(|(row, values)| UnmatchedExpense {
+            row,
+            property: values.2.to_string().trim().to_string(),
+            property_code: (!values.9.is_empty()).then(|| values.9.to_string().trim().to_string()),
+            amount: get_cell_amount(&values.3),
+            description: values.4.to_string().trim().to_string(),
+            timestamp: get_cell_datetime(&values.0),
         })
         .collect();
 
-    Ok(expenses)
+    Ok(UnmatchedExpenseReport { year, expenses })
+}
+
+fn parse_expense(row: u32, values: &ExpenseValues) -> Result<Expense, ExpenseError> {
+    let invalid = |column: &str, value: &CellValue| {
+        ExpenseError::InvalidRow(row, format!("invalid {column} '{value}'"))
+    };
+
+    Ok(Expense {
+        amount: get_cell_amount(&values.3).ok_or_else(|| invalid("amount", &values.3))?,
+        description: values.4.to_string().trim().to_string(),
+        timestamp: get_cell_datetime(&values.0).ok_or_else(|| invalid("timestamp", &values.0))?,
+        buyers_name: values.7.to_string().trim().to_string(),
+        merchant: values.6.to_string().trim().to_string(),
+        receipt_link: values.5.to_string().trim().to_string(),
+        category: values.8.to_string().trim().to_string(),
+        row,
+    })
+}
+
+/// Get the amount of a cell, such as `1200` or `$1,200.00` for cells that
+/// were formatted as plain text.
+fn get_cell_amount(value: &CellValue) -> Option<f32> {
+    let amount = match value {
+        CellValue::Text(text) => normalize_price(text).trim().parse().ok()?,
+        _ => value.as_f64()?,
+    };
+
+    Some(amount as f32)
+}
+
+fn normalize_price(price: &str) -> String {
+    price.replace("$", "").replace(",", "")
+}
+
+/// Get the date and time of a cell, which is a serial number unless the cell
+/// was formatted as plain text (e.g., `2025-01-31 14:05:00`).
+fn get_cell_datetime(value: &CellValue) -> Option<chrono::NaiveDateTime> {
+    match value {
+        CellValue::Text(text) => try_parse_timestamp(text.trim()),
+        _ => value.as_datetime(),
+    }
+}
+
+/// Get the date of a cell, which is a serial number unless the cell was
+/// formatted as plain text (e.g., `1/31/2025`).
+fn get_cell_date(value: &CellValue) -> Option<chrono::NaiveDate> {
+    match value {
+        CellValue::Text(text) => chrono::NaiveDate::parse_from_str(text.trim(), "%-m/%-d/%Y").ok(),
+        _ => value.as_date(),
+    }
 }
 
 fn try_parse_timestamp(timestamp: &str) -> Option<chrono::NaiveDateTime> {
-    static FORMAT: &'static [&str] = &[
+    static FORMAT: &[&str] = &[
         "%Y-%m-%d %H:%M:%S",
         "%Y-%m-%d %-H:%M:%S",
         "%Y-%m-%dT%H:%M:%S%z",
@@ -196,21 +586,23 @@ fn try_parse_timestamp(timestamp: &str) -> Option<chrono::NaiveDateTime> {
         "%-m/%d/%Y %-H:%M:%S",
     ];
 
-    for &format in FORMAT.iter() {
-        match chrono::NaiveDateTime::parse_from_str(timestamp, format) {
-            Ok(dt) => return Some(dt),
-            Err(err) => println!("{err}"),
-        };
-    }
-
-    None
+    FORMAT.iter().find_map(|format| {
+        chrono::NaiveDateTime::parse_from_str(timestamp, format)
+            .ok()
+            // Formats without a time are read as midnight.
+            .or_else(|| {
+                chrono::NaiveDate::parse_from_str(timestamp, format)
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+            })
+    })
 }
 
 pub async fn get_expenses_by_month(
     property: &Property,
     year: i32,
     month: u8,
-    sheets_client: &mut sheets::Client,
+    sheets_client: &sheets::Client,
     database: &mongodb::Database,
 ) -> Result<Vec<Expense>, ExpenseError> {
     Ok(
@@ -218,7 +610,7 @@ pub async fn get_expenses_by_month(
             .await?
             .into_iter()
             .filter_map(move |expense| {
-                (expense.timestamp.month() == (month as u32)).then(|| expense)
+                (expense.timestamp.month() == (month as u32)).then_some(expense)
             })
             .collect::<Vec<Expense>>(),
     )
@@ -233,26 +625,170 @@ struct SpreadsheetDocument {
     year: i32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, serde::Serialize, Deserialize)]
 struct ReservationValues(
-    String, // [0]: Platform
-    String, // [1]: Date Paid Out
-    String, // [2]: Check-in
-    String, // [3]: Check-out
-    String, // [4]: Revenue
-    String, // [5]: Management Fee
-    String, // [6]: Net Profit
+    CellValue, // [0]: Platform
+    CellValue, // [1]: Date Paid Out
+    CellValue, // [2]: Check-in
+    CellValue, // [3]: Check-out
+    CellValue, // [4]: Revenue
+    CellValue, // [5]: Management Fee
+    CellValue, // [6]: Net Profit
 );
 
+/// The version of how sheet values are stored in caches and snapshots.
+///
+/// Documents stored with another version are ignored rather than parsed
+/// (e.g., values stored as formatted text before they were read unformatted).
+const SHEET_VALUES_VERSION: i32 = 2;
+
+/// How long reservations read from a spreadsheet are reused before they are
+/// read again.
+///
+/// The cache for the current month is refreshed every night, so this covers a
+/// day (with some leeway) to keep the first requests of the day from waiting
+/// on Google Sheets. Every month of the current year is also refreshed
+/// whenever changes are detected, so those are never more than a few minutes
+/// old; only other years can be up to a day old.
+const RESERVATION_CACHE_MAX_AGE: chrono::TimeDelta = chrono::TimeDelta::hours(25);
+
+#[derive(Debug, serde::Serialize, Deserialize)]
+struct ReservationCacheDocument {
+    /// See [`SHEET_VALUES_VERSION`].
+    version: i32,
+    property_id: ObjectId,
+    year: i32,
+    month: u8,
+    values: Vec<ReservationValues>,
+    fetched_at: chrono::DateTime<chrono::Utc>,
+}
+
 pub async fn get_reservations_by_month(
     property: &Property,
     year: i32,
     month: u8,
     database: &mongodb::Database,
-    sheets_client: &mut sheets::Client,
+    sheets_client: &sheets::Client,
 ) -> Result<Vec<Reservation>, ReservationError> {
+    let values =
+        get_reservation_values(property, year, month, false, database, sheets_client).await?;
+
+    // The month was already validated when getting the values.
+    let month: Month = month.try_into().unwrap();
+
+    let mut reservations = values
+        .iter()
+        .enumerate()
+        .skip(1) // Skip the table headings.
+        .filter(|(_, values)| is_reservation(values))
+        .map(|(index, values)| parse_reservation(&month, (index + 1) as u32, values))
+        .collect::<Result<Vec<Reservation>, ReservationError>>()?;
+
+    for issue in validate_reservations(&reservations) {
+        if let Some(reservation) = reservations.iter_mut().find(|r| r.row == issue.row) {
+            reservation.warnings.push(issue);
+        }
+    }
+
+    Ok(reservations)
+}
+
+/// Check whether a row of a month's sheet contains a reservation; sheets have
+/// rows reserved for reservations that have not been filled in yet.
+fn is_reservation(values: &ReservationValues) -> bool {
+    values
+        .0
+        .as_str()
+        .is_some_and(|platform| !platform.is_empty() && platform != "#REF!")
+}
+
+fn parse_reservation(
+    month: &Month,
+    row: u32,
+    values: &ReservationValues,
+) -> Result<Reservation, ReservationError> {
+    let invalid = |column: &str, value: &CellValue| {
+        ReservationError::InvalidRow(
+            month.to_string(),
+            row,
+            format!("invalid {column} '{value}'"),
+        )
+    };
+    let date = |column: &str, value: &CellValue| {
+        get_cell_date(value).ok_or_else(|| invalid(column, value))
+    };
+    let amount = |column: &str, value: &CellValue| {
+        get_cell_amount(value).ok_or_else(|| invalid(column, value))
+    };
+
+    Ok(Reservation {
+        platform: values.0.to_string().trim().to_lowercase(),
+        payout_date: date("payout date", &values.1)?.into(),
+        check_in: date("check in date", &values.2)?.into(),
+        check_out: date("check out date", &values.3)?.into(),
+        revenue: amount("revenue", &values.4)?,
+        management_fee: if values.5.is_empty() {
+            0.0
+        } else {
+            amount("management fee", &values.5)?
+        },
+        net_profit: amount("net profit", &values.6)?,
+        sheet: month.to_string(),
+        row,
+        warnings: Vec::new(),
+    })
+}
+
+/// Read the reservations for a month from the spreadsheet into the cache,
+/// regardless of how recently they were cached.
+///
+/// Returns the number of rows that were read.
+pub async fn refresh_reservation_cache(
+    property: &Property,
+    year: i32,
+    month: u8,
+    database: &mongodb::Database,
+    sheets_client: &sheets::Client,
+) -> Result<usize, ReservationError> {
+    let values =
+        get_reservation_values(property, year, month, true, database, sheets_client).await?;
+
+    Ok(values.len())
+}
+
+/// Get the rows of a month's sheet, including the table headings.
+///
+/// Rows are read from the cache if they were cached recently, unless
+/// `refresh` is set; otherwise, they are read from the spreadsheet and cached.
+async fn get_reservation_values(
+    property: &Property,
+    year: i32,
+    month: u8,
+    refresh: bool,
+    database: &mongodb::Database,
+    sheets_client: &sheets::Client,
+) -> Result<Vec<ReservationValues>, ReservationError> {
     // Property ID should already be valid if we got to this point.
     let property_id = ObjectId::from_str(&property.id).unwrap();
+    let filter = doc! {"property_id": property_id, "year": year, "month": month as i32};
+    let collection = database.collection::<ReservationCacheDocument>("reservation_cache");
+
+    if !refresh {
+        let mut current = filter.clone();
+        current.insert("version", SHEET_VALUES_VERSION);
+
+        let cached = collection
+            .find_one(current)
+            .await
+            .map_err(|err| ReservationError::RequestFailure(err.to_string()))?;
+
+        if let Some(cached) = cached {
+            if chrono::Utc::now() - cached.fetched_at < RESERVATION_CACHE_MAX_AGE {
+                return Ok(cached.values);
+            }
+        }
+    }
+
     let spreadsheet: SpreadsheetDocument = database
         .collection("spreadsheet")
         .find_one(doc! {"property_id": property_id, "year": year})
@@ -260,57 +796,2880 @@ pub async fn get_reservations_by_month(
         .map_err(|err| ReservationError::RequestFailure(err.to_string()))?
         .ok_or_else(|| ReservationError::SpreadsheetNotFound(year, property.id.to_string()))?;
 
-    let month: Month = month
+    let sheet: Month = month
         .try_into()
         .map_err(|_| ReservationError::InvalidMonth)?;
 
-    let result: ValueRange<ReservationValues> =
-        sheets::get_values(sheets_client, &spreadsheet.id, &format!("{month}!A:G"))
-            .await
-            .map_err(|err| ReservationError::RequestFailure(err.to_string()))?;
+    let spreadsheet_id = spreadsheet.id.as_str();
+    let read = |title: String| async move {
+        sheets::get_values_with_options::<ReservationValues>(
+            sheets_client,
+            spreadsheet_id,
+            &A1Range::new(title).columns(1, 7).to_string(),
+            &GetValuesOptions::unformatted(),
+        )
+        .await
+    };
 
-    let reservations: Vec<Reservation> = result
-        .values
-        .iter()
-        .skip(1) // Skip the table headings.
-        .filter_map(|values| {
-            if values.0.is_empty() || values.0 == "#REF!" {
-                return None;
+    // Tabs are named by hand, so if there is no tab with the exact name of
+    // the month, look for one that matches it loosely (e.g., `Sept`).
+    let result: ValueRange<ReservationValues> = match read(sheet.to_string()).await {
+        Err(sheets::GetValuesError::InvalidRange(..)) => {
+            let metadata = sheets::get_spreadsheet(sheets_client, spreadsheet_id)
+                .await
+                .map_err(ReservationError::Metadata)?;
+            let titles: Vec<&str> = metadata.sheet_titles().collect();
+
+            match match_month_sheets(&titles)[usize::from(month - 1)] {
+                Some(index) => read(titles[index].to_string()).await,
+                None => return Err(ReservationError::SheetMissing(year, sheet.to_string())),
             }
+        }
+        result => result,
+    }
+    .map_err(ReservationError::Sheets)?;
 
-            let reservation = Reservation {
-                platform: values.0.trim().to_lowercase(),
-                payout_date: chrono::NaiveDate::parse_from_str(&values.1, "%-m/%-d/%Y")
-                    .expect("failed to parse payout date")
-                    .into(),
-                check_in: chrono::NaiveDate::parse_from_str(&values.2, "%-m/%-d/%Y")
-                    .expect("failed to parse check in date")
-                    .into(),
-                check_out: chrono::NaiveDate::parse_from_str(&values.3, "%-m/%-d/%Y")
-                    .expect("failed to parse check out date")
-                    .into(),
-                revenue: normalize_price(&values.4)
-                    .parse()
-                    .expect("failed to parse revenue"),
-                management_fee: if values.5.is_empty() {
-                    0.0
-                } else {
-                    normalize_price(&values.5)
-                        .parse()
-                        .expect("failed to parse management_fee")
+    let document = ReservationCacheDocument {
+        version: SHEET_VALUES_VERSION,
+        property_id,
+        year,
+        month,
+        values: result.values,
+        fetched_at: chrono::Utc::now(),
+    };
+
+    collection
+        .replace_one(filter, &document)
+        .upsert(true)
+        .await
+        .map_err(|err| ReservationError::RequestFailure(err.to_string()))?;
+
+    Ok(document.values)
+}
+
+/// Get the financial summary for every property that belongs to the user.
+///
+/// Properties are summarized concurrently. If a single property fails, the
+/// reason is reported alongside that property instead of failing the whole
+/// portfolio; the combined totals only include the successful properties.
+pub async fn get_portfolio_by_year(
+    user: &User,
+    year: i32,
+    sheets_client: &sheets::Client,
+    database: &mongodb::Database,
+) -> Result<Portfolio, PropertyError> {
+    let properties = get_properties_by_user(user, database).await?;
+
+    let properties: Vec<PropertySummary> =
+        futures::future::join_all(properties.into_iter().map(|property| async move {
+            match get_summary_by_year(&property, year, sheets_client, database).await {
+                Ok(summary) => PropertySummary {
+                    property,
+                    summary: Some(summary),
+                    error: None,
                 },
-                net_profit: normalize_price(&values.6)
-                    .parse()
-                    .expect("failed to parse net profit"),
-            };
+                Err(err) => PropertySummary {
+                    property,
+                    summary: None,
+                    error: Some(err.to_string()),
+                },
+            }
+        }))
+        .await;
+
+    Ok(Portfolio {
+        year,
+        total: get_portfolio_total(&properties),
+        properties,
+    })
+}
+
+/// Combine the summaries of the properties that were summarized successfully.
+fn get_portfolio_total(properties: &[PropertySummary]) -> FinancialSummary {
+    let mut total = FinancialSummary::default();
+
+    for summary in properties.iter().filter_map(|p| p.summary.as_ref()) {
+        total.revenue += summary.revenue;
+        total.expenses += summary.expenses;
+        total.net_profit += summary.net_profit;
+        total.nights_booked += summary.nights_booked;
+        total.nights_available += summary.nights_available;
+    }
+
+    total.occupancy = get_occupancy(total.nights_booked, total.nights_available);
+    total
+}
+
+/// Get the financial summary for a single property over the specified year.
+pub async fn get_summary_by_year(
+    property: &Property,
+    year: i32,
+    sheets_client: &sheets::Client,
+    database: &mongodb::Database,
+) -> Result<FinancialSummary, SummaryError> {
+    let (start, end) = get_year_bounds(year)?;
+
+    // Every month is read at the same time as the expenses.
+    let (months, expenses) = futures::future::join(
+        futures::future::join_all((1..=12).map(|month| {
+            get_reservations_by_month(property, year, month, database, sheets_client)
+        })),
+        get_expenses_by_year(property, year, sheets_client, database),
+    )
+    .await;
+
+    let mut reservations: Vec<Reservation> = Vec::new();
+
+    for values in months {
+        reservations.extend(values?);
+    }
+
+    Ok(summarize(start, end, &reservations, &expenses?))
+}
+
+/// Get the first day of a year, and the first day of the year after it.
+fn get_year_bounds(year: i32) -> Result<(chrono::NaiveDate, chrono::NaiveDate), SummaryError> {
+    let first_day = |year: i32| chrono::NaiveDate::from_ymd_opt(year, 1, 1);
+
+    first_day(year)
+        .zip(year.checked_add(1).and_then(first_day))
+        .ok_or(SummaryError::InvalidYear(year))
+}
 
-            Some(reservation)
+/// Summarize the reservations and expenses for the nights from `start` up to
+/// (but not including) `end`.
+fn summarize(
+    start: chrono::NaiveDate,
+    end: chrono::NaiveDate,
+    reservations: &[Reservation],
+    expenses: &[Expense],
+) -> FinancialSummary {
+    let revenue: f32 = reservations.iter().map(|r| r.revenue).sum();
+    let profit: f32 = reservations.iter().map(|r| r.net_profit).sum();
+    let expenses: f32 = expenses.iter().map(|e| e.amount).sum();
+
+    // Only count the nights that fall within the period; stays that cross
+    // into the next (or from the previous) period are split between both.
+    let nights_booked: i64 = reservations
+        .iter()
+        .map(|r| {
+            let check_in = r.check_in.date().max(start);
+            let check_out = r.check_out.date().min(end);
+            (check_out - check_in).num_days().max(0)
         })
-        .collect();
+        .sum();
+    let nights_available = (end - start).num_days();
 
-    Ok(reservations)
+    FinancialSummary {
+        revenue,
+        expenses,
+        net_profit: profit - expenses,
+        nights_booked,
+        nights_available,
+        occupancy: get_occupancy(nights_booked, nights_available),
+    }
 }
 
-fn normalize_price(price: &str) -> String {
-    price.replace("$", "").replace(",", "")
+fn get_occupancy(nights_booked: i64, nights_available: i64) -> f32 {
+    if nights_available == 0 {
+        return 0.0;
+    }
+
+    nights_booked as f32 / nights_available as f32
+}
+
+#[derive(Debug, serde::Serialize, Deserialize)]
+struct StatementDocument {
+    property_id: ObjectId,
+    year: i32,
+    month: u8,
+    summary: FinancialSummary,
+    reservations: Vec<Reservation>,
+    expenses: Vec<Expense>,
+    generated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<StatementDocument> for Statement {
+    fn from(document: StatementDocument) -> Self {
+        Self {
+            property_id: document.property_id.to_string(),
+            year: document.year,
+            month: document.month,
+            summary: document.summary,
+            reservations: document.reservations,
+            expenses: document.expenses,
+            generated_at: document.generated_at,
+        }
+    }
+}
+
+/// Generate the statement for a property over a month, replacing the existing
+/// statement if it was already generated (e.g., after correcting a mistake).
+///
+/// Also returns whether this is the first time the statement was generated.
+pub async fn generate_statement(
+    property: &Property,
+    year: i32,
+    month: u8,
+    sheets_client: &sheets::Client,
+    database: &mongodb::Database,
+) -> Result<(Statement, bool), StatementError> {
+    let start = chrono::NaiveDate::from_ymd_opt(year, month as u32, 1)
+        .ok_or(StatementError::InvalidMonth)?;
+    let end = start + chrono::Months::new(1);
+
+    let reservations = get_reservations_by_month(property, year, month, database, sheets_client)
+        .await
+        .map_err(|err| StatementError::RequestFailure(err.to_string()))?;
+    let expenses = get_expenses_by_month(property, year, month, sheets_client, database)
+        .await
+        .map_err(|err| StatementError::RequestFailure(err.to_string()))?;
+
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let document = StatementDocument {
+        property_id,
+        year,
+        month,
+        summary: summarize(start, end, &reservations, &expenses),
+        reservations,
+        expenses,
+        generated_at: chrono::Utc::now(),
+    };
+
+    let result = database
+        .collection::<StatementDocument>("statement")
+        .replace_one(
+            doc! {"property_id": property_id, "year": year, "month": month as i32},
+            &document,
+        )
+        .upsert(true)
+        .await
+        .map_err(|err| StatementError::RequestFailure(err.to_string()))?;
+
+    let created = result.upserted_id.is_some();
+    let action = if created {
+        AuditAction::Create
+    } else {
+        AuditAction::Update
+    };
+    let period = format!("{year}-{month:02}");
+    audit::record(
+        action,
+        "statement",
+        Some(&period),
+        Some(&property.id),
+        database,
+    )
+    .await;
+
+    Ok((document.into(), created))
+}
+
+/// Get the statement that was generated for a property over a month.
+pub async fn get_statement_by_month(
+    property: &Property,
+    year: i32,
+    month: u8,
+    database: &mongodb::Database,
+) -> Result<Statement, StatementError> {
+    if !(1..=12).contains(&month) {
+        return Err(StatementError::InvalidMonth);
+    }
+
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+
+    database
+        .collection::<StatementDocument>("statement")
+        .find_one(doc! {"property_id": property_id, "year": year, "month": month as i32})
+        .await
+        .map_err(|err| StatementError::RequestFailure(err.to_string()))?
+        .map(Statement::from)
+        .ok_or(StatementError::NotFound(year, month))
+}
+
+/// Check that the expense sheet and every property's spreadsheet have been
+/// set up for a year.
+pub async fn get_provisioning_report_by_year(
+    year: i32,
+    database: &mongodb::Database,
+) -> Result<ProvisioningReport, PropertyError> {
+    let has_expense_sheet = database
+        .collection::<ExpenseSheetDocument>("expense_sheet")
+        .count_documents(doc! {"year": year})
+        .await
+        .map_err(|err| PropertyError::RequestFailure(err.to_string()))?
+        > 0;
+
+    let cursor = database
+        .collection::<SpreadsheetDocument>("spreadsheet")
+        .find(doc! {"year": year})
+        .await
+        .map_err(|err| PropertyError::RequestFailure(err.to_string()))?;
+    let spreadsheets = cursor
+        .try_collect::<Vec<SpreadsheetDocument>>()
+        .await
+        .map_err(|err| PropertyError::RequestFailure(err.to_string()))?;
+
+    let missing_spreadsheets: Vec<Property> = get_all_properties(database)
+        .await?
+        .into_iter()
+        .filter(|property| {
+            !spreadsheets
+                .iter()
+                .any(|spreadsheet| spreadsheet.property_id.to_string() == property.id)
+        })
+        .collect();
+
+    Ok(ProvisioningReport {
+        year,
+        has_expense_sheet,
+        missing_spreadsheets,
+    })
+}
+
+/// The number of previous years used to calculate the seasonal baseline.
+const FORECAST_HISTORY_YEARS: i32 = 3;
+
+/// The deviation (as a fraction of the baseline) to use for the confidence
+/// band when there is not enough history to calculate a standard deviation.
+const FORECAST_FALLBACK_DEVIATION: f32 = 0.25;
+
+/// Project the net profit of a property for the remaining months of a year.
+///
+/// Each remaining month combines the reservations that are already booked
+/// with a seasonal baseline: the average net profit for that month over the
+/// previous years. The baseline only adds to the projection when bookings
+/// have not already exceeded it. Months that have already passed only
+/// report their actual net profit.
+pub async fn get_forecast_by_year(
+    property: &Property,
+    year: i32,
+    database: &mongodb::Database,
+    sheets_client: &sheets::Client,
+) -> Result<Forecast, ReservationError> {
+    let today = chrono::Utc::now().date_naive();
+
+    // There is nothing to project if the entire year is in the past.
+    let history_years = if year >= today.year() {
+        (year - FORECAST_HISTORY_YEARS)..year
+    } else {
+        year..year
+    };
+
+    // Every month of every year is read at the same time.
+    let (history, reservations) = futures::future::join(
+        futures::future::join_all(history_years.map(|previous_year| async move {
+            get_monthly_net_profit(property, previous_year, database, sheets_client)
+                .await
+                .map(|profits| profits.map(|profits| (previous_year, profits)))
+        })),
+        futures::future::join_all((1..=12u8).map(|month| {
+            get_reservations_by_month(property, year, month, database, sheets_client)
+        })),
+    )
+    .await;
+
+    let history: Vec<(i32, Vec<f32>)> = history
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let mut months: Vec<MonthlyForecast> = Vec::new();
+
+    for (month, reservations) in (1..=12u8).zip(reservations) {
+        let (actual, booked) = reservations?
+            .iter()
+            .fold((0.0, 0.0), |(actual, booked), r| {
+                if r.check_in.date() > today {
+                    (actual, booked + r.net_profit)
+                } else {
+                    (actual + r.net_profit, booked)
+                }
+            });
+
+        let is_past = (year, month as u32) < (today.year(), today.month());
+        let samples: Vec<f32> = history
+            .iter()
+            .map(|(_, profits)| profits[(month - 1) as usize])
+            .collect();
+
+        months.push(forecast_month(month, actual, booked, &samples, is_past));
+    }
+
+    Ok(Forecast {
+        year,
+        history: history.into_iter().map(|(year, _)| year).collect(),
+        months,
+    })
+}
+
+/// Forecast a single month from its actual and booked net profit, and the
+/// net profit of the same month in previous years.
+fn forecast_month(
+    month: u8,
+    actual: f32,
+    booked: f32,
+    samples: &[f32],
+    is_past: bool,
+) -> MonthlyForecast {
+    // Months that have already passed only report their actual net profit.
+    if is_past {
+        return MonthlyForecast {
+            month,
+            actual,
+            booked,
+            baseline: actual,
+            projected: actual,
+            lower: actual,
+            upper: actual,
+        };
+    }
+
+    let (baseline, deviation) = get_baseline(samples);
+
+    // Bookings are treated as a floor; the baseline only fills the gap
+    // between what has been booked so far and what is typical.
+    let known = actual + booked;
+    let project = |target: f32| known + (target - known).max(0.0);
+
+    MonthlyForecast {
+        month,
+        actual,
+        booked,
+        baseline,
+        projected: project(baseline),
+        lower: project(baseline - deviation),
+        upper: project(baseline + deviation),
+    }
+}
+
+/// Get the net profit for each month of the year, or `None` if the property
+/// does not have a spreadsheet for that year.
+async fn get_monthly_net_profit(
+    property: &Property,
+    year: i32,
+    database: &mongodb::Database,
+    sheets_client: &sheets::Client,
+) -> Result<Option<Vec<f32>>, ReservationError> {
+    let months =
+        futures::future::join_all((1..=12).map(|month| {
+            get_reservations_by_month(property, year, month, database, sheets_client)
+        }))
+        .await;
+
+    let mut profits: Vec<f32> = Vec::new();
+
+    for result in months {
+        match result {
+            Ok(reservations) => profits.push(reservations.iter().map(|r| r.net_profit).sum()),
+            Err(ReservationError::SpreadsheetNotFound(..)) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+    }
+
+    Ok(Some(profits))
+}
+
+/// Get the mean and standard deviation of the samples.
+fn get_baseline(samples: &[f32]) -> (f32, f32) {
+    if samples.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let n = samples.len() as f32;
+    let mean = samples.iter().sum::<f32>() / n;
+
+    if samples.len() < 2 {
+        return (mean, mean.abs() * FORECAST_FALLBACK_DEVIATION);
+    }
+
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / (n - 1.0);
+    (mean, variance.sqrt())
+}
+
+/// Check every month of a property's spreadsheet for data quality issues.
+pub async fn get_validation_report_by_year(
+    property: &Property,
+    year: i32,
+    database: &mongodb::Database,
+    sheets_client: &sheets::Client,
+) -> Result<ValidationReport, ReservationError> {
+    let mut reservations: Vec<Reservation> = Vec::new();
+
+    for month in 1..=12 {
+        let values =
+            get_reservations_by_month(property, year, month, database, sheets_client).await?;
+        reservations.extend(values);
+    }
+
+    Ok(ValidationReport {
+        property_id: property.id.to_string(),
+        year,
+        issues: validate_reservations(&reservations),
+    })
+}
+
+/// Check which months have a tab in a property's spreadsheet for a year.
+///
+/// Tabs are named by hand, so they are matched to months loosely (e.g.,
+/// `Sept`, `sep 2025` and `Septmber` all match September).
+pub async fn get_sheet_report_by_year(
+    property: &Property,
+    year: i32,
+    database: &mongodb::Database,
+    sheets_client: &sheets::Client,
+) -> Result<SheetReport, ReservationError> {
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+
+    let spreadsheet: SpreadsheetDocument = database
+        .collection("spreadsheet")
+        .find_one(doc! {"property_id": property_id, "year": year})
+        .await
+        .map_err(|err| ReservationError::RequestFailure(err.to_string()))?
+        .ok_or_else(|| ReservationError::SpreadsheetNotFound(year, property.id.to_string()))?;
+
+    let metadata = sheets::get_spreadsheet(sheets_client, &spreadsheet.id)
+        .await
+        .map_err(ReservationError::Metadata)?;
+    let titles: Vec<&str> = metadata.sheet_titles().collect();
+    let matches = match_month_sheets(&titles);
+
+    let mut months = Vec::new();
+    let mut missing = Vec::new();
+    for (month, index) in (1..=12).zip(matches) {
+        if index.is_none() {
+            // Every number in the range is a valid month.
+            missing.push(Month::try_from(month).unwrap().to_string());
+        }
+
+        months.push(MonthSheet {
+            month,
+            sheet: index.map(|index| titles[index].to_string()),
+        });
+    }
+
+    let unmatched = titles
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !matches.contains(&Some(*index)))
+        .map(|(_, title)| title.to_string())
+        .collect();
+
+    Ok(SheetReport {
+        property_id: property.id.to_string(),
+        year,
+        months,
+        missing,
+        unmatched,
+    })
+}
+
+/// Find the tab for each month (starting with January), given the titles of
+/// a spreadsheet's tabs; returns the index of each month's title.
+///
+/// A title matches a month if one of its words is the name of the month, an
+/// abbreviation of at least three letters, or the name with a typo or two.
+/// The closest title is used if several match.
+fn match_month_sheets(titles: &[&str]) -> [Option<usize>; 12] {
+    let mut matches = [None; 12];
+
+    for (month, matched) in (1..=12).zip(matches.iter_mut()) {
+        // Every number in the range is a valid month.
+        let name = Month::try_from(month).unwrap().to_string().to_lowercase();
+
+        *matched = titles
+            .iter()
+            .enumerate()
+            .filter_map(|(index, title)| {
+                title
+                    .split(|c: char| !c.is_alphabetic())
+                    .filter_map(|word| get_month_distance(&word.to_lowercase(), &name))
+                    .min()
+                    .map(|distance| (distance, index))
+            })
+            .min()
+            .map(|(_, index)| index);
+    }
+
+    matches
+}
+
+/// Get how far a word is from the name of a month, if close enough to match.
+fn get_month_distance(word: &str, name: &str) -> Option<usize> {
+    if word == name {
+        Some(0)
+    } else if word.len() >= 3 && name.starts_with(word) {
+        Some(1)
+    } else if word.len() >= 5 {
+        // Short words are too close to other months (e.g., `june` and `july`).
+        let distance = get_edit_distance(word, name);
+        (distance <= 2).then_some(distance + 1)
+    } else {
+        None
+    }
+}
+
+/// Get the number of characters that have to be inserted, removed or
+/// replaced to turn one word into the other (i.e., Levenshtein distance).
+fn get_edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let cost = usize::from(a != *b);
+            current.push(
+                (previous[j] + cost)
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Compare the management fees in a month's spreadsheet with the fees
+/// calculated from the property's fee agreements.
+///
+/// Each reservation is charged according to the agreement in effect on its
+/// check-in date. A flat monthly fee is charged according to the agreement in
+/// effect on the first day of the month.
+pub async fn get_fee_report_by_month(
+    property: &Property,
+    year: i32,
+    month: u8,
+    database: &mongodb::Database,
+    sheets_client: &sheets::Client,
+) -> Result<FeeReport, ReservationError> {
+    let first_day = chrono::NaiveDate::from_ymd_opt(year, month as u32, 1)
+        .ok_or(ReservationError::InvalidMonth)?;
+    let reservations =
+        get_reservations_by_month(property, year, month, database, sheets_client).await?;
+
+    let agreement = get_fee_agreement(&property.fee_agreements, first_day).cloned();
+
+    let fees: Vec<ReservationFee> = reservations
+        .iter()
+        .map(|reservation| {
+            let expected = get_fee_agreement(&property.fee_agreements, reservation.check_in.date())
+                .and_then(|agreement| get_reservation_fee(&agreement.rule, reservation));
+
+            ReservationFee {
+                sheet: reservation.sheet.to_string(),
+                row: reservation.row,
+                check_in: reservation.check_in,
+                revenue: reservation.revenue,
+                actual: reservation.management_fee,
+                expected,
+                mismatch: expected
+                    .is_some_and(|expected| is_mismatch(reservation.management_fee, expected)),
+            }
+        })
+        .collect();
+
+    let actual: f32 = fees.iter().map(|fee| fee.actual).sum();
+    let expected = match &agreement {
+        Some(FeeAgreement {
+            rule: FeeRule::FlatMonthly { amount },
+            ..
+        }) => Some(*amount),
+        Some(_) => Some(fees.iter().filter_map(|fee| fee.expected).sum()),
+        None => None,
+    };
+
+    Ok(FeeReport {
+        year,
+        month,
+        agreement,
+        mismatch: expected.is_some_and(|expected| is_mismatch(actual, expected))
+            || fees.iter().any(|fee| fee.mismatch),
+        reservations: fees,
+        actual,
+        expected,
+    })
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct PayoutDocument {
+    #[serde(rename = "_id")]
+    id: ObjectId,
+    property_id: ObjectId,
+    year: i32,
+    month: u8,
+    amount: f32,
+    paid_on: chrono::NaiveDate,
+    reference: Option<String>,
+    note: Option<String>,
+    recorded_at: chrono::DateTime<chrono::Utc>,
+    voided_at: Option<chrono::DateTime<chrono::Utc>>,
+    void_reason: Option<String>,
+}
+
+impl From<PayoutDocument> for Payout {
+    fn from(document: PayoutDocument) -> Self {
+        Self {
+            id: document.id.to_string(),
+            property_id: document.property_id.to_string(),
+            year: document.year,
+            month: document.month,
+            amount: document.amount,
+            paid_on: document.paid_on,
+            reference: document.reference,
+            note: document.note,
+            recorded_at: document.recorded_at,
+            voided_at: document.voided_at,
+            void_reason: document.void_reason,
+        }
+    }
+}
+
+/// Record a payment made to the owner of a property.
+pub async fn record_payout(
+    property: &Property,
+    payout: NewPayout,
+    database: &mongodb::Database,
+) -> Result<Payout, PayoutError> {
+    if !(1..=12).contains(&payout.month) {
+        return Err(PayoutError::InvalidMonth);
+    }
+
+    if !payout.amount.is_finite() || payout.amount <= 0.0 {
+        return Err(PayoutError::InvalidAmount);
+    }
+
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let document = PayoutDocument {
+        id: ObjectId::new(),
+        property_id,
+        year: payout.year,
+        month: payout.month,
+        amount: payout.amount,
+        paid_on: payout.paid_on,
+        reference: payout.reference,
+        note: payout.note,
+        recorded_at: chrono::Utc::now(),
+        voided_at: None,
+        void_reason: None,
+    };
+
+    database
+        .collection::<PayoutDocument>("payout")
+        .insert_one(&document)
+        .await
+        .map_err(|err| PayoutError::RequestFailure(err.to_string()))?;
+
+    audit::record(
+        AuditAction::Create,
+        "payout",
+        Some(&document.id.to_string()),
+        Some(&property.id),
+        database,
+    )
+    .await;
+
+    Ok(document.into())
+}
+
+/// Get every payout (including voided payouts) for a property's statements
+/// in the specified year.
+pub async fn get_payouts_by_year(
+    property: &Property,
+    year: i32,
+    database: &mongodb::Database,
+) -> Result<Vec<Payout>, PayoutError> {
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let cursor = database
+        .collection::<PayoutDocument>("payout")
+        .find(doc! {"property_id": property_id, "year": year})
+        .sort(doc! {"month": 1, "paid_on": 1})
+        .await
+        .map_err(|err| PayoutError::RequestFailure(err.to_string()))?;
+
+    let documents = cursor
+        .try_collect::<Vec<PayoutDocument>>()
+        .await
+        .map_err(|err| PayoutError::RequestFailure(err.to_string()))?;
+
+    Ok(documents.into_iter().map(Payout::from).collect())
+}
+
+/// Void a payout that was recorded by mistake.
+///
+/// Payouts are never deleted, so the ledger keeps a record of every change.
+pub async fn void_payout(
+    property: &Property,
+    id: &str,
+    void: VoidPayout,
+    database: &mongodb::Database,
+) -> Result<Payout, PayoutError> {
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let payout_id = ObjectId::from_str(id).map_err(|_| PayoutError::BadId(id.to_string()))?;
+    let collection = database.collection::<PayoutDocument>("payout");
+
+    let voided_at = mongodb::bson::to_bson(&chrono::Utc::now())
+        .map_err(|err| PayoutError::RequestFailure(err.to_string()))?;
+
+    // Only a payout that has not been voided yet is updated, so two requests
+    // to void the same payout cannot both succeed.
+    let document = collection
+        .find_one_and_update(
+            doc! {"_id": payout_id, "property_id": property_id, "voided_at": null},
+            doc! {"$set": {"voided_at": voided_at, "void_reason": void.reason}},
+        )
+        .return_document(mongodb::options::ReturnDocument::After)
+        .await
+        .map_err(|err| PayoutError::RequestFailure(err.to_string()))?;
+
+    let Some(document) = document else {
+        let exists = collection
+            .find_one(doc! {"_id": payout_id, "property_id": property_id})
+            .await
+            .map_err(|err| PayoutError::RequestFailure(err.to_string()))?
+            .is_some();
+
+        return Err(if exists {
+            PayoutError::AlreadyVoided(id.to_string())
+        } else {
+            PayoutError::NotFound(id.to_string())
+        });
+    };
+
+    audit::record(
+        AuditAction::Update,
+        "payout",
+        Some(id),
+        Some(&property.id),
+        database,
+    )
+    .await;
+
+    Ok(document.into())
+}
+
+/// Get the year of the first payout recorded for a property, which is the
+/// year its ledger starts.
+async fn get_first_payout_year(
+    property_id: ObjectId,
+    database: &mongodb::Database,
+) -> Result<Option<i32>, PayoutError> {
+    let document = database
+        .collection::<PayoutDocument>("payout")
+        .find_one(doc! {"property_id": property_id})
+        .sort(doc! {"year": 1})
+        .await
+        .map_err(|err| PayoutError::RequestFailure(err.to_string()))?;
+
+    Ok(document.map(|document| document.year))
+}
+
+/// Compare what the owner of a property is owed with what was paid to them.
+///
+/// The amount owed for each month is the net profit from that month's
+/// reservations minus that month's expenses. The ledger starts at zero in the
+/// year of the first payout recorded for the property, and each year opens
+/// with the balance the previous year closed with.
+pub async fn get_reconciliation_by_year(
+    property: &Property,
+    year: i32,
+    database: &mongodb::Database,
+    sheets_client: &sheets::Client,
+) -> Result<Reconciliation, PayoutError> {
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let first_year = get_first_payout_year(property_id, database)
+        .await?
+        .map_or(year, |first_year| first_year.min(year));
+
+    let mut opening_balance = 0.0;
+    for previous_year in first_year..year {
+        // Payouts may have been recorded for years the property's sheets were
+        // never set up for, which only count what was paid.
+        let owed =
+            get_owed_by_month(property, previous_year, true, database, sheets_client).await?;
+        let paid = get_paid_by_month(property, previous_year, database).await?;
+        opening_balance = reconcile(opening_balance, &owed, &paid)
+            .last()
+            .map_or(opening_balance, |p| p.balance);
+    }
+
+    let owed = get_owed_by_month(property, year, false, database, sheets_client).await?;
+    let paid = get_paid_by_month(property, year, database).await?;
+    let periods = reconcile(opening_balance, &owed, &paid);
+
+    Ok(Reconciliation {
+        property_id: property.id.to_string(),
+        year,
+        owed: periods.iter().map(|p| p.owed).sum(),
+        paid: periods.iter().map(|p| p.paid).sum(),
+        opening_balance,
+        balance: periods.last().map_or(opening_balance, |p| p.balance),
+        periods,
+    })
+}
+
+/// Get what the owner of a property was owed for each month of a year
+/// (January first).
+///
+/// If `allow_missing` is set, a year without a spreadsheet or expense sheet
+/// is treated as having no reservations or expenses instead of failing.
+async fn get_owed_by_month(
+    property: &Property,
+    year: i32,
+    allow_missing: bool,
+    database: &mongodb::Database,
+    sheets_client: &sheets::Client,
+) -> Result<[f32; 12], PayoutError> {
+    // Every month is read at the same time as the expenses.
+    let (months, expenses) = futures::future::join(
+        futures::future::join_all((1..=12u8).map(|month| {
+            get_reservations_by_month(property, year, month, database, sheets_client)
+        })),
+        get_expenses_by_year(property, year, sheets_client, database),
+    )
+    .await;
+
+    let expenses = match expenses {
+        Err(ExpenseError::SheetNotFound(..)) if allow_missing => Vec::new(),
+        expenses => expenses?,
+    };
+
+    let mut owed = [0.0; 12];
+    for ((month, reservations), owed) in (1..=12u32).zip(months).zip(&mut owed) {
+        let reservations =
+            match reservations {
+                Err(
+                    ReservationError::SpreadsheetNotFound(..) | ReservationError::SheetMissing(..),
+                ) if allow_missing => Vec::new(),
+                reservations => reservations?,
+            };
+
+        let profit: f32 = reservations.iter().map(|r| r.net_profit).sum();
+        let spent: f32 = expenses
+            .iter()
+            .filter(|e| e.timestamp.month() == month)
+            .map(|e| e.amount)
+            .sum();
+
+        *owed = profit - spent;
+    }
+
+    Ok(owed)
+}
+
+/// Get what was paid to the owner of a property for each month of a year
+/// (January first), leaving out voided payouts.
+async fn get_paid_by_month(
+    property: &Property,
+    year: i32,
+    database: &mongodb::Database,
+) -> Result<[f32; 12], PayoutError> {
+    let payouts = get_payouts_by_year(property, year, database).await?;
+
+    Ok(std::array::from_fn(|index| {
+        payouts
+            .iter()
+            .filter(|p| usize::from(p.month) == index + 1 && p.voided_at.is_none())
+            .map(|p| p.amount)
+            .sum()
+    }))
+}
+
+/// Build the periods of a reconciliation from what was owed and paid each
+/// month (January first), keeping a running balance from `opening_balance`.
+fn reconcile(
+    opening_balance: f32,
+    owed: &[f32; 12],
+    paid: &[f32; 12],
+) -> Vec<ReconciliationPeriod> {
+    let mut balance = opening_balance;
+
+    (1..=12u8)
+        .zip(owed.iter().zip(paid))
+        .map(|(month, (&owed, &paid))| {
+            balance += owed - paid;
+            ReconciliationPeriod {
+                month,
+                owed,
+                paid,
+                balance,
+            }
+        })
+        .collect()
+}
+
+/// The largest receipt (in bytes) that can be uploaded.
+pub const MAX_RECEIPT_SIZE: usize = 10 * 1024 * 1024;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct ReceiptDocument {
+    #[serde(rename = "_id")]
+    id: ObjectId,
+    property_id: ObjectId,
+    year: i32,
+    row: u32,
+    content_type: String,
+    size: usize,
+    file_name: Option<String>,
+    url: String,
+    uploaded_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<ReceiptDocument> for Receipt {
+    fn from(document: ReceiptDocument) -> Self {
+        Self {
+            id: document.id.to_string(),
+            property_id: document.property_id.to_string(),
+            year: document.year,
+            row: document.row,
+            content_type: document.content_type,
+            size: document.size,
+            file_name: document.file_name,
+            url: document.url,
+            uploaded_at: document.uploaded_at,
+        }
+    }
+}
+
+/// Get the content type of a receipt from the first few bytes of the file.
+///
+/// The content type sent by the client is not trusted, since it is only a
+/// guess based on the file's extension.
+fn get_receipt_content_type(contents: &[u8]) -> Option<&'static str> {
+    match contents {
+        [0xFF, 0xD8, 0xFF, ..] => Some("image/jpeg"),
+        [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n', ..] => Some("image/png"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        [b'%', b'P', b'D', b'F', b'-', ..] => Some("application/pdf"),
+        _ => None,
+    }
+}
+
+/// Store a receipt for an expense, and link to it from the expense sheet.
+///
+/// `base_url` is prepended to the receipt's URL before it is written into the
+/// expense's receipt column, so the link can be opened from the spreadsheet.
+#[allow(clippy::too_many_arguments)]
+pub async fn upload_receipt(
+    property: &Property,
+    year: i32,
+    row: u32,
+    contents: Vec<u8>,
+    file_name: Option<String>,
+    base_url: &str,
+    storage: &dyn Storage,
+    sheets_client: &sheets::Client,
+    database: &mongodb::Database,
+) -> Result<Receipt, ReceiptError> {
+    if contents.is_empty() {
+        return Err(ReceiptError::MissingFile);
+    }
+
+    if contents.len() > MAX_RECEIPT_SIZE {
+        return Err(ReceiptError::TooLarge(MAX_RECEIPT_SIZE));
+    }
+
+    let content_type = get_receipt_content_type(&contents).ok_or(ReceiptError::UnsupportedType)?;
+
+    // Make sure the row belongs to the property; otherwise, anyone could
+    // overwrite the receipt column of another property's expenses.
+    let expenses = get_expenses_by_year(property, year, sheets_client, database)
+        .await
+        .map_err(|err| ReceiptError::RequestFailure(err.to_string()))?;
+
+    if !expenses.iter().any(|expense| expense.row == row) {
+        return Err(ReceiptError::ExpenseNotFound(year, row));
+    }
+
+    let expense_sheet_id = get_expense_sheet_id_by_year(year, database)
+        .await
+        .map_err(|_| {
+            ReceiptError::RequestFailure(format!("failed to get id for {year}'s expense sheet"))
+        })?;
+
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let receipt_id = ObjectId::new();
+    let size = contents.len();
+
+    storage
+        .put(&receipt_id.to_hex(), contents)
+        .await
+        .map_err(|err| ReceiptError::RequestFailure(err.to_string()))?;
+
+    let document = ReceiptDocument {
+        id: receipt_id,
+        property_id,
+        year,
+        row,
+        content_type: content_type.to_string(),
+        size,
+        file_name,
+        url: format!(
+            "/api/users/{}/properties/{}/receipts/{}",
+            property.user_id, property.id, receipt_id
+        ),
+        uploaded_at: chrono::Utc::now(),
+    };
+
+    let collection = database.collection::<ReceiptDocument>("receipt");
+
+    if let Err(err) = collection.insert_one(&document).await {
+        discard_receipt_file(&receipt_id, storage).await;
+        return Err(ReceiptError::RequestFailure(err.to_string()));
+    }
+
+    // The sheet is written last, since a link to a receipt that was not
+    // stored would be broken; if it cannot be written, the receipt is
+    // removed instead of being left without a link.
+    let result = sheets::update_values(
+        sheets_client,
+        &expense_sheet_id,
+        &A1Range::new("Expenses")
+            .columns(6, 6)
+            .rows(row, row)
+            .to_string(),
+        vec![vec![format!("{base_url}{}", document.url)]],
+    )
+    .await;
+
+    if let Err(err) = result {
+        if let Err(err) = collection.delete_one(doc! {"_id": receipt_id}).await {
+            tracing::error!("failed to delete unlinked receipt {receipt_id}: {err}");
+        }
+        discard_receipt_file(&receipt_id, storage).await;
+        return Err(ReceiptError::Sheets(err));
+    }
+
+    audit::record(
+        AuditAction::Create,
+        "receipt",
+        Some(&document.id.to_string()),
+        Some(&property.id),
+        database,
+    )
+    .await;
+
+    Ok(document.into())
+}
+
+/// Delete the file of a receipt that could not be saved.
+async fn discard_receipt_file(receipt_id: &ObjectId, storage: &dyn Storage) {
+    if let Err(err) = storage.delete(&receipt_id.to_hex()).await {
+        tracing::error!("failed to delete file of unsaved receipt {receipt_id}: {err}");
+    }
+}
+
+/// Get a receipt, and the contents of the file, via receipt ID.
+pub async fn get_receipt_by_id(
+    id: &str,
+    property: &Property,
+    storage: &dyn Storage,
+    database: &mongodb::Database,
+) -> Result<(Receipt, Vec<u8>), ReceiptError> {
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let receipt_id = ObjectId::from_str(id).map_err(|_| ReceiptError::BadId(id.to_string()))?;
+
+    let document = database
+        .collection::<ReceiptDocument>("receipt")
+        .find_one(doc! {"_id": receipt_id, "property_id": property_id})
+        .await
+        .map_err(|err| ReceiptError::RequestFailure(err.to_string()))?
+        .ok_or_else(|| ReceiptError::NotFound(id.to_string()))?;
+
+    let contents = storage
+        .get(&receipt_id.to_hex())
+        .await
+        .map_err(|err| match err {
+            StorageError::NotFound(..) => ReceiptError::NotFound(id.to_string()),
+            _ => ReceiptError::RequestFailure(err.to_string()),
+        })?;
+
+    Ok((document.into(), contents))
+}
+
+/// Suggest values for an expense by recognizing the text on its receipt.
+///
+/// Only images are supported; PDF receipts are rejected as an unsupported
+/// type, since Tesseract cannot read them directly.
+pub async fn get_receipt_suggestion(
+    id: &str,
+    property: &Property,
+    storage: &dyn Storage,
+    database: &mongodb::Database,
+) -> Result<ExpenseSuggestion, ReceiptError> {
+    let (receipt, contents) = get_receipt_by_id(id, property, storage, database).await?;
+
+    if !receipt.content_type.starts_with("image/") {
+        return Err(ReceiptError::UnsupportedType);
+    }
+
+    let lines = ocr::recognize(&contents).await.map_err(|err| match err {
+        OcrError::Unavailable => ReceiptError::OcrUnavailable,
+        OcrError::TimedOut => ReceiptError::OcrTimedOut,
+        _ => ReceiptError::RequestFailure(err.to_string()),
+    })?;
+
+    Ok(suggest_expense(&lines))
+}
+
+/// The thresholds used when a budget is created without any.
+const DEFAULT_BUDGET_THRESHOLDS: &[f32] = &[0.8, 1.0];
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct BudgetDocument {
+    #[serde(rename = "_id")]
+    id: ObjectId,
+    property_id: ObjectId,
+    category: Option<String>,
+    period: BudgetPeriod,
+    amount: f32,
+    thresholds: Vec<f32>,
+}
+
+impl From<BudgetDocument> for Budget {
+    fn from(document: BudgetDocument) -> Self {
+        Self {
+            id: document.id.to_string(),
+            property_id: document.property_id.to_string(),
+            category: document.category,
+            period: document.period,
+            amount: document.amount,
+            thresholds: document.thresholds,
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct BudgetAlertDocument {
+    budget_id: ObjectId,
+    property_id: ObjectId,
+    category: Option<String>,
+    period: String,
+    threshold: f32,
+    spent: f32,
+    amount: f32,
+    triggered_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<BudgetAlertDocument> for BudgetAlert {
+    fn from(document: BudgetAlertDocument) -> Self {
+        Self {
+            budget_id: document.budget_id.to_string(),
+            property_id: document.property_id.to_string(),
+            category: document.category,
+            period: document.period,
+            threshold: document.threshold,
+            spent: document.spent,
+            amount: document.amount,
+            triggered_at: document.triggered_at,
+        }
+    }
+}
+
+/// Create a new budget for a property.
+pub async fn create_budget(
+    property: &Property,
+    budget: NewBudget,
+    database: &mongodb::Database,
+) -> Result<Budget, BudgetError> {
+    let thresholds = budget
+        .thresholds
+        .unwrap_or_else(|| DEFAULT_BUDGET_THRESHOLDS.to_vec());
+
+    if budget.amount <= 0.0 || thresholds.iter().any(|threshold| *threshold <= 0.0) {
+        return Err(BudgetError::InvalidAmount);
+    }
+
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let document = BudgetDocument {
+        id: ObjectId::new(),
+        property_id,
+        category: budget.category.map(|category| category.trim().to_string()),
+        period: budget.period,
+        amount: budget.amount,
+        thresholds,
+    };
+
+    database
+        .collection::<BudgetDocument>("budget")
+        .insert_one(&document)
+        .await
+        .map_err(|err| BudgetError::RequestFailure(err.to_string()))?;
+
+    audit::record(
+        AuditAction::Create,
+        "budget",
+        Some(&document.id.to_string()),
+        Some(&property.id),
+        database,
+    )
+    .await;
+
+    Ok(document.into())
+}
+
+/// Get every budget for a property.
+pub async fn get_budgets_by_property(
+    property: &Property,
+    database: &mongodb::Database,
+) -> Result<Vec<Budget>, BudgetError> {
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let cursor = database
+        .collection::<BudgetDocument>("budget")
+        .find(doc! {"property_id": property_id})
+        .await
+        .map_err(|err| BudgetError::RequestFailure(err.to_string()))?;
+
+    let documents = cursor
+        .try_collect::<Vec<BudgetDocument>>()
+        .await
+        .map_err(|err| BudgetError::RequestFailure(err.to_string()))?;
+
+    Ok(documents.into_iter().map(Budget::from).collect())
+}
+
+/// Delete one of a property's budgets.
+pub async fn delete_budget(
+    property: &Property,
+    id: &str,
+    database: &mongodb::Database,
+) -> Result<(), BudgetError> {
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let budget_id = ObjectId::from_str(id).map_err(|_| BudgetError::BadId(id.to_string()))?;
+
+    let result = database
+        .collection::<BudgetDocument>("budget")
+        .delete_one(doc! {"_id": budget_id, "property_id": property_id})
+        .await
+        .map_err(|err| BudgetError::RequestFailure(err.to_string()))?;
+
+    if result.deleted_count == 0 {
+        return Err(BudgetError::NotFound(id.to_string()));
+    }
+
+    audit::record(
+        AuditAction::Delete,
+        "budget",
+        Some(id),
+        Some(&property.id),
+        database,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Compare a property's expenses with its budgets.
+///
+/// The report only includes alerts that were already triggered for its
+/// periods; new alerts are triggered by [`check_budgets_by_month`].
+pub async fn get_budget_report_by_month(
+    property: &Property,
+    year: i32,
+    month: u8,
+    sheets_client: &sheets::Client,
+    database: &mongodb::Database,
+) -> Result<BudgetReport, BudgetError> {
+    if !(1..=12).contains(&month) {
+        return Err(BudgetError::InvalidMonth);
+    }
+
+    let budgets = get_budgets_by_property(property, database).await?;
+    let expenses = get_expenses_by_year(property, year, sheets_client, database)
+        .await
+        .map_err(|err| BudgetError::RequestFailure(err.to_string()))?;
+
+    let statuses: Vec<BudgetStatus> = budgets
+        .into_iter()
+        .map(|budget| {
+            let months = match budget.period {
+                BudgetPeriod::Monthly => month..=month,
+                BudgetPeriod::Annual => 1..=month,
+            };
+
+            let spent: f32 = expenses
+                .iter()
+                .filter(|expense| months.contains(&(expense.timestamp.month() as u8)))
+                .filter(|expense| {
+                    budget
+                        .category
+                        .as_ref()
+                        .is_none_or(|category| category.eq_ignore_ascii_case(&expense.category))
+                })
+                .map(|expense| expense.amount)
+                .sum();
+
+            get_budget_status(budget, spent)
+        })
+        .collect();
+
+    // Budget IDs are generated by the database, so they are valid.
+    let budget_ids: Vec<ObjectId> = statuses
+        .iter()
+        .map(|status| ObjectId::from_str(&status.budget.id).unwrap())
+        .collect();
+    let periods = [format!("{year}-{month:02}"), format!("{year}")];
+
+    let cursor = database
+        .collection::<BudgetAlertDocument>("budget_alert")
+        .find(doc! {"budget_id": {"$in": budget_ids}, "period": {"$in": periods.to_vec()}})
+        .sort(doc! {"triggered_at": 1})
+        .await
+        .map_err(|err| BudgetError::RequestFailure(err.to_string()))?;
+
+    let alerts = cursor
+        .try_collect::<Vec<BudgetAlertDocument>>()
+        .await
+        .map_err(|err| BudgetError::RequestFailure(err.to_string()))?
+        .into_iter()
+        .map(BudgetAlert::from)
+        .collect();
+
+    Ok(BudgetReport {
+        year,
+        month,
+        budgets: statuses,
+        alerts,
+    })
+}
+
+/// Compare spending with a budget.
+fn get_budget_status(budget: Budget, spent: f32) -> BudgetStatus {
+    let utilization = spent / budget.amount;
+    let thresholds_reached: Vec<f32> = budget
+        .thresholds
+        .iter()
+        .copied()
+        .filter(|threshold| utilization >= *threshold)
+        .collect();
+
+    BudgetStatus {
+        remaining: budget.amount - spent,
+        over_budget: spent > budget.amount,
+        budget,
+        spent,
+        utilization,
+        thresholds_reached,
+    }
+}
+
+/// Trigger an alert for each threshold of a property's budgets that spending
+/// has reached for the first time in a month (or the year up to the month).
+///
+/// Returns the alerts that were triggered, so the owner can be notified.
+pub async fn check_budgets_by_month(
+    property: &Property,
+    year: i32,
+    month: u8,
+    sheets_client: &sheets::Client,
+    database: &mongodb::Database,
+) -> Result<Vec<BudgetAlert>, BudgetError> {
+    let report = get_budget_report_by_month(property, year, month, sheets_client, database).await?;
+    let mut alerts: Vec<BudgetAlert> = Vec::new();
+
+    for status in &report.budgets {
+        let period = match status.budget.period {
+            BudgetPeriod::Monthly => format!("{year}-{month:02}"),
+            BudgetPeriod::Annual => format!("{year}"),
+        };
+
+        for threshold in &status.thresholds_reached {
+            if let Some(alert) =
+                trigger_budget_alert(&status.budget, &period, *threshold, status.spent, database)
+                    .await?
+            {
+                alerts.push(alert);
+            }
+        }
+    }
+
+    Ok(alerts)
+}
+
+/// Record that spending reached one of a budget's thresholds.
+///
+/// Returns the alert if this is the first time the threshold was reached for
+/// the period, or `None` if it was already triggered.
+async fn trigger_budget_alert(
+    budget: &Budget,
+    period: &str,
+    threshold: f32,
+    spent: f32,
+    database: &mongodb::Database,
+) -> Result<Option<BudgetAlert>, BudgetError> {
+    // Budget and property IDs are generated by the database, so they are valid.
+    let budget_id = ObjectId::from_str(&budget.id).unwrap();
+    let property_id = ObjectId::from_str(&budget.property_id).unwrap();
+    let triggered_at = mongodb::bson::to_bson(&chrono::Utc::now())
+        .map_err(|err| BudgetError::RequestFailure(err.to_string()))?;
+
+    let filter = doc! {"budget_id": budget_id, "period": period, "threshold": threshold};
+    let collection = database.collection::<BudgetAlertDocument>("budget_alert");
+
+    // Upserting makes sure an alert is only ever triggered once. If more than
+    // one report is generated at the same time, both may try to insert the
+    // alert; the unique index (see `create_indexes`) rejects all but one.
+    let result = collection
+        .update_one(
+            filter.clone(),
+            doc! {"$setOnInsert": {
+                "property_id": property_id,
+                "category": budget.category.clone(),
+                "spent": spent,
+                "amount": budget.amount,
+                "triggered_at": triggered_at,
+            }},
+        )
+        .upsert(true)
+        .await;
+
+    let result = match result {
+        Ok(result) => result,
+        Err(err) if is_duplicate_key_error(&err) => return Ok(None),
+        Err(err) => return Err(BudgetError::RequestFailure(err.to_string())),
+    };
+
+    if result.upserted_id.is_none() {
+        return Ok(None);
+    }
+
+    let document = collection
+        .find_one(filter)
+        .await
+        .map_err(|err| BudgetError::RequestFailure(err.to_string()))?
+        .ok_or_else(|| BudgetError::RequestFailure("failed to get budget alert".to_string()))?;
+
+    Ok(Some(document.into()))
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct NotificationPreferencesDocument {
+    /// The ID of the user the preferences belong to.
+    #[serde(rename = "_id")]
+    id: String,
+    #[serde(flatten)]
+    preferences: NotificationPreferences,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct NotificationDeliveryDocument {
+    #[serde(rename = "_id")]
+    id: ObjectId,
+    user_id: String,
+    kind: NotificationKind,
+    to: Option<String>,
+    subject: String,
+    status: DeliveryStatus,
+    error: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<NotificationDeliveryDocument> for NotificationDelivery {
+    fn from(document: NotificationDeliveryDocument) -> Self {
+        Self {
+            id: document.id.to_string(),
+            user_id: document.user_id,
+            kind: document.kind,
+            to: document.to,
+            subject: document.subject,
+            status: document.status,
+            error: document.error,
+            created_at: document.created_at,
+        }
+    }
+}
+
+/// Get a user's notification preferences, or the defaults if they have not
+/// changed them.
+pub async fn get_notification_preferences(
+    user: &User,
+    database: &mongodb::Database,
+) -> Result<NotificationPreferences, NotificationError> {
+    let document = database
+        .collection::<NotificationPreferencesDocument>("notification_preferences")
+        .find_one(doc! {"_id": user.id.to_string()})
+        .await
+        .map_err(|err| NotificationError::RequestFailure(err.to_string()))?;
+
+    Ok(document
+        .map(|document| document.preferences)
+        .unwrap_or_default())
+}
+
+/// Replace a user's notification preferences.
+pub async fn set_notification_preferences(
+    user: &User,
+    preferences: NotificationPreferences,
+    database: &mongodb::Database,
+) -> Result<NotificationPreferences, NotificationError> {
+    let document = NotificationPreferencesDocument {
+        id: user.id.to_string(),
+        preferences,
+    };
+
+    database
+        .collection::<NotificationPreferencesDocument>("notification_preferences")
+        .replace_one(doc! {"_id": user.id.to_string()}, &document)
+        .upsert(true)
+        .await
+        .map_err(|err| NotificationError::RequestFailure(err.to_string()))?;
+
+    audit::record(
+        AuditAction::Update,
+        "notification_preferences",
+        Some(&user.id),
+        None,
+        database,
+    )
+    .await;
+
+    Ok(document.preferences)
+}
+
+/// Send a notification to a user, if they chose to receive it.
+///
+/// Every attempt is recorded in the delivery log, including notifications the
+/// user chose not to receive and notifications that failed to be delivered.
+pub async fn notify(
+    user: &User,
+    notification: &Notification,
+    notifier: &dyn Notifier,
+    database: &mongodb::Database,
+) -> Result<NotificationDelivery, NotificationError> {
+    let preferences = get_notification_preferences(user, database).await?;
+    let kind = notification.kind();
+
+    let name = user.first_name.as_deref().unwrap_or("there");
+    let (subject, body) = notification.render(name);
+    let to = preferences.email.clone().or_else(|| {
+        user.email_addresses
+            .first()
+            .map(|email| email.email_address.to_string())
+    });
+
+    let (status, error) = match &to {
+        _ if !preferences.is_enabled(kind) => (DeliveryStatus::Skipped, None),
+        None => (
+            DeliveryStatus::Failed,
+            Some("user does not have an email address".to_string()),
+        ),
+        Some(to) => {
+            let message = Message {
+                kind,
+                to: to.to_string(),
+                subject: subject.to_string(),
+                body,
+            };
+
+            match notifier.send(&message).await {
+                Ok(()) if notifier.delivers() => (DeliveryStatus::Sent, None),
+                Ok(()) => (DeliveryStatus::Skipped, None),
+                Err(err) => (DeliveryStatus::Failed, Some(err.to_string())),
+            }
+        }
+    };
+
+    let document = NotificationDeliveryDocument {
+        id: ObjectId::new(),
+        user_id: user.id.to_string(),
+        kind,
+        to,
+        subject,
+        status,
+        error,
+        created_at: chrono::Utc::now(),
+    };
+
+    database
+        .collection::<NotificationDeliveryDocument>("notification_delivery")
+        .insert_one(&document)
+        .await
+        .map_err(|err| NotificationError::RequestFailure(err.to_string()))?;
+
+    Ok(document.into())
+}
+
+/// Get the most recent notification deliveries, newest first.
+pub async fn get_notification_deliveries(
+    user_id: Option<&str>,
+    limit: i64,
+    database: &mongodb::Database,
+) -> Result<Vec<NotificationDelivery>, NotificationError> {
+    let filter = match user_id {
+        Some(user_id) => doc! {"user_id": user_id},
+        None => doc! {},
+    };
+
+    let cursor = database
+        .collection::<NotificationDeliveryDocument>("notification_delivery")
+        .find(filter)
+        .sort(doc! {"created_at": -1})
+        .limit(limit)
+        .await
+        .map_err(|err| NotificationError::RequestFailure(err.to_string()))?;
+
+    let documents = cursor
+        .try_collect::<Vec<NotificationDeliveryDocument>>()
+        .await
+        .map_err(|err| NotificationError::RequestFailure(err.to_string()))?;
+
+    Ok(documents
+        .into_iter()
+        .map(NotificationDelivery::from)
+        .collect())
+}
+
+/// Notify the owner of a property about budget alerts that were triggered.
+pub async fn notify_budget_alerts(
+    user: &User,
+    property: &Property,
+    alerts: &[BudgetAlert],
+    notifier: &dyn Notifier,
+    database: &mongodb::Database,
+) -> Result<(), NotificationError> {
+    for alert in alerts {
+        let notification = Notification::BudgetOverrun {
+            property_name: property.name.to_string(),
+            category: alert.category.clone(),
+            period: alert.period.to_string(),
+            spent: alert.spent,
+            amount: alert.amount,
+            threshold: alert.threshold,
+        };
+
+        notify(user, &notification, notifier, database).await?;
+    }
+
+    Ok(())
+}
+
+/// Notify the owner of a property about the new reservations and large
+/// expenses among the changes detected in its spreadsheets.
+///
+/// Expenses are large if they are at least the owner's threshold (see
+/// [`NotificationPreferences::large_expense_threshold`]).
+pub async fn notify_changes(
+    user: &User,
+    property: &Property,
+    events: &[Event],
+    notifier: &dyn Notifier,
+    database: &mongodb::Database,
+) -> Result<(), NotificationError> {
+    let preferences = get_notification_preferences(user, database).await?;
+
+    for event in events {
+        let notification = match &event.change {
+            Change::ReservationAdded { reservation, .. } => Notification::NewReservation {
+                property_name: property.name.to_string(),
+                platform: reservation.platform.to_string(),
+                check_in: reservation.check_in.date(),
+                check_out: reservation.check_out.date(),
+                revenue: reservation.revenue,
+            },
+            Change::ExpenseAdded { expense }
+                if expense.amount >= preferences.large_expense_threshold =>
+            {
+                Notification::LargeExpense {
+                    property_name: property.name.to_string(),
+                    amount: expense.amount,
+                    description: expense.description.to_string(),
+                    merchant: expense.merchant.to_string(),
+                    date: expense.timestamp.date(),
+                }
+            }
+            _ => continue,
+        };
+
+        notify(user, &notification, notifier, database).await?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, serde::Serialize, Deserialize)]
+struct SnapshotDocument<T> {
+    /// See [`SHEET_VALUES_VERSION`].
+    version: i32,
+    property_id: ObjectId,
+    year: i32,
+    /// The name of the sheet (tab) the snapshot was taken of.
+    sheet: String,
+    rows: Vec<SnapshotRow<T>>,
+    taken_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Get the rows of the last snapshot of a sheet, if one was taken.
+async fn get_snapshot<T>(
+    property_id: ObjectId,
+    year: i32,
+    sheet: &str,
+    database: &mongodb::Database,
+) -> Result<Option<Vec<SnapshotRow<T>>>, mongodb::error::Error>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync,
+{
+    let filter = doc! {
+        "property_id": property_id,
+        "year": year,
+        "sheet": sheet,
+        "version": SHEET_VALUES_VERSION,
+    };
+
+    let previous = database
+        .collection::<SnapshotDocument<T>>("snapshot")
+        .find_one(filter)
+        .await?;
+
+    Ok(previous.map(|previous| previous.rows))
+}
+
+/// Replace the snapshot of a sheet with the current rows.
+async fn replace_snapshot<T>(
+    property_id: ObjectId,
+    year: i32,
+    sheet: &str,
+    rows: Vec<SnapshotRow<T>>,
+    database: &mongodb::Database,
+) -> Result<(), mongodb::error::Error>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync,
+{
+    let filter = doc! {"property_id": property_id, "year": year, "sheet": sheet};
+    let document = SnapshotDocument {
+        version: SHEET_VALUES_VERSION,
+        property_id,
+        year,
+        sheet: sheet.to_string(),
+        rows,
+        taken_at: chrono::Utc::now(),
+    };
+
+    database
+        .collection::<SnapshotDocument<T>>("snapshot")
+        .replace_one(filter, &document)
+        .upsert(true)
+        .await?;
+
+    Ok(())
+}
+
+/// Compare a month's reservations with the last time they were checked, and
+/// record the changes as events (see [`record_events`]).
+///
+/// Nothing is reported the first time a month is checked, since there is
+/// nothing to compare it with. The events are recorded before the snapshot
+/// is replaced, so changes are reported again rather than lost if the
+/// snapshot cannot be replaced.
+pub async fn detect_reservation_changes(
+    property: &Property,
+    year: i32,
+    month: u8,
+    database: &mongodb::Database,
+    sheets_client: &sheets::Client,
+) -> Result<Vec<Event>, ReservationError> {
+    // Reading the rows also refreshes the cache, since they were read anyway.
+    let values =
+        get_reservation_values(property, year, month, true, database, sheets_client).await?;
+
+    // The month was already validated when getting the values.
+    let month: Month = month.try_into().unwrap();
+
+    let rows: Vec<SnapshotRow<ReservationValues>> = values
+        .into_iter()
+        .enumerate()
+        .skip(1) // Skip the table headings.
+        .filter(|(_, values)| is_reservation(values))
+        .map(|(index, values)| {
+            // The same guest never checks in twice on the same day through
+            // the same platform, so this identifies the reservation even
+            // after its other details are corrected.
+            let key = format!(
+                "{}|{}",
+                values.0.to_string().trim().to_lowercase(),
+                values.2
+            );
+            SnapshotRow::new((index + 1) as u32, key, values)
+        })
+        .collect();
+
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let sheet = month.to_string();
+    let previous = get_snapshot(property_id, year, &sheet, database)
+        .await
+        .map_err(|err| ReservationError::RequestFailure(err.to_string()))?;
+
+    let events = match previous {
+        Some(previous) => {
+            let changes = get_reservation_changes(&month, &previous, &rows)?;
+            record_events(property, changes, database)
+                .await
+                .map_err(|err| ReservationError::RequestFailure(err.to_string()))?
+        }
+        None => Vec::new(),
+    };
+
+    replace_snapshot(property_id, year, &sheet, rows, database)
+        .await
+        .map_err(|err| ReservationError::RequestFailure(err.to_string()))?;
+
+    Ok(events)
+}
+
+/// Get the changes between two snapshots of a month's reservations.
+fn get_reservation_changes(
+    month: &Month,
+    previous: &[SnapshotRow<ReservationValues>],
+    current: &[SnapshotRow<ReservationValues>],
+) -> Result<Vec<Change>, ReservationError> {
+    let diff = diff_rows(previous, current);
+    let parse =
+        |row: &SnapshotRow<ReservationValues>| parse_reservation(month, row.row, &row.values);
+    let sheet = month.to_string();
+
+    let mut changes: Vec<Change> = Vec::new();
+    for row in diff.added {
+        changes.push(Change::ReservationAdded {
+            sheet: sheet.to_string(),
+            reservation: parse(row)?,
+        });
+    }
+    for (before, after) in diff.changed {
+        changes.push(Change::ReservationChanged {
+            sheet: sheet.to_string(),
+            before: parse(before)?,
+            after: parse(after)?,
+        });
+    }
+    for row in diff.removed {
+        changes.push(Change::ReservationRemoved {
+            sheet: sheet.to_string(),
+            reservation: parse(row)?,
+        });
+    }
+
+    Ok(changes)
+}
+
+/// Where the last check for a property's new expenses stopped.
+#[derive(Debug, serde::Serialize, Deserialize)]
+struct ExpenseCursorDocument {
+    property_id: ObjectId,
+    year: i32,
+    /// The row after the last row of the expense sheet that was read.
+    next_row: u32,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Find the expenses added for a property since the last time they were
+/// checked, and record them as events (see [`record_events`]).
+///
+/// Expenses are submitted through a form, which only ever appends rows, so
+/// only the rows after the ones read by the last check are read; corrections
+/// to existing expenses are not reported. Nothing is reported the first time
+/// a year is checked, since every expense would be new. The events are
+/// recorded before the cursor is moved, so expenses are reported again
+/// rather than lost if the cursor cannot be moved.
+pub async fn detect_expense_changes(
+    property: &Property,
+    year: i32,
+    sheets_client: &sheets::Client,
+    database: &mongodb::Database,
+) -> Result<Vec<Event>, ExpenseError> {
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let filter = doc! {"property_id": property_id, "year": year};
+    let collection = database.collection::<ExpenseCursorDocument>("expense_cursor");
+
+    let cursor = collection
+        .find_one(filter.clone())
+        .await
+        .map_err(|err| ExpenseError::RequestFailure(err.to_string()))?;
+    let from_row = cursor
+        .as_ref()
+        .map_or(FIRST_EXPENSE_ROW, |cursor| cursor.next_row);
+
+    let (rows, next_row) =
+        get_expense_rows(property, year, from_row, sheets_client, database).await?;
+
+    let events = match cursor {
+        Some(_) => {
+            let changes = rows
+                .iter()
+                .map(|(row, values)| {
+                    Ok(Change::ExpenseAdded {
+                        expense: parse_expense(*row, values)?,
+                    })
+                })
+                .collect::<Result<Vec<Change>, ExpenseError>>()?;
+
+            record_events(property, changes, database)
+                .await
+                .map_err(|err| ExpenseError::RequestFailure(err.to_string()))?
+        }
+        None => Vec::new(),
+    };
+
+    let document = ExpenseCursorDocument {
+        property_id,
+        year,
+        next_row,
+        updated_at: chrono::Utc::now(),
+    };
+    collection
+        .replace_one(filter, &document)
+        .upsert(true)
+        .await
+        .map_err(|err| ExpenseError::RequestFailure(err.to_string()))?;
+
+    Ok(events)
+}
+
+#[derive(Debug, serde::Serialize, Deserialize)]
+struct EventDocument {
+    #[serde(rename = "_id")]
+    id: ObjectId,
+    property_id: ObjectId,
+    user_id: String,
+    occurred_at: chrono::DateTime<chrono::Utc>,
+    change: Change,
+}
+
+impl From<EventDocument> for Event {
+    fn from(document: EventDocument) -> Self {
+        Self {
+            id: document.id.to_string(),
+            property_id: document.property_id.to_string(),
+            user_id: document.user_id,
+            occurred_at: document.occurred_at,
+            change: document.change,
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize, Deserialize)]
+struct WebhookDocument {
+    #[serde(rename = "_id")]
+    id: ObjectId,
+    url: String,
+    events: Vec<ChangeKind>,
+    property_id: Option<ObjectId>,
+    secret: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<WebhookDocument> for Webhook {
+    fn from(document: WebhookDocument) -> Self {
+        Self {
+            id: document.id.to_string(),
+            url: document.url,
+            events: document.events,
+            property_id: document.property_id.map(|id| id.to_string()),
+            secret: None,
+            created_at: document.created_at,
+        }
+    }
+}
+
+impl WebhookDocument {
+    fn accepts(&self, property_id: ObjectId, kind: ChangeKind) -> bool {
+        self.property_id.is_none_or(|id| id == property_id)
+            && (self.events.is_empty() || self.events.contains(&kind))
+    }
+}
+
+#[derive(Debug, serde::Serialize, Deserialize)]
+struct WebhookDeliveryDocument {
+    #[serde(rename = "_id")]
+    id: ObjectId,
+    webhook_id: ObjectId,
+    event_id: ObjectId,
+    status: WebhookDeliveryStatus,
+    attempts: u32,
+    last_error: Option<String>,
+    /// Stored as a BSON date (rather than a string), so due deliveries can be
+    /// found by comparing dates.
+    next_attempt_at: Option<mongodb::bson::DateTime>,
+    delivered_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<WebhookDeliveryDocument> for WebhookDelivery {
+    fn from(document: WebhookDeliveryDocument) -> Self {
+        Self {
+            id: document.id.to_string(),
+            webhook_id: document.webhook_id.to_string(),
+            event_id: document.event_id.to_string(),
+            status: document.status,
+            attempts: document.attempts,
+            last_error: document.last_error,
+            next_attempt_at: document
+                .next_attempt_at
+                .and_then(|at| chrono::DateTime::from_timestamp_millis(at.timestamp_millis())),
+            delivered_at: document.delivered_at,
+        }
+    }
+}
+
+/// How long to wait (in minutes) before retrying a failed delivery, after
+/// each failed attempt; deliveries are abandoned after the last one.
+const WEBHOOK_RETRY_DELAYS: [i64; 5] = [1, 5, 30, 120, 720];
+
+/// The maximum number of deliveries attempted at once.
+const WEBHOOK_DELIVERY_BATCH_SIZE: i64 = 100;
+
+/// How long a claimed delivery is reserved for the runner that claimed it;
+/// deliveries left in flight for longer (e.g., because the server stopped
+/// while sending them) can be claimed again.
+const WEBHOOK_DELIVERY_LEASE: chrono::TimeDelta = chrono::TimeDelta::minutes(5);
+
+/// Register an outgoing webhook; the secret used to sign deliveries is only
+/// included in the webhook returned.
+///
+/// If the webhook is limited to a property, the property should already be
+/// known to exist.
+pub async fn create_webhook(
+    webhook: NewWebhook,
+    database: &mongodb::Database,
+) -> Result<Webhook, WebhookError> {
+    let url = reqwest::Url::parse(&webhook.url)
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+        .ok_or_else(|| WebhookError::InvalidUrl(webhook.url.to_string()))?;
+
+    let property_id = webhook
+        .property_id
+        .as_ref()
+        .map(|id| ObjectId::from_str(id).map_err(|_| WebhookError::BadId(id.to_string())))
+        .transpose()?;
+
+    let document = WebhookDocument {
+        id: ObjectId::new(),
+        url: url.to_string(),
+        events: webhook.events,
+        property_id,
+        secret: webhooks::generate_secret(),
+        created_at: chrono::Utc::now(),
+    };
+
+    database
+        .collection::<WebhookDocument>("webhook")
+        .insert_one(&document)
+        .await
+        .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+
+    audit::record(
+        AuditAction::Create,
+        "webhook",
+        Some(&document.id.to_string()),
+        webhook.property_id.as_deref(),
+        database,
+    )
+    .await;
+
+    let secret = document.secret.to_string();
+    let mut webhook = Webhook::from(document);
+    webhook.secret = Some(secret);
+
+    Ok(webhook)
+}
+
+pub async fn get_webhooks(database: &mongodb::Database) -> Result<Vec<Webhook>, WebhookError> {
+    let cursor = database
+        .collection::<WebhookDocument>("webhook")
+        .find(doc! {})
+        .await
+        .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+
+    let documents = cursor
+        .try_collect::<Vec<WebhookDocument>>()
+        .await
+        .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+
+    Ok(documents.into_iter().map(Webhook::from).collect())
+}
+
+/// The reason recorded for deliveries to a webhook that was deleted.
+const WEBHOOK_DELETED: &str = "webhook was deleted";
+
+/// Remove a webhook; pending deliveries to it are abandoned.
+pub async fn delete_webhook(id: &str, database: &mongodb::Database) -> Result<(), WebhookError> {
+    let webhook_id = ObjectId::from_str(id).map_err(|_| WebhookError::BadId(id.to_string()))?;
+
+    let result = database
+        .collection::<WebhookDocument>("webhook")
+        .delete_one(doc! {"_id": webhook_id})
+        .await
+        .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+
+    if result.deleted_count == 0 {
+        return Err(WebhookError::NotFound(id.to_string()));
+    }
+
+    // Deliveries being attempted right now are abandoned once the attempt
+    // finds the webhook missing.
+    database
+        .collection::<WebhookDeliveryDocument>("webhook_delivery")
+        .update_many(
+            doc! {"webhook_id": webhook_id, "status": "pending"},
+            doc! {"$set": {
+                "status": "failed",
+                "last_error": WEBHOOK_DELETED,
+                "next_attempt_at": null,
+            }},
+        )
+        .await
+        .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+
+    audit::record(AuditAction::Delete, "webhook", Some(id), None, database).await;
+
+    Ok(())
+}
+
+/// Get the most recent deliveries to a webhook, newest first.
+pub async fn get_webhook_deliveries(
+    id: &str,
+    limit: i64,
+    database: &mongodb::Database,
+) -> Result<Vec<WebhookDelivery>, WebhookError> {
+    let webhook_id = ObjectId::from_str(id).map_err(|_| WebhookError::BadId(id.to_string()))?;
+
+    let cursor = database
+        .collection::<WebhookDeliveryDocument>("webhook_delivery")
+        .find(doc! {"webhook_id": webhook_id})
+        .sort(doc! {"_id": -1})
+        .limit(limit)
+        .await
+        .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+
+    let documents = cursor
+        .try_collect::<Vec<WebhookDeliveryDocument>>()
+        .await
+        .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+
+    Ok(documents.into_iter().map(WebhookDelivery::from).collect())
+}
+
+/// Record the changes detected for a property as events, and queue their
+/// delivery to every webhook interested in them.
+pub async fn record_events(
+    property: &Property,
+    changes: Vec<Change>,
+    database: &mongodb::Database,
+) -> Result<Vec<Event>, WebhookError> {
+    if changes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Property ID should already be valid if we got to this point.
+    let property_id = ObjectId::from_str(&property.id).unwrap();
+    let webhooks = database
+        .collection::<WebhookDocument>("webhook")
+        .find(doc! {})
+        .await
+        .map_err(|err| WebhookError::RequestFailure(err.to_string()))?
+        .try_collect::<Vec<WebhookDocument>>()
+        .await
+        .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+
+    let mut events: Vec<Event> = Vec::new();
+
+    for change in changes {
+        let kind = change.kind();
+        let document = EventDocument {
+            id: ObjectId::new(),
+            property_id,
+            user_id: property.user_id.to_string(),
+            occurred_at: chrono::Utc::now(),
+            change,
+        };
+
+        database
+            .collection::<EventDocument>("event")
+            .insert_one(&document)
+            .await
+            .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+
+        let deliveries: Vec<WebhookDeliveryDocument> = webhooks
+            .iter()
+            .filter(|webhook| webhook.accepts(property_id, kind))
+            .map(|webhook| WebhookDeliveryDocument {
+                id: ObjectId::new(),
+                webhook_id: webhook.id,
+                event_id: document.id,
+                status: WebhookDeliveryStatus::Pending,
+                attempts: 0,
+                last_error: None,
+                next_attempt_at: Some(mongodb::bson::DateTime::now()),
+                delivered_at: None,
+            })
+            .collect();
+
+        if !deliveries.is_empty() {
+            database
+                .collection::<WebhookDeliveryDocument>("webhook_delivery")
+                .insert_many(&deliveries)
+                .await
+                .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+        }
+
+        events.push(document.into());
+    }
+
+    Ok(events)
+}
+
+/// Attempt every webhook delivery that is due, returning how many were
+/// delivered and how many failed (and will be retried, unless abandoned).
+///
+/// Each delivery is claimed before it is sent, so a delivery is only sent
+/// once even if deliveries are attempted concurrently.
+pub async fn deliver_pending_webhooks(
+    database: &mongodb::Database,
+) -> Result<(usize, usize), WebhookError> {
+    let (mut delivered, mut failed) = (0, 0);
+
+    for _ in 0..WEBHOOK_DELIVERY_BATCH_SIZE {
+        let Some(mut delivery) = claim_webhook_delivery(database).await? else {
+            break;
+        };
+
+        let result = attempt_webhook_delivery(&delivery, database).await?;
+        delivery.attempts += 1;
+
+        match result {
+            DeliveryOutcome::Delivered => {
+                delivered += 1;
+                delivery.status = WebhookDeliveryStatus::Delivered;
+                delivery.last_error = None;
+                delivery.next_attempt_at = None;
+                delivery.delivered_at = Some(chrono::Utc::now());
+            }
+            DeliveryOutcome::Abandoned(reason) => {
+                failed += 1;
+                delivery.status = WebhookDeliveryStatus::Failed;
+                delivery.last_error = Some(reason);
+                delivery.next_attempt_at = None;
+            }
+            DeliveryOutcome::Failed(reason) => {
+                failed += 1;
+                delivery.last_error = Some(reason);
+
+                match WEBHOOK_RETRY_DELAYS.get(delivery.attempts as usize - 1) {
+                    Some(minutes) => {
+                        delivery.status = WebhookDeliveryStatus::Pending;
+                        let at = chrono::Utc::now() + chrono::TimeDelta::minutes(*minutes);
+                        delivery.next_attempt_at =
+                            Some(mongodb::bson::DateTime::from_millis(at.timestamp_millis()));
+                    }
+                    None => {
+                        delivery.status = WebhookDeliveryStatus::Failed;
+                        delivery.next_attempt_at = None;
+                    }
+                }
+            }
+        }
+
+        database
+            .collection::<WebhookDeliveryDocument>("webhook_delivery")
+            .replace_one(doc! {"_id": delivery.id}, &delivery)
+            .await
+            .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+    }
+
+    Ok((delivered, failed))
+}
+
+/// Claim the delivery that has been due the longest by marking it as being
+/// delivered, so no other runner attempts it at the same time.
+async fn claim_webhook_delivery(
+    database: &mongodb::Database,
+) -> Result<Option<WebhookDeliveryDocument>, WebhookError> {
+    let now = chrono::Utc::now();
+    let lease_expires_at =
+        mongodb::bson::DateTime::from_millis((now + WEBHOOK_DELIVERY_LEASE).timestamp_millis());
+
+    database
+        .collection::<WebhookDeliveryDocument>("webhook_delivery")
+        .find_one_and_update(
+            doc! {
+                "status": {"$in": ["pending", "delivering"]},
+                "next_attempt_at": {"$lte": mongodb::bson::DateTime::from_millis(now.timestamp_millis())},
+            },
+            doc! {"$set": {"status": "delivering", "next_attempt_at": lease_expires_at}},
+        )
+        .sort(doc! {"next_attempt_at": 1})
+        .return_document(mongodb::options::ReturnDocument::After)
+        .await
+        .map_err(|err| WebhookError::RequestFailure(err.to_string()))
+}
+
+/// The outcome of an attempt to deliver an event to a webhook.
+enum DeliveryOutcome {
+    Delivered,
+    /// The attempt failed, but may succeed if it is retried.
+    Failed(String),
+    /// The delivery can never succeed (e.g., the webhook was deleted), so it
+    /// is not retried.
+    Abandoned(String),
+}
+
+/// Send the event of a delivery to its webhook.
+///
+/// The result only fails if the delivery could not be looked up; the outcome
+/// holds the reason the attempt itself failed.
+async fn attempt_webhook_delivery(
+    delivery: &WebhookDeliveryDocument,
+    database: &mongodb::Database,
+) -> Result<DeliveryOutcome, WebhookError> {
+    let webhook = database
+        .collection::<WebhookDocument>("webhook")
+        .find_one(doc! {"_id": delivery.webhook_id})
+        .await
+        .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+    let Some(webhook) = webhook else {
+        return Ok(DeliveryOutcome::Abandoned(WEBHOOK_DELETED.to_string()));
+    };
+
+    let event = database
+        .collection::<EventDocument>("event")
+        .find_one(doc! {"_id": delivery.event_id})
+        .await
+        .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+    let Some(event) = event else {
+        return Ok(DeliveryOutcome::Abandoned("event was deleted".to_string()));
+    };
+
+    let event_id = event.id.to_string();
+    let body = serde_json::to_string(&Event::from(event))
+        .map_err(|err| WebhookError::RequestFailure(err.to_string()))?;
+
+    Ok(
+        match webhooks::send(&webhook.url, &webhook.secret, &event_id, body).await {
+            Ok(()) => DeliveryOutcome::Delivered,
+            Err(err) => DeliveryOutcome::Failed(err.to_string()),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn clerk_failures_map_to_errors() {
+        use wiremock::{matchers::path, Mock, MockServer, ResponseTemplate};
+
+        /// Checks whether an error is the one expected for a case.
+        type Expected = fn(&UserError) -> bool;
+
+        let cases: [(u16, Expected); 6] = [
+            (400, |err| matches!(err, UserError::BadId(..))),
+            (401, |err| matches!(err, UserError::InvalidApiKey)),
+            (404, |err| matches!(err, UserError::NotFound(..))),
+            (429, |err| matches!(err, UserError::RateLimited)),
+            (500, |err| matches!(err, UserError::Unavailable)),
+            (503, |err| matches!(err, UserError::Unavailable)),
+        ];
+
+        for (status, expected) in cases {
+            let server = MockServer::start().await;
+            Mock::given(path("/users/user_123"))
+                .respond_with(ResponseTemplate::new(status))
+                .mount(&server)
+                .await;
+
+            let err = get_user_by_id_from(&server.uri(), "user_123", "key")
+                .await
+                .unwrap_err();
+            assert!(expected(&err), "{status}: {err:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn clerk_responses_that_are_not_users_are_errors() {
+        use wiremock::{matchers::path, Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(path("/users/user_123"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+            .mount(&server)
+            .await;
+
+        let err = get_user_by_id_from(&server.uri(), "user_123", "key")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, UserError::InvalidResponse(..)), "{err:?}");
+    }
+
+    #[test]
+    fn month_sheets_match_loosely() {
+        let titles = [
+            "Summary", "January", "feb 2025", "Mar", "April", "MAY", "June", "July", "Agust",
+            "Sept", "Octobre", "Nov.", "Notes",
+        ];
+
+        let matches = match_month_sheets(&titles);
+
+        let expected: Vec<Option<usize>> = (1..=11).map(Some).chain([None]).collect();
+        assert_eq!(matches.to_vec(), expected);
+    }
+
+    fn get_property(name: &str, matching: ExpenseMatching) -> Property {
+        Property {
+            id: "0123456789abcdef01234567".to_string(),
+            user_id: "user".to_string(),
+            name: name.to_string(),
+            address: None,
+            fee_agreements: Vec::new(),
+            expense_matching: matching,
+        }
+    }
+
+    #[test]
+    fn expenses_match_names_regardless_of_case() {
+        let property = get_property("Lake House", ExpenseMatching::default());
+
+        let row = serde_json::json!([45678.5, "", "  lake HOUSE ", 12.5, "Soap"]);
+        assert!(is_property_expense(&property, &row));
+
+        let row = serde_json::json!([45678.5, "", "Beach House", 12.5, "Soap"]);
+        assert!(!is_property_expense(&property, &row));
+    }
+
+    #[test]
+    fn expenses_match_aliases_and_codes() {
+        let matching = ExpenseMatching {
+            code: Some("LAKE-01".to_string()),
+            aliases: vec!["Lakehouse".to_string()],
+        };
+        let property = get_property("Lake House", matching);
+
+        let row = serde_json::json!([45678.5, "", "lakehouse", 12.5, "Soap"]);
+        assert!(is_property_expense(&property, &row));
+
+        let row =
+            serde_json::json!([45678.5, "", "Old Name", 12.5, "Soap", "", "", "", "", "lake-01"]);
+        assert!(is_property_expense(&property, &row));
+
+        // Codes take precedence over names.
+        let row = serde_json::json!([
+            45678.5,
+            "",
+            "Lake House",
+            12.5,
+            "Soap",
+            "",
+            "",
+            "",
+            "",
+            "BEACH-01"
+        ]);
+        assert!(!is_property_expense(&property, &row));
+    }
+
+    #[test]
+    fn expenses_parse_text_cells() {
+        let values = ExpenseValues(
+            CellValue::Text("2025-01-31 14:05:00".into()),
+            CellValue::default(),
+            CellValue::Text("Lake House".into()),
+            CellValue::Text("$1,200.50".into()),
+            CellValue::Text(" Soap ".into()),
+            CellValue::default(),
+            CellValue::default(),
+            CellValue::default(),
+            CellValue::default(),
+            CellValue::default(),
+        );
+
+        let expense = parse_expense(2, &values).unwrap();
+        assert_eq!(expense.amount, 1200.5);
+        assert_eq!(expense.description, "Soap");
+        assert_eq!(
+            expense.timestamp,
+            chrono::NaiveDate::from_ymd_opt(2025, 1, 31)
+                .unwrap()
+                .and_hms_opt(14, 5, 0)
+                .unwrap()
+        );
+
+        // Serial numbers are still read as dates and amounts.
+        let mut values = values;
+        values.0 = CellValue::Number(45688.5);
+        let expense = parse_expense(2, &values).unwrap();
+        assert_eq!(expense.timestamp.to_string(), "2025-01-31 12:00:00");
+    }
+
+    #[test]
+    fn expenses_with_invalid_cells_are_errors() {
+        let values = ExpenseValues {
+            0: CellValue::Text("yesterday".into()),
+            3: CellValue::Number(12.0),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            parse_expense(7, &values),
+            Err(ExpenseError::InvalidRow(7, _))
+        ));
+    }
+
+    #[test]
+    fn reservations_parse_text_cells() {
+        let values = ReservationValues(
+            CellValue::Text("Airbnb".into()),
+            CellValue::Text("2/3/2025".into()),
+            CellValue::Text("1/28/2025".into()),
+            CellValue::Number(45688.0),
+            CellValue::Text("$1,000.00".into()),
+            CellValue::default(),
+            CellValue::Text("800".into()),
+        );
+
+        let reservation = parse_reservation(&Month::January, 3, &values).unwrap();
+        assert_eq!(reservation.platform, "airbnb");
+        assert_eq!(reservation.revenue, 1000.0);
+        assert_eq!(reservation.management_fee, 0.0);
+        assert_eq!(reservation.net_profit, 800.0);
+
+        assert_eq!(reservation.check_in.to_string(), "2025-01-28 00:00:00");
+        assert_eq!(reservation.check_out.to_string(), "2025-01-31 00:00:00");
+
+        let mut values = values;
+        values.1 = CellValue::Text("soon".into());
+        assert!(matches!(
+            parse_reservation(&Month::January, 3, &values),
+            Err(ReservationError::InvalidRow(_, 3, _))
+        ));
+    }
+
+    #[test]
+    fn exact_names_are_preferred() {
+        let titles = ["Junee", "June", "Jun"];
+
+        assert_eq!(match_month_sheets(&titles)[5], Some(1));
+    }
+
+    #[test]
+    fn reconciliation_carries_the_balance() {
+        let mut owed = [0.0; 12];
+        let mut paid = [0.0; 12];
+        owed[0] = 1000.0;
+        paid[0] = 800.0;
+        owed[1] = -50.0;
+        paid[2] = 150.0;
+
+        let periods = reconcile(250.0, &owed, &paid);
+        let balances: Vec<f32> = periods.iter().map(|p| p.balance).collect();
+
+        assert_eq!(periods.len(), 12);
+        assert_eq!(periods[0].month, 1);
+        assert_eq!(periods[11].month, 12);
+        assert_eq!(&balances[..4], &[450.0, 400.0, 250.0, 250.0]);
+        assert_eq!(balances[11], 250.0);
+
+        // Without an opening balance, overpaying leaves a negative balance.
+        assert_eq!(reconcile(0.0, &[0.0; 12], &paid)[11].balance, -950.0);
+    }
+
+    #[test]
+    fn baselines_use_the_sample_deviation() {
+        assert_eq!(get_baseline(&[]), (0.0, 0.0));
+        assert_eq!(get_baseline(&[400.0]), (400.0, 100.0));
+        assert_eq!(get_baseline(&[-400.0]), (-400.0, 100.0));
+        assert_eq!(get_baseline(&[100.0, 200.0, 300.0]), (200.0, 100.0));
+        assert_eq!(get_baseline(&[250.0, 250.0]), (250.0, 0.0));
+    }
+
+    #[test]
+    fn bookings_are_a_floor_for_projections() {
+        let samples = [1000.0, 1200.0, 1400.0];
+
+        // Nothing is booked yet, so the baseline and its band are projected.
+        let forecast = forecast_month(6, 0.0, 0.0, &samples, false);
+        assert_eq!(forecast.baseline, 1200.0);
+        assert_eq!(forecast.projected, 1200.0);
+        assert_eq!((forecast.lower, forecast.upper), (1000.0, 1400.0));
+
+        // Bookings within the band only raise its lower end.
+        let forecast = forecast_month(6, 300.0, 800.0, &samples, false);
+        assert_eq!(forecast.projected, 1200.0);
+        assert_eq!((forecast.lower, forecast.upper), (1100.0, 1400.0));
+
+        // Bookings above the band replace it entirely.
+        let forecast = forecast_month(6, 0.0, 1500.0, &samples, false);
+        assert_eq!(forecast.baseline, 1200.0);
+        assert_eq!(forecast.projected, 1500.0);
+        assert_eq!((forecast.lower, forecast.upper), (1500.0, 1500.0));
+
+        // Without history, only the bookings are projected.
+        let forecast = forecast_month(6, 0.0, 500.0, &[], false);
+        assert_eq!(forecast.projected, 500.0);
+    }
+
+    #[test]
+    fn past_months_only_report_actual_profit() {
+        let forecast = forecast_month(1, 900.0, 0.0, &[1000.0, 1200.0, 1400.0], true);
+
+        assert_eq!(forecast.baseline, 900.0);
+        assert_eq!(forecast.projected, 900.0);
+        assert_eq!((forecast.lower, forecast.upper), (900.0, 900.0));
+    }
+
+    fn get_summary(revenue: f32, nights_booked: i64, nights_available: i64) -> FinancialSummary {
+        FinancialSummary {
+            revenue,
+            expenses: 100.0,
+            net_profit: revenue - 100.0,
+            nights_booked,
+            nights_available,
+            occupancy: get_occupancy(nights_booked, nights_available),
+        }
+    }
+
+    #[test]
+    fn portfolio_totals_only_include_summarized_properties() {
+        let property = || get_property("Lake House", ExpenseMatching::default());
+        let properties = [
+            PropertySummary {
+                property: property(),
+                summary: Some(get_summary(1000.0, 300, 365)),
+                error: None,
+            },
+            PropertySummary {
+                property: property(),
+                summary: None,
+                error: Some("no spreadsheet".to_string()),
+            },
+            PropertySummary {
+                property: property(),
+                summary: Some(get_summary(500.0, 65, 365)),
+                error: None,
+            },
+        ];
+
+        let total = get_portfolio_total(&properties);
+        assert_eq!(total.revenue, 1500.0);
+        assert_eq!(total.expenses, 200.0);
+        assert_eq!(total.net_profit, 1300.0);
+        assert_eq!((total.nights_booked, total.nights_available), (365, 730));
+        // Occupancy is recalculated from the combined nights, rather than
+        // averaged across properties.
+        assert_eq!(total.occupancy, 0.5);
+
+        assert_eq!(get_portfolio_total(&[]).occupancy, 0.0);
+    }
+
+    #[test]
+    fn summaries_only_count_nights_within_the_period() {
+        let night = |month, day| {
+            chrono::NaiveDate::from_ymd_opt(2025, month, day)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        };
+        let reservation = |check_in, check_out, revenue| Reservation {
+            platform: "airbnb".to_string(),
+            payout_date: check_in,
+            check_in,
+            check_out,
+            revenue,
+            management_fee: 0.0,
+            net_profit: revenue * 0.8,
+            sheet: String::new(),
+            row: 0,
+            warnings: Vec::new(),
+        };
+        let expense = Expense {
+            amount: 50.0,
+            description: String::new(),
+            timestamp: night(2, 10),
+            receipt_link: String::new(),
+            merchant: String::new(),
+            buyers_name: String::new(),
+            category: String::new(),
+            row: 2,
+        };
+
+        // February, with a stay from the end of January and one into March.
+        let summary = summarize(
+            night(2, 1).date(),
+            night(3, 1).date(),
+            &[
+                reservation(night(1, 30), night(2, 3), 500.0),
+                reservation(night(2, 10), night(2, 14), 400.0),
+                reservation(night(2, 26), night(3, 4), 700.0),
+            ],
+            &[expense],
+        );
+
+        assert_eq!(summary.revenue, 1600.0);
+        assert_eq!(summary.expenses, 50.0);
+        assert_eq!(summary.net_profit, 1230.0);
+        assert_eq!((summary.nights_booked, summary.nights_available), (9, 28));
+        assert_eq!(summary.occupancy, 9.0 / 28.0);
+    }
+
+    #[test]
+    fn budget_thresholds_are_reached_at_their_utilization() {
+        let budget = Budget {
+            id: String::new(),
+            property_id: String::new(),
+            category: None,
+            period: BudgetPeriod::Monthly,
+            amount: 500.0,
+            thresholds: vec![0.5, 0.8, 1.0],
+        };
+
+        let status = get_budget_status(budget.clone(), 0.0);
+        assert_eq!(status.utilization, 0.0);
+        assert!(status.thresholds_reached.is_empty());
+
+        // Reaching a threshold exactly counts as reaching it.
+        let status = get_budget_status(budget.clone(), 400.0);
+        assert_eq!(status.utilization, 0.8);
+        assert_eq!(status.thresholds_reached, [0.5, 0.8]);
+        assert_eq!(status.remaining, 100.0);
+        assert!(!status.over_budget);
+
+        let status = get_budget_status(budget.clone(), 500.0);
+        assert_eq!(status.thresholds_reached, [0.5, 0.8, 1.0]);
+        assert!(!status.over_budget);
+
+        let status = get_budget_status(budget, 750.0);
+        assert_eq!(status.utilization, 1.5);
+        assert_eq!(status.thresholds_reached, [0.5, 0.8, 1.0]);
+        assert_eq!(status.remaining, -250.0);
+        assert!(status.over_budget);
+    }
+
+    #[test]
+    fn years_past_the_last_supported_date_are_invalid() {
+        let (start, end) = get_year_bounds(2025).unwrap();
+        assert_eq!(start.to_string(), "2025-01-01");
+        assert_eq!(end.to_string(), "2026-01-01");
+
+        // The first day of the next year is out of range for the last year.
+        let last_year = chrono::NaiveDate::MAX.year();
+        assert!(matches!(
+            get_year_bounds(last_year),
+            Err(SummaryError::InvalidYear(year)) if year == last_year
+        ));
+        assert!(get_year_bounds(i32::MAX).is_err());
+    }
 }
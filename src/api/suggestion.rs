@@ -0,0 +1,244 @@
+//! Suggests values for an expense from the text recognized on its receipt.
+
+use crate::ocr::Line;
+
+use super::model::{ExpenseSuggestion, Suggested};
+
+/// Keywords that label the total of a receipt, from most to least specific.
+static TOTAL_KEYWORDS: &[&str] = &["grand total", "amount due", "balance due", "total"];
+
+/// Keywords that look like a total, but are not the amount that was paid.
+static NOT_TOTAL_KEYWORDS: &[&str] = &["subtotal", "sub total", "sub-total", "tax", "savings"];
+
+/// Words that commonly appear at the top of a receipt, but are not the name
+/// of the merchant.
+static NOT_MERCHANT_KEYWORDS: &[&str] = &["receipt", "welcome", "invoice", "order"];
+
+/// The number of lines at the top of the receipt to search for the merchant.
+const MERCHANT_SEARCH_LINES: usize = 5;
+
+// Two-digit years are tried first; otherwise, `%Y` would parse `25` as the
+// year 25 instead of 2025.
+static DATE_FORMATS: &[&str] = &[
+    "%m/%d/%y", "%m/%d/%Y", "%m-%d-%y", "%m-%d-%Y", "%Y-%m-%d", "%Y/%m/%d", "%b %d %Y", "%B %d %Y",
+    "%d %b %Y",
+];
+
+/// Suggest the amount, date and merchant of an expense from its receipt.
+///
+/// Each suggestion includes a confidence score (0.0 to 1.0) that combines how
+/// confident the text recognition was with how reliable the heuristic is.
+pub fn suggest_expense(lines: &[Line]) -> ExpenseSuggestion {
+    ExpenseSuggestion {
+        amount: suggest_amount(lines),
+        timestamp: suggest_date(lines),
+        merchant: suggest_merchant(lines),
+    }
+}
+
+fn suggest_amount(lines: &[Line]) -> Option<Suggested<f32>> {
+    for keyword in TOTAL_KEYWORDS {
+        // Totals are usually printed at the bottom of the receipt, after any
+        // intermediate totals; prefer the last matching line.
+        let total = lines.iter().rev().find_map(|line| {
+            let text = line.text.to_lowercase();
+            let is_total = text.contains(keyword)
+                && !NOT_TOTAL_KEYWORDS.iter().any(|word| text.contains(word));
+
+            is_total
+                .then(|| parse_amounts(&line.text).last().copied())
+                .flatten()
+                .map(|amount| Suggested {
+                    value: amount,
+                    confidence: line.confidence,
+                })
+        });
+
+        if total.is_some() {
+            return total;
+        }
+    }
+
+    // Without a label, the largest amount on the receipt is a good guess,
+    // but it could just as well be the amount tendered (e.g., cash).
+    lines
+        .iter()
+        .flat_map(|line| {
+            parse_amounts(&line.text)
+                .into_iter()
+                .map(|amount| Suggested {
+                    value: amount,
+                    confidence: line.confidence * 0.5,
+                })
+        })
+        .max_by(|a, b| a.value.total_cmp(&b.value))
+}
+
+fn suggest_date(lines: &[Line]) -> Option<Suggested<chrono::NaiveDate>> {
+    lines.iter().find_map(|line| {
+        parse_date(&line.text).map(|date| Suggested {
+            value: date,
+            confidence: line.confidence,
+        })
+    })
+}
+
+fn suggest_merchant(lines: &[Line]) -> Option<Suggested<String>> {
+    lines
+        .iter()
+        .take(MERCHANT_SEARCH_LINES)
+        .find(|line| {
+            let text = line.text.trim();
+            let lowercase = text.to_lowercase();
+            let letters = text.chars().filter(|c| c.is_alphabetic()).count();
+
+            // The name of the merchant is mostly letters (unlike an address
+            // or phone number, which are usually printed right below it).
+            letters >= 3
+                && letters * 2 > text.chars().count()
+                && !NOT_MERCHANT_KEYWORDS
+                    .iter()
+                    .any(|word| lowercase.contains(word))
+        })
+        .map(|line| Suggested {
+            value: line.text.trim().to_string(),
+            // The first line of text is not always the merchant's name.
+            confidence: line.confidence * 0.8,
+        })
+}
+
+/// Get every amount of money (e.g., `$1,234.56`) in the text.
+fn parse_amounts(text: &str) -> Vec<f32> {
+    text.split_whitespace()
+        .filter_map(|word| {
+            let word = word.trim_start_matches('$').replace(',', "");
+            let (whole, cents) = word.split_once('.')?;
+
+            let is_amount = !whole.is_empty()
+                && whole.chars().all(|c| c.is_ascii_digit())
+                && cents.len() == 2
+                && cents.chars().all(|c| c.is_ascii_digit());
+
+            is_amount.then(|| word.parse().ok()).flatten()
+        })
+        .collect()
+}
+
+/// Get the first date in the text.
+fn parse_date(text: &str) -> Option<chrono::NaiveDate> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| c == ',' || c == '.')
+                .to_string()
+        })
+        .collect();
+
+    // Dates may be a single word (e.g., `01/31/2025`) or span multiple words
+    // (e.g., `Jan 31, 2025`).
+    for size in [1, 3] {
+        for window in words.windows(size) {
+            let candidate = window.join(" ");
+
+            for format in DATE_FORMATS {
+                if let Ok(date) = chrono::NaiveDate::parse_from_str(&candidate, format) {
+                    return Some(date);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_lines(text: &[&str]) -> Vec<Line> {
+        text.iter()
+            .map(|text| Line {
+                text: text.to_string(),
+                confidence: 0.9,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn amounts_need_dollars_and_cents() {
+        assert_eq!(parse_amounts("Total $1,234.56"), [1234.56]);
+        assert_eq!(parse_amounts("2 @ 3.50 7.00"), [3.5, 7.0]);
+        assert!(parse_amounts("Qty 2 Item #1234 $5 3.5 .99 1.999").is_empty());
+    }
+
+    #[test]
+    fn dates_are_found_in_any_supported_format() {
+        let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d);
+
+        assert_eq!(parse_date("Date: 01/31/25 14:02"), date(2025, 1, 31));
+        assert_eq!(parse_date("01/31/2025"), date(2025, 1, 31));
+        assert_eq!(parse_date("2025-01-31"), date(2025, 1, 31));
+        assert_eq!(parse_date("Sold Jan 31, 2025 by Sam"), date(2025, 1, 31));
+        assert_eq!(parse_date("31 Jan 2025"), date(2025, 1, 31));
+        assert_eq!(parse_date("13/31/2025"), None);
+        assert_eq!(parse_date("Thank you!"), None);
+    }
+
+    #[test]
+    fn labeled_totals_are_preferred() {
+        let lines = get_lines(&[
+            "Corner Hardware",
+            "Hammer 19.99",
+            "Subtotal 19.99",
+            "Tax 1.60",
+            "Total 21.59",
+            "Cash 40.00",
+            "Change 18.41",
+        ]);
+
+        let amount = suggest_amount(&lines).unwrap();
+        assert_eq!(amount.value, 21.59);
+        assert_eq!(amount.confidence, 0.9);
+    }
+
+    #[test]
+    fn specific_totals_are_preferred_over_total() {
+        let lines = get_lines(&["Total 10.00", "Amount Due 12.00"]);
+
+        assert_eq!(suggest_amount(&lines).unwrap().value, 12.0);
+    }
+
+    #[test]
+    fn unlabeled_amounts_fall_back_to_the_largest() {
+        let lines = get_lines(&["Soap 3.99", "Towels 24.50", "Subtotal 28.49"]);
+
+        let amount = suggest_amount(&lines).unwrap();
+        assert_eq!(amount.value, 28.49);
+        // The guess is less reliable than a labeled total.
+        assert_eq!(amount.confidence, 0.45);
+
+        assert!(suggest_amount(&get_lines(&["No amounts here"])).is_none());
+    }
+
+    #[test]
+    fn merchant_is_the_first_line_of_mostly_letters() {
+        let lines = get_lines(&[
+            "RECEIPT",
+            "***",
+            "  Corner Hardware  ",
+            "123 Main St.",
+            "(555) 123-4567",
+        ]);
+
+        let merchant = suggest_merchant(&lines).unwrap();
+        assert_eq!(merchant.value, "Corner Hardware");
+        assert!((merchant.confidence - 0.72).abs() < 1e-6);
+    }
+
+    #[test]
+    fn merchant_is_only_searched_for_at_the_top() {
+        let lines = get_lines(&["#1", "#2", "#3", "#4", "#5", "Corner Hardware"]);
+
+        assert!(suggest_merchant(&lines).is_none());
+    }
+}
@@ -0,0 +1,230 @@
+//! Detects data quality issues in the reservations read from a spreadsheet.
+
+use super::model::{IssueKind, Reservation, Severity, ValidationIssue};
+
+/// The largest difference allowed between the net profit in the sheet and the
+/// net profit calculated from the revenue and management fee.
+const NET_PROFIT_TOLERANCE: f32 = 0.01;
+
+/// Check the reservations for common spreadsheet mistakes.
+///
+/// The reservations may come from more than one sheet; overlapping stays are
+/// detected across all of them (e.g., a stay at the end of January that runs
+/// into a stay listed in February).
+pub fn validate_reservations(reservations: &[Reservation]) -> Vec<ValidationIssue> {
+    let mut issues: Vec<ValidationIssue> = reservations.iter().flat_map(validate_row).collect();
+
+    // Stays with invalid dates were already reported above; including them
+    // here would only report the same mistake a second time.
+    let mut stays: Vec<&Reservation> = reservations
+        .iter()
+        .filter(|r| r.check_out > r.check_in)
+        .collect();
+    stays.sort_by_key(|r| r.check_in);
+
+    // Keep track of the stay that checks out the latest so far, since a long
+    // stay can overlap with several of the stays that come after it.
+    let mut latest: Option<&Reservation> = None;
+
+    for stay in stays {
+        if let Some(previous) = latest {
+            if stay.check_in < previous.check_out {
+                issues.push(ValidationIssue {
+                    severity: Severity::Error,
+                    kind: IssueKind::OverlappingStay,
+                    sheet: stay.sheet.to_string(),
+                    row: stay.row,
+                    detail: format!(
+                        "stay overlaps with the reservation in {} row {}",
+                        previous.sheet, previous.row
+                    ),
+                });
+            }
+        }
+
+        if latest.is_none_or(|previous| stay.check_out > previous.check_out) {
+            latest = Some(stay);
+        }
+    }
+
+    issues
+}
+
+fn validate_row(reservation: &Reservation) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let mut report = |severity, kind, detail: String| {
+        issues.push(ValidationIssue {
+            severity,
+            kind,
+            sheet: reservation.sheet.to_string(),
+            row: reservation.row,
+            detail,
+        })
+    };
+
+    if reservation.check_out <= reservation.check_in {
+        report(
+            Severity::Error,
+            IssueKind::CheckOutBeforeCheckIn,
+            format!(
+                "check-out ({}) is not after check-in ({})",
+                reservation.check_out.date(),
+                reservation.check_in.date()
+            ),
+        );
+    }
+
+    if reservation.payout_date < reservation.check_in {
+        report(
+            Severity::Warning,
+            IssueKind::PayoutBeforeCheckIn,
+            format!(
+                "payout date ({}) is before check-in ({})",
+                reservation.payout_date.date(),
+                reservation.check_in.date()
+            ),
+        );
+    }
+
+    let expected = reservation.revenue - reservation.management_fee;
+
+    if (reservation.net_profit - expected).abs() > NET_PROFIT_TOLERANCE {
+        report(
+            Severity::Error,
+            IssueKind::NetProfitMismatch,
+            format!(
+                "net profit ({:.2}) does not equal revenue minus management fee ({:.2})",
+                reservation.net_profit, expected
+            ),
+        );
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    use super::*;
+
+    fn day(month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, month, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    /// A valid reservation in January, listed in the sheet at the row.
+    fn reservation(row: u32, check_in: NaiveDateTime, check_out: NaiveDateTime) -> Reservation {
+        Reservation {
+            platform: "airbnb".to_string(),
+            payout_date: check_in,
+            check_in,
+            check_out,
+            revenue: 100.0,
+            management_fee: 20.0,
+            net_profit: 80.0,
+            sheet: "January".to_string(),
+            row,
+            warnings: Vec::new(),
+        }
+    }
+
+    fn kinds(issues: &[ValidationIssue]) -> Vec<(IssueKind, u32)> {
+        issues.iter().map(|issue| (issue.kind, issue.row)).collect()
+    }
+
+    #[test]
+    fn valid_reservations_have_no_issues() {
+        let reservations = [
+            reservation(2, day(1, 1), day(1, 5)),
+            // Checking in on the day the previous stay checks out is fine.
+            reservation(3, day(1, 5), day(1, 8)),
+        ];
+
+        assert!(validate_reservations(&reservations).is_empty());
+    }
+
+    #[test]
+    fn long_stays_overlap_every_later_stay() {
+        let reservations = [
+            reservation(2, day(1, 1), day(1, 20)),
+            reservation(3, day(1, 5), day(1, 8)),
+            reservation(4, day(1, 10), day(1, 12)),
+            reservation(5, day(1, 20), day(1, 22)),
+        ];
+
+        let issues = validate_reservations(&reservations);
+        assert_eq!(
+            kinds(&issues),
+            [
+                (IssueKind::OverlappingStay, 3),
+                (IssueKind::OverlappingStay, 4)
+            ]
+        );
+        assert!(issues.iter().all(|issue| issue.detail.ends_with("row 2")));
+    }
+
+    #[test]
+    fn stays_overlap_across_sheets() {
+        let mut february = reservation(2, day(2, 1), day(2, 3));
+        february.sheet = "February".to_string();
+        let reservations = [reservation(30, day(1, 30), day(2, 2)), february];
+
+        let issues = validate_reservations(&reservations);
+        assert_eq!(kinds(&issues), [(IssueKind::OverlappingStay, 2)]);
+        assert_eq!(issues[0].sheet, "February");
+        assert!(issues[0].detail.contains("January row 30"));
+    }
+
+    #[test]
+    fn invalid_stays_are_not_also_overlaps() {
+        let reservations = [
+            reservation(2, day(1, 1), day(1, 10)),
+            // Checks out before it checks in, within the stay above.
+            reservation(3, day(1, 6), day(1, 4)),
+            // Checks out on the day it checks in.
+            reservation(4, day(1, 5), day(1, 5)),
+        ];
+
+        let issues = validate_reservations(&reservations);
+        assert_eq!(
+            kinds(&issues),
+            [
+                (IssueKind::CheckOutBeforeCheckIn, 3),
+                (IssueKind::CheckOutBeforeCheckIn, 4)
+            ]
+        );
+        assert!(issues.iter().all(|issue| issue.severity == Severity::Error));
+    }
+
+    #[test]
+    fn payouts_before_check_in_are_warnings() {
+        let mut early = reservation(2, day(1, 10), day(1, 12));
+        early.payout_date = day(1, 9);
+
+        let issues = validate_reservations(&[early]);
+        assert_eq!(kinds(&issues), [(IssueKind::PayoutBeforeCheckIn, 2)]);
+        assert_eq!(issues[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn net_profit_is_checked_within_a_tolerance() {
+        let mut inside = reservation(2, day(1, 1), day(1, 3));
+        inside.net_profit = 80.009;
+        let mut outside = reservation(3, day(1, 5), day(1, 7));
+        outside.net_profit = 80.011;
+        let mut below = reservation(4, day(1, 9), day(1, 11));
+        below.net_profit = 79.989;
+
+        let issues = validate_reservations(&[inside, outside, below]);
+        assert_eq!(
+            kinds(&issues),
+            [
+                (IssueKind::NetProfitMismatch, 3),
+                (IssueKind::NetProfitMismatch, 4)
+            ]
+        );
+    }
+}
@@ -0,0 +1,467 @@
+//! Keeps an append-only record of who viewed or changed what.
+//!
+//! Every API request is recorded by [`middleware`], and the service functions
+//! that change data also [`record`] what was changed. Entries are only ever
+//! inserted into the `audit_log` collection; nothing updates or deletes them.
+
+use std::{
+    error, fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    extract::{OriginalUri, Request, State},
+    http::{HeaderMap, Method},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use futures::{future::Either, TryStreamExt};
+use mongodb::bson::{doc, oid::ObjectId, Document};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{api::error::ApiError, AppState};
+
+/// The kind of user that performed an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditRole {
+    /// The owner of the properties, using the dashboard.
+    Owner,
+    /// An administrator, using the admin secret key.
+    Admin,
+    /// A background job, or a request that is not tied to a user.
+    System,
+    /// A request that was not authorized (e.g., with a missing or invalid
+    /// admin key or session token).
+    Anonymous,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Read,
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+}
+
+/// A single entry in the audit log.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuditEntry {
+    pub id: String,
+    /// The ID of the user that performed the action, `admin`, `system`, or
+    /// `anonymous`.
+    pub actor: String,
+    pub role: AuditRole,
+    /// The method and path of the request (e.g., `GET /api/users/...`), if
+    /// the action was performed through the API.
+    pub route: Option<String>,
+    pub property_id: Option<String>,
+    pub action: AuditAction,
+    /// What was changed (e.g., `payout`), for entries recorded by services.
+    pub resource: Option<String>,
+    pub resource_id: Option<String>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub ip: Option<String>,
+    pub outcome: AuditOutcome,
+    /// The status code of the response, for entries recorded by requests.
+    pub status: Option<u16>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AuditEntryDocument {
+    #[serde(rename = "_id")]
+    id: ObjectId,
+    actor: String,
+    role: AuditRole,
+    route: Option<String>,
+    property_id: Option<String>,
+    action: AuditAction,
+    resource: Option<String>,
+    resource_id: Option<String>,
+    /// Stored as a BSON date (rather than a string), so entries can be
+    /// filtered by time range.
+    timestamp: mongodb::bson::DateTime,
+    ip: Option<String>,
+    outcome: AuditOutcome,
+    status: Option<u16>,
+}
+
+impl From<AuditEntryDocument> for AuditEntry {
+    fn from(document: AuditEntryDocument) -> Self {
+        Self {
+            id: document.id.to_string(),
+            actor: document.actor,
+            role: document.role,
+            route: document.route,
+            property_id: document.property_id,
+            action: document.action,
+            resource: document.resource,
+            resource_id: document.resource_id,
+            timestamp: chrono::DateTime::from_timestamp_millis(
+                document.timestamp.timestamp_millis(),
+            )
+            .unwrap_or_default(),
+            ip: document.ip,
+            outcome: document.outcome,
+            status: document.status,
+        }
+    }
+}
+
+/// Who performed an action.
+#[derive(Debug, Clone)]
+struct Actor {
+    id: String,
+    role: AuditRole,
+}
+
+impl Actor {
+    fn new(id: &str, role: AuditRole) -> Self {
+        Self {
+            id: id.to_string(),
+            role,
+        }
+    }
+}
+
+/// Who is making the current request.
+#[derive(Debug, Clone)]
+struct AuditContext {
+    /// Shared with the request, so it can be changed once the request is
+    /// authorized (see [`authorize`]).
+    actor: Arc<Mutex<Actor>>,
+    route: Option<String>,
+    ip: Option<String>,
+}
+
+impl AuditContext {
+    fn actor(&self) -> Actor {
+        self.actor.lock().unwrap().clone()
+    }
+}
+
+tokio::task_local! {
+    /// Set for the duration of each request, so services can record changes
+    /// without every function having to be told who made the request.
+    static CONTEXT: AuditContext;
+}
+
+/// Record every request made to the API.
+pub async fn middleware(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    // The API is nested, so the path of the request does not include the
+    // prefix; the original path is recorded instead.
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|uri| uri.path().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let segment_after = |name: &str| {
+        segments
+            .iter()
+            .position(|segment| *segment == name)
+            .and_then(|index| segments.get(index + 1))
+            .map(|segment| segment.to_string())
+    };
+
+    // Administrators and owners are only recorded once their credentials
+    // have been checked (see `authorize`).
+    let actor = if matches!(segments.get(1), Some(&"admin" | &"users")) {
+        Actor::new("anonymous", AuditRole::Anonymous)
+    } else {
+        Actor::new("system", AuditRole::System)
+    };
+
+    let context = AuditContext {
+        actor: Arc::new(Mutex::new(actor)),
+        route: Some(format!("{} {}", request.method(), path)),
+        ip: get_client_ip(request.headers()),
+    };
+    let action = match *request.method() {
+        Method::POST => AuditAction::Create,
+        Method::PUT | Method::PATCH => AuditAction::Update,
+        Method::DELETE => AuditAction::Delete,
+        _ => AuditAction::Read,
+    };
+    let property_id = segment_after("properties");
+
+    let response = CONTEXT.scope(context.clone(), next.run(request)).await;
+    let status = response.status();
+    let actor = context.actor();
+
+    let document = AuditEntryDocument {
+        id: ObjectId::new(),
+        actor: actor.id,
+        role: actor.role,
+        route: context.route,
+        property_id,
+        action,
+        resource: None,
+        resource_id: None,
+        timestamp: mongodb::bson::DateTime::now(),
+        ip: context.ip,
+        outcome: if status.is_client_error() || status.is_server_error() {
+            AuditOutcome::Failure
+        } else {
+            AuditOutcome::Success
+        },
+        status: Some(status.as_u16()),
+    };
+    insert(document, &state.db).await;
+
+    response
+}
+
+/// Attribute the rest of the current request to an actor whose credentials
+/// were checked (e.g., an administrator).
+pub fn authorize(actor: &str, role: AuditRole) {
+    // Outside of a request, there is nothing to attribute.
+    let _ = CONTEXT.try_with(|context| *context.actor.lock().unwrap() = Actor::new(actor, role));
+}
+
+/// Run a future with the audit context of the current request, so changes
+/// made by work that outlives the request (e.g., a job spawned by it) are
+/// still attributed to whoever made it.
+pub fn in_current_context<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    match CONTEXT.try_with(|context| context.clone()) {
+        Ok(context) => Either::Left(CONTEXT.scope(context, future)),
+        Err(_) => Either::Right(future),
+    }
+}
+
+/// Record a change made by a service, attributed to whoever made the current
+/// request (or the system, if the change was not made through the API).
+pub async fn record(
+    action: AuditAction,
+    resource: &str,
+    resource_id: Option<&str>,
+    property_id: Option<&str>,
+    database: &mongodb::Database,
+) {
+    let context = CONTEXT
+        .try_with(|context| context.clone())
+        .unwrap_or_else(|_| AuditContext {
+            actor: Arc::new(Mutex::new(Actor::new("system", AuditRole::System))),
+            route: None,
+            ip: None,
+        });
+    let actor = context.actor();
+
+    let document = AuditEntryDocument {
+        id: ObjectId::new(),
+        actor: actor.id,
+        role: actor.role,
+        route: context.route,
+        property_id: property_id.map(str::to_string),
+        action,
+        resource: Some(resource.to_string()),
+        resource_id: resource_id.map(str::to_string),
+        timestamp: mongodb::bson::DateTime::now(),
+        ip: context.ip,
+        outcome: AuditOutcome::Success,
+        status: None,
+    };
+    insert(document, database).await;
+}
+
+/// Insert an entry into the audit log.
+///
+/// Failing to record an entry should not fail the request it describes, so
+/// errors are only reported.
+async fn insert(document: AuditEntryDocument, database: &mongodb::Database) {
+    if let Err(err) = database
+        .collection::<AuditEntryDocument>("audit_log")
+        .insert_one(&document)
+        .await
+    {
+        tracing::error!("failed to record audit entry: {err}");
+    }
+}
+
+/// Get the address of the client; requests are forwarded by a proxy, so the
+/// address of the connection is not the client's.
+///
+/// Proxies append the address they received the request from to
+/// `X-Forwarded-For`, so only the last address was added by our proxy; the
+/// ones before it are whatever the client sent.
+fn get_client_ip(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("X-Forwarded-For")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next_back())
+        .or_else(|| {
+            headers
+                .get("X-Real-IP")
+                .and_then(|value| value.to_str().ok())
+        })
+        .map(|value| value.trim().to_string())
+}
+
+/// Which entries of the audit log to get.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditQuery {
+    pub actor: Option<String>,
+    pub property_id: Option<String>,
+    /// Only include entries recorded at or after this time.
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only include entries recorded before this time.
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub limit: Option<i64>,
+}
+
+/// Get the entries of the audit log that match the query, newest first.
+pub async fn get_entries(
+    query: AuditQuery,
+    database: &mongodb::Database,
+) -> Result<Vec<AuditEntry>, AuditError> {
+    let mut filter = Document::new();
+    if let Some(actor) = query.actor {
+        filter.insert("actor", actor);
+    }
+    if let Some(property_id) = query.property_id {
+        filter.insert("property_id", property_id);
+    }
+
+    let mut timestamp = Document::new();
+    if let Some(from) = query.from {
+        timestamp.insert(
+            "$gte",
+            mongodb::bson::DateTime::from_millis(from.timestamp_millis()),
+        );
+    }
+    if let Some(to) = query.to {
+        timestamp.insert(
+            "$lt",
+            mongodb::bson::DateTime::from_millis(to.timestamp_millis()),
+        );
+    }
+    if !timestamp.is_empty() {
+        filter.insert("timestamp", timestamp);
+    }
+
+    let cursor = database
+        .collection::<AuditEntryDocument>("audit_log")
+        .find(filter)
+        .sort(doc! {"_id": -1})
+        .limit(query.limit.unwrap_or(100).clamp(1, 1000))
+        .await
+        .map_err(|err| AuditError::RequestFailure(err.to_string()))?;
+
+    let documents = cursor
+        .try_collect::<Vec<AuditEntryDocument>>()
+        .await
+        .map_err(|err| AuditError::RequestFailure(err.to_string()))?;
+
+    Ok(documents.into_iter().map(AuditEntry::from).collect())
+}
+
+/// An error occurred while trying to get entries from the audit log.
+#[derive(Debug)]
+pub enum AuditError {
+    /// An unexpected error occurred while trying to get the data.
+    RequestFailure(String),
+}
+
+impl error::Error for AuditError {}
+
+impl fmt::Display for AuditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RequestFailure(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl From<AuditError> for ApiError {
+    fn from(err: AuditError) -> Self {
+        let (status, code) = match &err {
+            AuditError::RequestFailure(..) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "audit.request_failed")
+            }
+        };
+
+        Self::new(status, code, err)
+    }
+}
+
+impl IntoResponse for AuditError {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_context() -> AuditContext {
+        AuditContext {
+            actor: Arc::new(Mutex::new(Actor::new("anonymous", AuditRole::Anonymous))),
+            route: Some("POST /api/admin/jobs/detect_changes".to_string()),
+            ip: None,
+        }
+    }
+
+    #[test]
+    fn client_ip_is_the_address_added_by_the_proxy() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Forwarded-For", "10.0.0.1, 203.0.113.7".parse().unwrap());
+        assert_eq!(get_client_ip(&headers).as_deref(), Some("203.0.113.7"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Real-IP", "203.0.113.7".parse().unwrap());
+        assert_eq!(get_client_ip(&headers).as_deref(), Some("203.0.113.7"));
+
+        assert_eq!(get_client_ip(&HeaderMap::new()), None);
+    }
+
+    #[tokio::test]
+    async fn requests_are_anonymous_until_authorized() {
+        let context = get_context();
+
+        CONTEXT
+            .scope(context.clone(), async {
+                assert_eq!(CONTEXT.with(AuditContext::actor).role, AuditRole::Anonymous);
+                authorize("admin", AuditRole::Admin);
+            })
+            .await;
+
+        let actor = context.actor();
+        assert_eq!((actor.id.as_str(), actor.role), ("admin", AuditRole::Admin));
+    }
+
+    #[tokio::test]
+    async fn spawned_work_keeps_the_context() {
+        let context = get_context();
+
+        let actor = CONTEXT
+            .scope(context, async {
+                authorize("admin", AuditRole::Admin);
+                tokio::spawn(in_current_context(async {
+                    CONTEXT.with(AuditContext::actor)
+                }))
+                .await
+                .unwrap()
+            })
+            .await;
+        assert_eq!(actor.role, AuditRole::Admin);
+
+        // Outside of a request, work is not attributed to anyone.
+        let outside = tokio::spawn(in_current_context(async {
+            CONTEXT.try_with(AuditContext::actor).is_err()
+        }));
+        assert!(outside.await.unwrap());
+    }
+}
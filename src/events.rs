@@ -0,0 +1,235 @@
+//! Streams the changes detected in the spreadsheets to connected dashboards.
+
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    sync::{Arc, Mutex},
+};
+
+use axum::response::sse;
+use futures::{stream, Stream, StreamExt};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::api::model::{ChangeKind, Event};
+
+/// The number of recent events kept for clients that reconnect.
+const EVENT_LOG_CAPACITY: usize = 1000;
+
+/// The number of events a slow client can fall behind before it has to
+/// reload instead.
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Publishes events to every subscriber, and keeps a bounded log of recent
+/// events so clients can resume from the last event they received.
+pub struct EventBus {
+    sender: broadcast::Sender<Arc<Event>>,
+    log: Mutex<VecDeque<Arc<Event>>>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(CHANNEL_CAPACITY).0,
+            log: Mutex::new(VecDeque::with_capacity(EVENT_LOG_CAPACITY)),
+        }
+    }
+}
+
+impl EventBus {
+    pub fn publish(&self, event: Event) {
+        let event = Arc::new(event);
+
+        // The log is locked while sending, so a new subscriber either finds
+        // the event in the log or receives it; never both or neither.
+        let mut log = self.log.lock().unwrap();
+        if log.len() == EVENT_LOG_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(Arc::clone(&event));
+
+        // Sending only fails if nobody is subscribed, which is fine.
+        let _ = self.sender.send(event);
+    }
+
+    /// Subscribe to new events, also replaying the events published after
+    /// `last_event_id` (if provided).
+    pub fn subscribe(&self, last_event_id: Option<&str>) -> Subscription {
+        let log = self.log.lock().unwrap();
+        let receiver = self.sender.subscribe();
+
+        let replay = match last_event_id {
+            None => Some(Vec::new()),
+            // If the event is no longer in the log, some events were missed.
+            Some(id) => log
+                .iter()
+                .position(|event| event.id == id)
+                .map(|index| log.iter().skip(index + 1).cloned().collect()),
+        };
+
+        Subscription { replay, receiver }
+    }
+}
+
+/// The events a client receives, starting with any events it missed.
+pub struct Subscription {
+    /// `None` if the client missed events that are no longer in the log.
+    replay: Option<Vec<Arc<Event>>>,
+    receiver: broadcast::Receiver<Arc<Event>>,
+}
+
+impl Subscription {
+    /// Convert the subscription into a stream of server-sent events, only
+    /// including the events the client is allowed to see.
+    ///
+    /// Each event is named after the kind of change (e.g., `reservation_added`)
+    /// and uses its ID as the event ID. A `reset` event is sent when the
+    /// client missed events, letting it know to reload instead.
+    pub fn into_stream(
+        self,
+        visible: impl Fn(&Event) -> bool + Send + 'static,
+    ) -> impl Stream<Item = Result<sse::Event, Infallible>> + Send {
+        self.into_events(visible).map(|event| {
+            Ok(match event {
+                Some(event) => to_sse_event(&event),
+                None => sse::Event::default().event("reset").data("reload"),
+            })
+        })
+    }
+
+    /// The events the client is allowed to see, with `None` wherever the
+    /// client missed events.
+    fn into_events(
+        self,
+        visible: impl Fn(&Event) -> bool + Send + 'static,
+    ) -> impl Stream<Item = Option<Arc<Event>>> + Send {
+        let replay: Vec<Option<Arc<Event>>> = match self.replay {
+            Some(events) => events.into_iter().map(Some).collect(),
+            None => vec![None],
+        };
+
+        let live = stream::unfold(self.receiver, |mut receiver| async move {
+            match receiver.recv().await {
+                Ok(event) => Some((Some(event), receiver)),
+                Err(RecvError::Lagged(..)) => Some((None, receiver)),
+                Err(RecvError::Closed) => None,
+            }
+        });
+
+        stream::iter(replay).chain(live).filter(move |event| {
+            let keep = event.as_ref().is_none_or(|event| visible(event));
+            async move { keep }
+        })
+    }
+}
+
+fn to_sse_event(event: &Event) -> sse::Event {
+    let name = match event.change.kind() {
+        ChangeKind::ReservationAdded => "reservation_added",
+        ChangeKind::ReservationChanged => "reservation_changed",
+        ChangeKind::ReservationRemoved => "reservation_removed",
+        ChangeKind::ExpenseAdded => "expense_added",
+    };
+
+    sse::Event::default()
+        .id(&event.id)
+        .event(name)
+        // Events only contain plain data, so serializing them cannot fail.
+        .json_data(event)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::model::{Change, Expense};
+
+    fn event(id: usize, user_id: &str) -> Event {
+        Event {
+            id: id.to_string(),
+            property_id: "property".to_string(),
+            user_id: user_id.to_string(),
+            occurred_at: chrono::Utc::now(),
+            change: Change::ExpenseAdded {
+                expense: Expense {
+                    amount: 10.0,
+                    description: String::new(),
+                    timestamp: chrono::Utc::now().naive_utc(),
+                    receipt_link: String::new(),
+                    merchant: String::new(),
+                    buyers_name: String::new(),
+                    category: String::new(),
+                    row: 2,
+                },
+            },
+        }
+    }
+
+    /// Take the next few events from a subscription, as their IDs (or
+    /// `None` for a reset).
+    async fn next_ids(
+        subscription: Subscription,
+        visible: impl Fn(&Event) -> bool + Send + 'static,
+        count: usize,
+    ) -> Vec<Option<String>> {
+        subscription
+            .into_events(visible)
+            .take(count)
+            .map(|event| event.map(|event| event.id.to_string()))
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn events_after_the_last_event_id_are_replayed() {
+        let bus = EventBus::default();
+        for id in 1..=3 {
+            bus.publish(event(id, "user_1"));
+        }
+
+        let subscription = bus.subscribe(Some("1"));
+        bus.publish(event(4, "user_1"));
+
+        let ids = next_ids(subscription, |_| true, 3).await;
+        assert_eq!(ids, [Some("2".into()), Some("3".into()), Some("4".into())]);
+    }
+
+    #[tokio::test]
+    async fn evicted_event_ids_reset_the_client() {
+        let bus = EventBus::default();
+        for id in 0..=EVENT_LOG_CAPACITY {
+            bus.publish(event(id, "user_1"));
+        }
+
+        // The first event was evicted to make room for the last one.
+        let subscription = bus.subscribe(Some("0"));
+        bus.publish(event(EVENT_LOG_CAPACITY + 1, "user_1"));
+
+        let ids = next_ids(subscription, |_| true, 2).await;
+        assert_eq!(ids, [None, Some((EVENT_LOG_CAPACITY + 1).to_string())]);
+
+        // The oldest event still in the log can be resumed from.
+        let subscription = bus.subscribe(Some("2"));
+        let ids = next_ids(subscription, |_| true, 1).await;
+        assert_eq!(ids, [Some("3".into())]);
+    }
+
+    #[tokio::test]
+    async fn only_visible_events_are_sent() {
+        let bus = EventBus::default();
+        bus.publish(event(1, "user_1"));
+        bus.publish(event(2, "user_2"));
+
+        let subscription = bus.subscribe(None);
+        bus.publish(event(3, "user_2"));
+        bus.publish(event(4, "user_1"));
+
+        let visible = |event: &Event| event.user_id == "user_1";
+        let ids = next_ids(subscription, visible, 1).await;
+        assert_eq!(ids, [Some("4".into())]);
+
+        let subscription = bus.subscribe(Some("1"));
+        bus.publish(event(5, "user_1"));
+        let ids = next_ids(subscription, visible, 2).await;
+        assert_eq!(ids, [Some("4".into()), Some("5".into())]);
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Datelike;
+
+use crate::{
+    api::{
+        model::User,
+        service::{
+            check_budgets_by_month, get_all_properties, get_user_by_id, notify_budget_alerts,
+        },
+    },
+    AppState,
+};
+
+use super::{get_sheets_client, Job, JobError, Schedule};
+
+/// Compares every property's expenses with its budgets each morning, and
+/// notifies the owners about the thresholds that spending reached.
+///
+/// Each threshold only triggers an alert once per period, so owners are not
+/// notified again when the job runs the next day.
+pub struct CheckBudgets;
+
+#[async_trait]
+impl Job for CheckBudgets {
+    fn name(&self) -> &'static str {
+        "check_budgets"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::Daily { hour: 7, minute: 0 }
+    }
+
+    async fn run(&self, state: &AppState) -> Result<String, JobError> {
+        // Checking yesterday's month makes sure expenses added on the last day
+        // of a month are still compared with that month's budgets.
+        let yesterday = chrono::Utc::now().date_naive() - chrono::Days::new(1);
+        let (year, month) = (yesterday.year(), yesterday.month() as u8);
+
+        let secret_key = state
+            .secrets
+            .get("CLERK_SECRET_KEY")
+            .ok_or_else(|| JobError::RequestFailure("CLERK_SECRET_KEY is not defined".into()))?;
+        let sheets_client = get_sheets_client(state)?;
+
+        let properties = get_all_properties(&state.db)
+            .await
+            .map_err(|err| JobError::RequestFailure(err.to_string()))?;
+
+        // Most owners have more than one property, so avoid asking Clerk for
+        // the same user more than once.
+        let mut owners: HashMap<String, User> = HashMap::new();
+        let mut triggered = 0;
+        let mut failures: Vec<String> = Vec::new();
+
+        for property in &properties {
+            let alerts = match check_budgets_by_month(
+                property,
+                year,
+                month,
+                &sheets_client,
+                &state.db,
+            )
+            .await
+            {
+                Ok(alerts) => alerts,
+                Err(err) => {
+                    failures.push(format!("{}: {err}", property.name));
+                    continue;
+                }
+            };
+            triggered += alerts.len();
+
+            if alerts.is_empty() {
+                continue;
+            }
+
+            if !owners.contains_key(&property.user_id) {
+                match get_user_by_id(&property.user_id, &secret_key).await {
+                    Ok(user) => owners.insert(property.user_id.to_string(), user),
+                    Err(err) => {
+                        failures.push(format!("{}: {err}", property.name));
+                        continue;
+                    }
+                };
+            }
+
+            if let Err(err) = notify_budget_alerts(
+                &owners[&property.user_id],
+                property,
+                &alerts,
+                state.notifier.as_ref(),
+                &state.db,
+            )
+            .await
+            {
+                failures.push(format!("{}: {err}", property.name));
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(JobError::RequestFailure(format!(
+                "triggered {triggered} budget alerts for {year}-{month:02}; {}",
+                failures.join("; ")
+            )));
+        }
+
+        Ok(format!(
+            "triggered {triggered} budget alerts for {year}-{month:02}"
+        ))
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Datelike;
+
+use crate::{
+    api::{
+        model::User,
+        service::{
+            deliver_pending_webhooks, detect_expense_changes, detect_reservation_changes,
+            get_all_properties, get_user_by_id, notify_changes,
+        },
+    },
+    AppState,
+};
+
+use super::{get_sheets_client, Job, JobError, Schedule};
+
+/// Compares the current year's spreadsheets with the last time they were
+/// checked, and records the changes as events; the events are delivered to
+/// the registered webhooks by [`DeliverWebhooks`].
+///
+/// Owners are notified about new reservations and large expenses.
+pub struct DetectChanges;
+
+#[async_trait]
+impl Job for DetectChanges {
+    fn name(&self) -> &'static str {
+        "detect_changes"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::Every { minutes: 15 }
+    }
+
+    async fn run(&self, state: &AppState) -> Result<String, JobError> {
+        let year = chrono::Utc::now().year();
+        // Only needed to notify owners, so changes are still detected without
+        // it.
+        let secret_key = state.secrets.get("CLERK_SECRET_KEY");
+        let sheets_client = get_sheets_client(state)?;
+
+        let properties = get_all_properties(&state.db)
+            .await
+            .map_err(|err| JobError::RequestFailure(err.to_string()))?;
+
+        // Most owners have more than one property, so avoid asking Clerk for
+        // the same user more than once.
+        let mut owners: HashMap<String, User> = HashMap::new();
+        let mut events = 0;
+        let mut failures: Vec<String> = Vec::new();
+
+        for property in &properties {
+            let mut recorded = Vec::new();
+
+            for month in 1..=12 {
+                match detect_reservation_changes(property, year, month, &state.db, &sheets_client)
+                    .await
+                {
+                    Ok(values) => recorded.extend(values),
+                    Err(err) => failures.push(format!("{} ({month}): {err}", property.name)),
+                }
+            }
+
+            match detect_expense_changes(property, year, &sheets_client, &state.db).await {
+                Ok(values) => recorded.extend(values),
+                Err(err) => failures.push(format!("{} (expenses): {err}", property.name)),
+            }
+
+            if recorded.is_empty() {
+                continue;
+            }
+
+            if !owners.contains_key(&property.user_id) {
+                let owner = match &secret_key {
+                    Some(key) => get_user_by_id(&property.user_id, key)
+                        .await
+                        .map_err(|err| err.to_string()),
+                    None => Err("CLERK_SECRET_KEY is not defined".to_string()),
+                };
+
+                match owner {
+                    Ok(user) => {
+                        owners.insert(property.user_id.to_string(), user);
+                    }
+                    Err(err) => failures.push(format!("{}: {err}", property.name)),
+                }
+            }
+
+            if let Some(owner) = owners.get(&property.user_id) {
+                if let Err(err) = notify_changes(
+                    owner,
+                    property,
+                    &recorded,
+                    state.notifier.as_ref(),
+                    &state.db,
+                )
+                .await
+                {
+                    failures.push(format!("{}: {err}", property.name));
+                }
+            }
+
+            events += recorded.len();
+            for event in recorded {
+                state.events.publish(event);
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(JobError::RequestFailure(format!(
+                "detected {events} changes, but failed to check {}",
+                failures.join("; ")
+            )));
+        }
+
+        Ok(format!("detected {events} changes"))
+    }
+}
+
+/// Delivers new events to the registered webhooks, and retries the deliveries
+/// that failed and are due to be retried.
+pub struct DeliverWebhooks;
+
+#[async_trait]
+impl Job for DeliverWebhooks {
+    fn name(&self) -> &'static str {
+        "deliver_webhooks"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::Every { minutes: 1 }
+    }
+
+    async fn run(&self, state: &AppState) -> Result<String, JobError> {
+        let (delivered, failed) = deliver_pending_webhooks(&state.db)
+            .await
+            .map_err(|err| JobError::RequestFailure(err.to_string()))?;
+
+        Ok(format!("delivered {delivered} webhooks ({failed} failed)"))
+    }
+}
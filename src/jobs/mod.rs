@@ -0,0 +1,404 @@
+//! Runs jobs (e.g., generating statements) in the background on a schedule.
+
+mod budgets;
+mod changes;
+mod provisioning;
+mod reservations;
+mod schedule;
+mod statements;
+
+use std::{
+    collections::HashSet,
+    error, fmt,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use axum::response::{IntoResponse, Response};
+use futures::TryStreamExt;
+use mongodb::bson::{doc, oid::ObjectId};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{api::error::ApiError, audit, AppState};
+
+pub use budgets::CheckBudgets;
+pub use changes::{DeliverWebhooks, DetectChanges};
+pub use provisioning::CheckProvisioning;
+pub use reservations::WarmReservationCache;
+pub use schedule::Schedule;
+pub use statements::GenerateStatements;
+
+/// Work that runs periodically in the background.
+#[async_trait]
+pub(crate) trait Job: Send + Sync {
+    /// The name used to identify the job (e.g., `generate_statements`).
+    fn name(&self) -> &'static str;
+
+    fn schedule(&self) -> Schedule;
+
+    /// Run the job, returning a short description of what was done.
+    async fn run(&self, state: &AppState) -> Result<String, JobError>;
+}
+
+/// What caused a job to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobTrigger {
+    Scheduled,
+    Manual,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// A single run of a job.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct JobRun {
+    pub id: String,
+    pub job: String,
+    pub trigger: JobTrigger,
+    pub status: JobStatus,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// How long the job took to run, in milliseconds.
+    pub duration_ms: Option<i64>,
+    /// A short description of what was done, if the job succeeded.
+    pub output: Option<String>,
+    /// The reason the job failed, if it did.
+    pub error: Option<String>,
+}
+
+/// Information about a job registered with the scheduler.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct JobInfo {
+    pub name: String,
+    pub schedule: String,
+    pub running: bool,
+    pub next_run_at: chrono::DateTime<chrono::Utc>,
+    pub last_run: Option<JobRun>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct JobRunDocument {
+    #[serde(rename = "_id")]
+    id: ObjectId,
+    job: String,
+    trigger: JobTrigger,
+    status: JobStatus,
+    started_at: chrono::DateTime<chrono::Utc>,
+    finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    duration_ms: Option<i64>,
+    output: Option<String>,
+    error: Option<String>,
+}
+
+impl From<JobRunDocument> for JobRun {
+    fn from(document: JobRunDocument) -> Self {
+        Self {
+            id: document.id.to_string(),
+            job: document.job,
+            trigger: document.trigger,
+            status: document.status,
+            started_at: document.started_at,
+            finished_at: document.finished_at,
+            duration_ms: document.duration_ms,
+            output: document.output,
+            error: document.error,
+        }
+    }
+}
+
+/// Keeps track of the jobs registered at startup, and makes sure the same
+/// job never runs more than once at a time.
+///
+/// Every run is recorded in the `job_run` collection.
+#[derive(Default)]
+pub struct Scheduler {
+    jobs: Vec<Arc<dyn Job>>,
+    running: Mutex<HashSet<&'static str>>,
+}
+
+impl Scheduler {
+    /// Add a job to the scheduler; this must be done before it is started.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the job's schedule is invalid (see [`Schedule::is_valid`]).
+    pub(crate) fn register(mut self, job: impl Job + 'static) -> Self {
+        let schedule = job.schedule();
+        assert!(
+            schedule.is_valid(),
+            "expected job {} to have a valid schedule, not {schedule}",
+            job.name()
+        );
+
+        self.jobs.push(Arc::new(job));
+        self
+    }
+
+    /// Run every job in the background whenever it is scheduled to.
+    pub(crate) fn start(self: &Arc<Self>, state: AppState) {
+        for job in &self.jobs {
+            let scheduler = Arc::clone(self);
+            let job = Arc::clone(job);
+            let state = state.clone();
+
+            tokio::spawn(async move {
+                loop {
+                    let now = chrono::Utc::now();
+                    let next = job.schedule().next_after(now);
+                    tokio::time::sleep((next - now).to_std().unwrap_or_default()).await;
+
+                    if let Err(err) = scheduler
+                        .run(job.name(), JobTrigger::Scheduled, &state)
+                        .await
+                    {
+                        tracing::error!(job = job.name(), "failed to run job: {err}");
+                    }
+                }
+            });
+        }
+    }
+
+    /// Run a job immediately and wait for it to finish.
+    async fn run(
+        &self,
+        name: &str,
+        trigger: JobTrigger,
+        state: &AppState,
+    ) -> Result<JobRun, JobError> {
+        let (job, document) = self.begin(name, trigger, &state.db).await?;
+        let result = execute(Arc::clone(&job), state.clone()).await;
+
+        self.finish(job.name(), document, result, &state.db).await
+    }
+
+    /// Start running a job in the background, returning as soon as the run
+    /// has been recorded.
+    pub(crate) async fn trigger(
+        self: &Arc<Self>,
+        name: &str,
+        state: &AppState,
+    ) -> Result<JobRun, JobError> {
+        let (job, document) = self.begin(name, JobTrigger::Manual, &state.db).await?;
+        let run = JobRun::from(document.clone());
+
+        let scheduler = Arc::clone(self);
+        let state = state.clone();
+        // Changes made by the job are attributed to whoever triggered it.
+        tokio::spawn(audit::in_current_context(async move {
+            let result = execute(Arc::clone(&job), state.clone()).await;
+            if let Err(err) = scheduler
+                .finish(job.name(), document, result, &state.db)
+                .await
+            {
+                tracing::error!(job = job.name(), "failed to record job run: {err}");
+            }
+        }));
+
+        Ok(run)
+    }
+
+    /// Get information about every registered job, including its last run.
+    pub async fn get_jobs(&self, database: &mongodb::Database) -> Result<Vec<JobInfo>, JobError> {
+        let now = chrono::Utc::now();
+        let mut jobs: Vec<JobInfo> = Vec::new();
+
+        for job in &self.jobs {
+            let last_run = database
+                .collection::<JobRunDocument>("job_run")
+                .find_one(doc! {"job": job.name()})
+                .sort(doc! {"_id": -1})
+                .await
+                .map_err(|err| JobError::RequestFailure(err.to_string()))?;
+
+            jobs.push(JobInfo {
+                name: job.name().to_string(),
+                schedule: job.schedule().to_string(),
+                running: self.running.lock().unwrap().contains(job.name()),
+                next_run_at: job.schedule().next_after(now),
+                last_run: last_run.map(JobRun::from),
+            });
+        }
+
+        Ok(jobs)
+    }
+
+    /// Get the most recent runs of a job (or of every job), newest first.
+    pub async fn get_runs(
+        &self,
+        name: Option<&str>,
+        limit: i64,
+        database: &mongodb::Database,
+    ) -> Result<Vec<JobRun>, JobError> {
+        let filter = match name {
+            Some(name) => doc! {"job": name},
+            None => doc! {},
+        };
+
+        let cursor = database
+            .collection::<JobRunDocument>("job_run")
+            .find(filter)
+            .sort(doc! {"_id": -1})
+            .limit(limit)
+            .await
+            .map_err(|err| JobError::RequestFailure(err.to_string()))?;
+
+        let documents = cursor
+            .try_collect::<Vec<JobRunDocument>>()
+            .await
+            .map_err(|err| JobError::RequestFailure(err.to_string()))?;
+
+        Ok(documents.into_iter().map(JobRun::from).collect())
+    }
+
+    /// Mark a job as running and record the start of the run.
+    async fn begin(
+        &self,
+        name: &str,
+        trigger: JobTrigger,
+        database: &mongodb::Database,
+    ) -> Result<(Arc<dyn Job>, JobRunDocument), JobError> {
+        let job = self
+            .jobs
+            .iter()
+            .find(|job| job.name() == name)
+            .cloned()
+            .ok_or_else(|| JobError::NotFound(name.to_string()))?;
+
+        if !self.running.lock().unwrap().insert(job.name()) {
+            return Err(JobError::AlreadyRunning(name.to_string()));
+        }
+
+        let document = JobRunDocument {
+            id: ObjectId::new(),
+            job: job.name().to_string(),
+            trigger,
+            status: JobStatus::Running,
+            started_at: chrono::Utc::now(),
+            finished_at: None,
+            duration_ms: None,
+            output: None,
+            error: None,
+        };
+
+        if let Err(err) = database
+            .collection::<JobRunDocument>("job_run")
+            .insert_one(&document)
+            .await
+        {
+            self.running.lock().unwrap().remove(job.name());
+            return Err(JobError::RequestFailure(err.to_string()));
+        }
+
+        Ok((job, document))
+    }
+
+    /// Record the outcome of a run and allow the job to run again.
+    async fn finish(
+        &self,
+        name: &'static str,
+        mut document: JobRunDocument,
+        result: Result<String, JobError>,
+        database: &mongodb::Database,
+    ) -> Result<JobRun, JobError> {
+        self.running.lock().unwrap().remove(name);
+
+        let finished_at = chrono::Utc::now();
+        document.finished_at = Some(finished_at);
+        document.duration_ms = Some((finished_at - document.started_at).num_milliseconds());
+
+        match result {
+            Ok(output) => {
+                document.status = JobStatus::Succeeded;
+                document.output = Some(output);
+            }
+            Err(err) => {
+                document.status = JobStatus::Failed;
+                document.error = Some(err.to_string());
+            }
+        }
+
+        database
+            .collection::<JobRunDocument>("job_run")
+            .replace_one(doc! {"_id": document.id}, &document)
+            .await
+            .map_err(|err| JobError::RequestFailure(err.to_string()))?;
+
+        Ok(document.into())
+    }
+}
+
+/// Run a job in its own task, so a job that panics is recorded as a failed
+/// run instead of stopping the scheduler.
+async fn execute(job: Arc<dyn Job>, state: AppState) -> Result<String, JobError> {
+    tokio::spawn(async move { job.run(&state).await })
+        .await
+        .unwrap_or_else(|err| Err(JobError::RequestFailure(format!("job panicked: {err}"))))
+}
+
+/// Create a client for reading the spreadsheets as the service account.
+fn get_sheets_client(state: &AppState) -> Result<sheets::Client, JobError> {
+    let service_account_key = state
+        .secrets
+        .get("SERVICE_ACCOUNT_KEY")
+        .ok_or_else(|| JobError::RequestFailure("SERVICE_ACCOUNT_KEY is not defined".into()))?;
+    let credentials: sheets::ServiceAccountKey = serde_json::from_str(&service_account_key)
+        .map_err(|err| JobError::RequestFailure(err.to_string()))?;
+
+    Ok(sheets::Client::new(
+        credentials,
+        sheets::Scope::SpreadsheetsReadOnly,
+    ))
+}
+
+/// An error occurred while trying to run a job.
+#[derive(Debug)]
+pub enum JobError {
+    /// An unexpected error occurred while running the job.
+    RequestFailure(String),
+    /// No job is registered with the name.
+    NotFound(String),
+    /// The job is already running; the same job never runs more than once at
+    /// a time.
+    AlreadyRunning(String),
+}
+
+impl error::Error for JobError {}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RequestFailure(reason) => write!(f, "{}", reason),
+            Self::NotFound(name) => write!(f, "no job named {name}"),
+            Self::AlreadyRunning(name) => write!(f, "job {name} is already running"),
+        }
+    }
+}
+
+impl From<JobError> for ApiError {
+    fn from(err: JobError) -> Self {
+        let (status, code) = match &err {
+            JobError::RequestFailure(..) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "job.request_failed")
+            }
+            JobError::NotFound(..) => (StatusCode::NOT_FOUND, "job.not_found"),
+            JobError::AlreadyRunning(..) => (StatusCode::CONFLICT, "job.already_running"),
+        };
+
+        Self::new(status, code, err)
+    }
+}
+
+impl IntoResponse for JobError {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
+    }
+}
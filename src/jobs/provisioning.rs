@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use chrono::Datelike;
+
+use crate::{api::service::get_provisioning_report_by_year, AppState};
+
+use super::{Job, JobError, Schedule};
+
+/// Checks that the next year's spreadsheets have been set up.
+///
+/// Runs on the 1st of December to leave a month to create anything that is
+/// missing; the job fails (and lists what is missing) until everything is.
+pub struct CheckProvisioning;
+
+#[async_trait]
+impl Job for CheckProvisioning {
+    fn name(&self) -> &'static str {
+        "check_provisioning"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::Yearly {
+            month: 12,
+            day: 1,
+            hour: 9,
+            minute: 0,
+        }
+    }
+
+    async fn run(&self, state: &AppState) -> Result<String, JobError> {
+        let year = chrono::Utc::now().year() + 1;
+
+        let report = get_provisioning_report_by_year(year, &state.db)
+            .await
+            .map_err(|err| JobError::RequestFailure(err.to_string()))?;
+
+        if report.is_complete() {
+            return Ok(format!("everything is set up for {year}"));
+        }
+
+        let mut missing: Vec<String> = Vec::new();
+        if !report.has_expense_sheet {
+            missing.push("the expense sheet".to_string());
+        }
+        for property in &report.missing_spreadsheets {
+            missing.push(format!("the spreadsheet for {}", property.name));
+        }
+
+        Err(JobError::RequestFailure(format!(
+            "missing for {year}: {}",
+            missing.join(", ")
+        )))
+    }
+}
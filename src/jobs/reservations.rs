@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use chrono::Datelike;
+
+use crate::{
+    api::service::{get_all_properties, refresh_reservation_cache},
+    AppState,
+};
+
+use super::{get_sheets_client, Job, JobError, Schedule};
+
+/// Reads the current month's reservations for every property into the cache
+/// every night, so the first requests of the day are fast.
+pub struct WarmReservationCache;
+
+#[async_trait]
+impl Job for WarmReservationCache {
+    fn name(&self) -> &'static str {
+        "warm_reservation_cache"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::Daily { hour: 3, minute: 0 }
+    }
+
+    async fn run(&self, state: &AppState) -> Result<String, JobError> {
+        let today = chrono::Utc::now().date_naive();
+        let sheets_client = get_sheets_client(state)?;
+
+        let properties = get_all_properties(&state.db)
+            .await
+            .map_err(|err| JobError::RequestFailure(err.to_string()))?;
+
+        let mut rows = 0;
+        let mut failures: Vec<String> = Vec::new();
+
+        for property in &properties {
+            match refresh_reservation_cache(
+                property,
+                today.year(),
+                today.month() as u8,
+                &state.db,
+                &sheets_client,
+            )
+            .await
+            {
+                Ok(count) => rows += count,
+                Err(err) => failures.push(format!("{}: {err}", property.name)),
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(JobError::RequestFailure(format!(
+                "failed to cache reservations for {} of {} properties; {}",
+                failures.len(),
+                properties.len(),
+                failures.join("; ")
+            )));
+        }
+
+        Ok(format!(
+            "cached {rows} rows for {} properties",
+            properties.len()
+        ))
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeDelta, Timelike, Utc};
+
+/// When a job runs; all times are in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    /// Every few minutes, counting from midnight (e.g., every 15 minutes runs
+    /// at 00:00, 00:15, 00:30, etc.).
+    Every { minutes: u32 },
+    /// Every day at the specified time.
+    Daily { hour: u32, minute: u32 },
+    /// Every month on the specified day (1 to 28) and time.
+    Monthly { day: u32, hour: u32, minute: u32 },
+    /// Every year on the specified month, day, and time.
+    Yearly {
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+    },
+}
+
+impl Schedule {
+    /// Check whether the schedule describes times that exist (e.g., not
+    /// every 0 minutes, or at 24:00).
+    pub fn is_valid(&self) -> bool {
+        let is_time = |hour: u32, minute: u32| hour < 24 && minute < 60;
+
+        match *self {
+            Self::Every { minutes } => (1..=24 * 60).contains(&minutes),
+            Self::Daily { hour, minute } => is_time(hour, minute),
+            Self::Monthly { day, hour, minute } => (1..=28).contains(&day) && is_time(hour, minute),
+            Self::Yearly {
+                month,
+                day,
+                hour,
+                minute,
+            } => {
+                // February 29 is rejected, since it only exists in leap years.
+                NaiveDate::from_ymd_opt(2023, month, day).is_some() && is_time(hour, minute)
+            }
+        }
+    }
+
+    /// Get the first time the job should run strictly after `after`.
+    ///
+    /// The schedule must be valid (see [`Schedule::is_valid`]).
+    pub fn next_after(&self, after: DateTime<Utc>) -> DateTime<Utc> {
+        let today = after.date_naive();
+
+        let (candidate, next) = match *self {
+            Self::Every { minutes } => {
+                let elapsed = after.hour() * 60 + after.minute();
+                let slot = (elapsed / minutes + 1) * minutes;
+                let candidate = if slot < 24 * 60 {
+                    at(today, slot / 60, slot % 60)
+                } else {
+                    at(today + TimeDelta::days(1), 0, 0)
+                };
+                (candidate, candidate)
+            }
+            Self::Daily { hour, minute } => {
+                let candidate = at(today, hour, minute);
+                (candidate, candidate + TimeDelta::days(1))
+            }
+            Self::Monthly { day, hour, minute } => {
+                let date = NaiveDate::from_ymd_opt(today.year(), today.month(), day)
+                    .expect("expected day to be within every month");
+                let candidate = at(date, hour, minute);
+                (candidate, candidate + chrono::Months::new(1))
+            }
+            Self::Yearly {
+                month,
+                day,
+                hour,
+                minute,
+            } => {
+                let date = NaiveDate::from_ymd_opt(today.year(), month, day)
+                    .expect("expected month and day to be valid");
+                let candidate = at(date, hour, minute);
+                (candidate, candidate + chrono::Months::new(12))
+            }
+        };
+
+        if candidate > after {
+            candidate
+        } else {
+            next
+        }
+    }
+}
+
+fn at(date: NaiveDate, hour: u32, minute: u32) -> DateTime<Utc> {
+    let time = NaiveTime::from_hms_opt(hour, minute, 0).expect("expected time to be valid");
+    date.and_time(time).and_utc()
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Every { minutes } => write!(f, "every {minutes} minutes"),
+            Self::Daily { hour, minute } => write!(f, "daily at {hour:02}:{minute:02} UTC"),
+            Self::Monthly { day, hour, minute } => {
+                write!(f, "monthly on day {day} at {hour:02}:{minute:02} UTC")
+            }
+            Self::Yearly {
+                month,
+                day,
+                hour,
+                minute,
+            } => write!(
+                f,
+                "yearly on {month:02}-{day:02} at {hour:02}:{minute:02} UTC"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn every_runs_on_the_next_slot() {
+        let schedule = Schedule::Every { minutes: 15 };
+
+        assert_eq!(
+            schedule.next_after(utc("2025-03-10T10:07:30Z")),
+            utc("2025-03-10T10:15:00Z")
+        );
+        // Runs strictly after the time, even when it is on a slot.
+        assert_eq!(
+            schedule.next_after(utc("2025-03-10T10:15:00Z")),
+            utc("2025-03-10T10:30:00Z")
+        );
+        assert_eq!(
+            schedule.next_after(utc("2025-03-10T23:50:00Z")),
+            utc("2025-03-11T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn every_restarts_at_midnight() {
+        // 1440 is not a multiple of 7, so the last slot of the day is 23:55.
+        let schedule = Schedule::Every { minutes: 7 };
+
+        assert_eq!(
+            schedule.next_after(utc("2025-03-10T23:56:00Z")),
+            utc("2025-03-11T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn daily_runs_today_or_tomorrow() {
+        let schedule = Schedule::Daily { hour: 3, minute: 0 };
+
+        assert_eq!(
+            schedule.next_after(utc("2025-03-10T02:59:59Z")),
+            utc("2025-03-10T03:00:00Z")
+        );
+        assert_eq!(
+            schedule.next_after(utc("2025-03-10T03:00:00Z")),
+            utc("2025-03-11T03:00:00Z")
+        );
+    }
+
+    #[test]
+    fn monthly_and_yearly_roll_over() {
+        let monthly = Schedule::Monthly {
+            day: 1,
+            hour: 6,
+            minute: 0,
+        };
+        assert_eq!(
+            monthly.next_after(utc("2025-12-15T00:00:00Z")),
+            utc("2026-01-01T06:00:00Z")
+        );
+
+        let yearly = Schedule::Yearly {
+            month: 12,
+            day: 1,
+            hour: 9,
+            minute: 30,
+        };
+        assert_eq!(
+            yearly.next_after(utc("2025-11-30T12:00:00Z")),
+            utc("2025-12-01T09:30:00Z")
+        );
+        assert_eq!(
+            yearly.next_after(utc("2025-12-01T09:30:00Z")),
+            utc("2026-12-01T09:30:00Z")
+        );
+    }
+
+    #[test]
+    fn invalid_schedules_are_rejected() {
+        assert!(!Schedule::Every { minutes: 0 }.is_valid());
+        assert!(!Schedule::Daily {
+            hour: 24,
+            minute: 0
+        }
+        .is_valid());
+        assert!(!Schedule::Monthly {
+            day: 31,
+            hour: 0,
+            minute: 0
+        }
+        .is_valid());
+        assert!(!Schedule::Yearly {
+            month: 2,
+            day: 29,
+            hour: 0,
+            minute: 0
+        }
+        .is_valid());
+
+        assert!(Schedule::Every { minutes: 1 }.is_valid());
+        assert!(Schedule::Yearly {
+            month: 12,
+            day: 31,
+            hour: 23,
+            minute: 59
+        }
+        .is_valid());
+    }
+}
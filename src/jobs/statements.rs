@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Datelike;
+
+use crate::{
+    api::{
+        model::User,
+        service::{generate_statement, get_all_properties, get_user_by_id, notify},
+    },
+    notifications::Notification,
+    AppState,
+};
+
+use super::{get_sheets_client, Job, JobError, Schedule};
+
+/// Generates the previous month's statement for every property on the 1st of
+/// each month, and lets the owners know they are ready.
+///
+/// Owners are only notified the first time a statement is generated, so the
+/// job can safely be run again (e.g., after correcting a spreadsheet).
+pub struct GenerateStatements;
+
+#[async_trait]
+impl Job for GenerateStatements {
+    fn name(&self) -> &'static str {
+        "generate_statements"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::Monthly {
+            day: 1,
+            hour: 6,
+            minute: 0,
+        }
+    }
+
+    async fn run(&self, state: &AppState) -> Result<String, JobError> {
+        let last_month = chrono::Utc::now().date_naive() - chrono::Months::new(1);
+        let (year, month) = (last_month.year(), last_month.month() as u8);
+
+        let secret_key = state
+            .secrets
+            .get("CLERK_SECRET_KEY")
+            .ok_or_else(|| JobError::RequestFailure("CLERK_SECRET_KEY is not defined".into()))?;
+        let sheets_client = get_sheets_client(state)?;
+
+        let properties = get_all_properties(&state.db)
+            .await
+            .map_err(|err| JobError::RequestFailure(err.to_string()))?;
+
+        // Most owners have more than one property, so avoid asking Clerk for
+        // the same user more than once.
+        let mut owners: HashMap<String, User> = HashMap::new();
+        let mut generated = 0;
+        let mut failures: Vec<String> = Vec::new();
+
+        for property in &properties {
+            let (statement, created) =
+                match generate_statement(property, year, month, &sheets_client, &state.db).await {
+                    Ok(result) => result,
+                    Err(err) => {
+                        failures.push(format!("{}: {err}", property.name));
+                        continue;
+                    }
+                };
+            generated += 1;
+
+            if !created {
+                continue;
+            }
+
+            if !owners.contains_key(&property.user_id) {
+                match get_user_by_id(&property.user_id, &secret_key).await {
+                    Ok(user) => owners.insert(property.user_id.to_string(), user),
+                    Err(err) => {
+                        failures.push(format!("{}: {err}", property.name));
+                        continue;
+                    }
+                };
+            }
+
+            let notification = Notification::StatementReady {
+                property_name: property.name.to_string(),
+                year,
+                month,
+                net_profit: statement.summary.net_profit,
+            };
+
+            if let Err(err) = notify(
+                &owners[&property.user_id],
+                &notification,
+                state.notifier.as_ref(),
+                &state.db,
+            )
+            .await
+            {
+                failures.push(format!("{}: {err}", property.name));
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(JobError::RequestFailure(format!(
+                "generated {generated} of {} statements for {year}-{month:02}; {}",
+                properties.len(),
+                failures.join("; ")
+            )));
+        }
+
+        Ok(format!(
+            "generated {generated} statements for {year}-{month:02}"
+        ))
+    }
+}
@@ -1,4 +1,14 @@
 mod api;
+mod audit;
+mod events;
+mod jobs;
+mod notifications;
+mod ocr;
+mod request_id;
+mod storage;
+mod webhooks;
+
+use std::sync::Arc;
 
 use axum::{
     extract::{FromRef, Request},
@@ -17,6 +27,10 @@ use tower_http::{normalize_path::NormalizePathLayer, services::ServeDir};
 struct AppState {
     secrets: SecretStore,
     db: mongodb::Database,
+    storage: Arc<dyn storage::Storage>,
+    notifier: Arc<dyn notifications::Notifier>,
+    scheduler: Arc<jobs::Scheduler>,
+    events: Arc<events::EventBus>,
 }
 
 /// The main entry point to the program.
@@ -41,11 +55,87 @@ async fn main(#[shuttle_runtime::Secrets] secrets: SecretStore) -> shuttle_axum:
         .await
         .expect("failed to ping the database");
 
-    let state = AppState { secrets, db };
+    api::service::create_indexes(&db)
+        .await
+        .expect("failed to create the database indexes");
+
+    // Uploaded files are stored in MongoDB by default, since the local
+    // filesystem does not persist between deployments.
+    let storage: Arc<dyn storage::Storage> = match secrets.get("STORAGE").as_deref() {
+        Some("local") => {
+            let path = secrets
+                .get("STORAGE_PATH")
+                .unwrap_or_else(|| "./uploads".to_string());
+            Arc::new(storage::LocalStorage::new(path))
+        }
+        Some("gridfs") | None => Arc::new(storage::GridFsStorage::new(&db, "uploads")),
+        Some(other) => panic!("expected 'STORAGE' to be 'local' or 'gridfs', got '{other}'"),
+    };
+
+    // Emails are only sent if an SMTP server is configured; otherwise, they
+    // are printed to the console (e.g., when running locally).
+    let notifier: Arc<dyn notifications::Notifier> = match secrets.get("SMTP_HOST") {
+        Some(host) => {
+            let security = match secrets.get("SMTP_SECURITY").as_deref() {
+                Some("none") => notifications::SmtpSecurity::None,
+                Some("starttls") | None => notifications::SmtpSecurity::StartTls,
+                Some("tls") => notifications::SmtpSecurity::Tls,
+                Some(other) => panic!(
+                    "expected 'SMTP_SECURITY' to be 'none', 'starttls', or 'tls', got '{other}'"
+                ),
+            };
+            let config = notifications::SmtpConfig {
+                host,
+                port: secrets
+                    .get("SMTP_PORT")
+                    .map(|port| port.parse().expect("expected 'SMTP_PORT' to be a port")),
+                username: secrets.get("SMTP_USERNAME"),
+                password: secrets.get("SMTP_PASSWORD"),
+                from: secrets
+                    .get("SMTP_FROM")
+                    .expect("expected 'SMTP_FROM' to be defined"),
+                security,
+            };
+            Arc::new(
+                notifications::SmtpNotifier::new(config)
+                    .expect("failed to configure the SMTP transport"),
+            )
+        }
+        None => Arc::new(notifications::ConsoleNotifier),
+    };
+
+    let scheduler = Arc::new(
+        jobs::Scheduler::default()
+            .register(jobs::WarmReservationCache)
+            .register(jobs::GenerateStatements)
+            .register(jobs::CheckProvisioning)
+            .register(jobs::DetectChanges)
+            .register(jobs::DeliverWebhooks)
+            .register(jobs::CheckBudgets),
+    );
+
+    let state = AppState {
+        secrets,
+        db,
+        storage,
+        notifier,
+        scheduler: Arc::clone(&scheduler),
+        events: Arc::new(events::EventBus::default()),
+    };
+
+    scheduler.start(state.clone());
 
     let router = Router::<AppState>::new()
         .route("/", get(serve_frontend))
-        .nest("/api", api::get_router())
+        .nest(
+            "/api",
+            api::get_router()
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    audit::middleware,
+                ))
+                .layer(middleware::from_fn(request_id::middleware)),
+        )
         .nest_service("/public", public)
         .with_state(state.into());
 
@@ -80,11 +170,3 @@ async fn set_static_cache_control(request: Request, next: middleware::Next) -> i
 
     response
 }
-
-macro_rules! http_error {
-    ($status_code:expr, $detail:expr) => {
-        axum::response::IntoResponse::into_response(($status_code, axum::Json(serde_json::json!({"detail": $detail}))))
-    };
-}
-
-pub(crate) use http_error;
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+
+use super::{Message, Notifier, NotifyError};
+
+/// Logs that a message would have been sent instead of delivering it.
+///
+/// Used when no other way of delivering messages has been configured, so the
+/// rest of the application does not have to check whether notifications are
+/// enabled. Only the kind and recipient are logged, since messages contain
+/// the owner's financial details.
+pub struct ConsoleNotifier;
+
+#[async_trait]
+impl Notifier for ConsoleNotifier {
+    async fn send(&self, message: &Message) -> Result<(), NotifyError> {
+        tracing::info!(
+            "not sending {:?} notification to {}; notifications are not configured",
+            message.kind,
+            message.to
+        );
+        Ok(())
+    }
+
+    fn delivers(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,55 @@
+//! Sends notifications (e.g., emails) to the owners of properties.
+
+mod console;
+mod smtp;
+mod templates;
+
+use std::{error, fmt};
+
+use async_trait::async_trait;
+
+pub use console::ConsoleNotifier;
+pub use smtp::{SmtpConfig, SmtpNotifier, SmtpSecurity};
+pub use templates::{Notification, NotificationKind};
+
+/// A message ready to be delivered to a single recipient.
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub kind: NotificationKind,
+    /// The address of the recipient (e.g., an email address).
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// A way of delivering messages to users.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn send(&self, message: &Message) -> Result<(), NotifyError>;
+
+    /// Whether sent messages actually reach their recipients. Messages sent
+    /// with a notifier that does not deliver them are recorded as skipped.
+    fn delivers(&self) -> bool {
+        true
+    }
+}
+
+/// An error occurred while trying to deliver a message.
+#[derive(Debug)]
+pub enum NotifyError {
+    /// The address of the sender or recipient is not valid.
+    InvalidAddress(String),
+    /// An unexpected error occurred while trying to deliver the message.
+    RequestFailure(String),
+}
+
+impl error::Error for NotifyError {}
+
+impl fmt::Display for NotifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidAddress(address) => write!(f, "invalid address: {address}"),
+            Self::RequestFailure(reason) => write!(f, "failed to send message: {}", reason),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use async_trait::async_trait;
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Tokio1Executor,
+};
+
+use super::{Message, Notifier, NotifyError};
+
+/// How the connection to the SMTP server is secured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    /// Do not encrypt the connection; only use this with a local SMTP sink
+    /// (e.g., for testing).
+    None,
+    /// Upgrade the connection using `STARTTLS` (usually port 587).
+    StartTls,
+    /// Use TLS for the entire connection (usually port 465).
+    Tls,
+}
+
+/// The information required to connect to an SMTP server.
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    /// Defaults to the usual port for the security method if not provided.
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// The address messages are sent from (e.g., `Bojano Homes <no-reply@...>`).
+    pub from: String,
+    pub security: SmtpSecurity,
+}
+
+/// Delivers messages as plain text emails through an SMTP server.
+pub struct SmtpNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpNotifier {
+    pub fn new(config: SmtpConfig) -> Result<Self, NotifyError> {
+        let from: Mailbox = config
+            .from
+            .parse()
+            .map_err(|_| NotifyError::InvalidAddress(config.from.to_string()))?;
+
+        let mut builder = match config.security {
+            SmtpSecurity::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+            }
+            SmtpSecurity::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                    .map_err(|err| NotifyError::RequestFailure(err.to_string()))?
+            }
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+                .map_err(|err| NotifyError::RequestFailure(err.to_string()))?,
+        };
+
+        if let Some(port) = config.port {
+            builder = builder.port(port);
+        }
+
+        if let (Some(username), Some(password)) = (config.username, config.password) {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for SmtpNotifier {
+    async fn send(&self, message: &Message) -> Result<(), NotifyError> {
+        let to: Mailbox = message
+            .to
+            .parse()
+            .map_err(|_| NotifyError::InvalidAddress(message.to.to_string()))?;
+
+        let email = lettre::Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(&message.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(message.body.to_string())
+            .map_err(|err| NotifyError::RequestFailure(err.to_string()))?;
+
+        self.transport
+            .send(email)
+            .await
+            .map_err(|err| NotifyError::RequestFailure(err.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::notifications::NotificationKind;
+
+    /// Accept a single connection and answer like an SMTP server, returning
+    /// the data of every message it received.
+    async fn run_sink(listener: TcpListener) -> Vec<String> {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut messages = Vec::new();
+
+        writer.write_all(b"220 sink ESMTP\r\n").await.unwrap();
+
+        while let Some(line) = lines.next_line().await.unwrap() {
+            let command = line.to_uppercase();
+            let reply: &[u8] = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                b"250 sink\r\n"
+            } else if command.starts_with("DATA") {
+                writer.write_all(b"354 end with .\r\n").await.unwrap();
+
+                let mut data = String::new();
+                while let Some(line) = lines.next_line().await.unwrap() {
+                    if line == "." {
+                        break;
+                    }
+                    data.push_str(&line);
+                    data.push('\n');
+                }
+                messages.push(data);
+
+                b"250 queued\r\n"
+            } else if command.starts_with("QUIT") {
+                writer.write_all(b"221 bye\r\n").await.unwrap();
+                break;
+            } else {
+                b"250 ok\r\n"
+            };
+
+            writer.write_all(reply).await.unwrap();
+        }
+
+        messages
+    }
+
+    #[tokio::test]
+    async fn messages_are_sent_as_plain_text() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let sink = tokio::spawn(run_sink(listener));
+
+        let notifier = SmtpNotifier::new(SmtpConfig {
+            host: "127.0.0.1".to_string(),
+            port: Some(port),
+            username: None,
+            password: None,
+            from: "Bojano Homes <no-reply@example.com>".to_string(),
+            security: SmtpSecurity::None,
+        })
+        .unwrap();
+
+        let message = Message {
+            kind: NotificationKind::StatementReady,
+            to: "owner@example.com".to_string(),
+            subject: "Your statement is ready".to_string(),
+            body: "Hi Sam,\n\nNet profit: $1.00\n".to_string(),
+        };
+        notifier.send(&message).await.unwrap();
+
+        // Close the pooled connection, so the sink stops.
+        drop(notifier);
+        let messages = sink.await.unwrap();
+
+        assert_eq!(messages.len(), 1);
+        let data = &messages[0];
+        assert!(
+            data.contains("From: \"Bojano Homes\" <no-reply@example.com>"),
+            "{data}"
+        );
+        assert!(data.contains("To: owner@example.com"), "{data}");
+        assert!(data.contains("Subject: Your statement is ready"), "{data}");
+        assert!(data.contains("Content-Type: text/plain"), "{data}");
+        assert!(data.contains("Net profit: $1.00"), "{data}");
+    }
+
+    #[tokio::test]
+    async fn invalid_recipients_are_rejected() {
+        let notifier = SmtpNotifier::new(SmtpConfig {
+            host: "127.0.0.1".to_string(),
+            port: Some(1),
+            username: None,
+            password: None,
+            from: "no-reply@example.com".to_string(),
+            security: SmtpSecurity::None,
+        })
+        .unwrap();
+
+        let message = Message {
+            kind: NotificationKind::StatementReady,
+            to: "not an address".to_string(),
+            subject: String::new(),
+            body: String::new(),
+        };
+        let err = notifier.send(&message).await.unwrap_err();
+        assert!(matches!(err, NotifyError::InvalidAddress(..)), "{err:?}");
+    }
+}
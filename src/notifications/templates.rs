@@ -0,0 +1,278 @@
+pub use models::NotificationKind;
+
+/// Something that happened that the owner of a property should know about.
+#[derive(Debug, Clone)]
+pub enum Notification {
+    /// The statement for a month is ready to be viewed.
+    StatementReady {
+        property_name: String,
+        year: i32,
+        month: u8,
+        net_profit: f32,
+    },
+    /// A new reservation was added to the spreadsheet.
+    NewReservation {
+        property_name: String,
+        platform: String,
+        check_in: chrono::NaiveDate,
+        check_out: chrono::NaiveDate,
+        revenue: f32,
+    },
+    /// An expense larger than the owner's threshold was added.
+    LargeExpense {
+        property_name: String,
+        amount: f32,
+        description: String,
+        merchant: String,
+        date: chrono::NaiveDate,
+    },
+    /// Spending reached one of a budget's thresholds.
+    BudgetOverrun {
+        property_name: String,
+        category: Option<String>,
+        period: String,
+        spent: f32,
+        amount: f32,
+        threshold: f32,
+    },
+}
+
+impl Notification {
+    /// Create a notification with made up details, used to check that
+    /// messages of a kind are delivered and rendered correctly.
+    pub fn sample(kind: NotificationKind) -> Self {
+        let property_name = "Sample Property".to_string();
+        let date = chrono::Utc::now().date_naive();
+
+        match kind {
+            NotificationKind::StatementReady => Self::StatementReady {
+                property_name,
+                year: 2025,
+                month: 1,
+                net_profit: 1234.56,
+            },
+            NotificationKind::NewReservation => Self::NewReservation {
+                property_name,
+                platform: "Airbnb".to_string(),
+                check_in: date,
+                check_out: date + chrono::Days::new(3),
+                revenue: 450.0,
+            },
+            NotificationKind::LargeExpense => Self::LargeExpense {
+                property_name,
+                amount: 789.0,
+                description: "Replaced water heater".to_string(),
+                merchant: "Home Depot".to_string(),
+                date,
+            },
+            NotificationKind::BudgetOverrun => Self::BudgetOverrun {
+                property_name,
+                category: Some("Maintenance".to_string()),
+                period: "2025-01".to_string(),
+                spent: 850.0,
+                amount: 1000.0,
+                threshold: 0.8,
+            },
+        }
+    }
+
+    pub fn kind(&self) -> NotificationKind {
+        match self {
+            Self::StatementReady { .. } => NotificationKind::StatementReady,
+            Self::NewReservation { .. } => NotificationKind::NewReservation,
+            Self::LargeExpense { .. } => NotificationKind::LargeExpense,
+            Self::BudgetOverrun { .. } => NotificationKind::BudgetOverrun,
+        }
+    }
+
+    /// Get the subject and body of the message sent to the recipient.
+    pub fn render(&self, recipient_name: &str) -> (String, String) {
+        let (subject, content) = match self {
+            Self::StatementReady {
+                property_name,
+                year,
+                month,
+                net_profit,
+            } => (
+                format!("Your {month:02}/{year} statement for {property_name} is ready"),
+                format!(
+                    "The statement for {property_name} for {month:02}/{year} is now \
+                     available on your dashboard.\n\nNet profit: {}",
+                    format_money(*net_profit)
+                ),
+            ),
+            Self::NewReservation {
+                property_name,
+                platform,
+                check_in,
+                check_out,
+                revenue,
+            } => (
+                format!("New reservation at {property_name}"),
+                format!(
+                    "A new reservation was booked at {property_name} through \
+                     {platform}.\n\nCheck-in: {check_in}\nCheck-out: {check_out}\n\
+                     Revenue: {}",
+                    format_money(*revenue)
+                ),
+            ),
+            Self::LargeExpense {
+                property_name,
+                amount,
+                description,
+                merchant,
+                date,
+            } => (
+                format!(
+                    "New expense of {} at {property_name}",
+                    format_money(*amount)
+                ),
+                format!(
+                    "An expense was recorded for {property_name}.\n\nAmount: {}\n\
+                     Description: {description}\nMerchant: {merchant}\nDate: {date}",
+                    format_money(*amount)
+                ),
+            ),
+            Self::BudgetOverrun {
+                property_name,
+                category,
+                period,
+                spent,
+                amount,
+                threshold,
+            } => {
+                let budget = match category {
+                    Some(category) => format!("{category} budget"),
+                    None => "budget".to_string(),
+                };
+                // Spending exactly the budget reaches it, but does not exceed it.
+                let status = if spent > amount {
+                    "has been exceeded".to_string()
+                } else {
+                    format!("has reached {:.0}%", threshold * 100.0)
+                };
+
+                (
+                    format!("The {budget} for {property_name} {status}"),
+                    format!(
+                        "The {budget} for {property_name} ({period}) {status}.\n\n\
+                         Spent: {}\nBudget: {}",
+                        format_money(*spent),
+                        format_money(*amount)
+                    ),
+                )
+            }
+        };
+
+        let body = format!(
+            "Hi {recipient_name},\n\n{content}\n\n\
+             You are receiving this email because of your notification \
+             preferences on the Bojano Homes homeowner dashboard.\n"
+        );
+
+        (subject, body)
+    }
+}
+
+fn format_money(amount: f32) -> String {
+    if amount < 0.0 {
+        format!("-${:.2}", amount.abs())
+    } else {
+        format!("${:.2}", amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget_overrun(spent: f32, threshold: f32) -> Notification {
+        Notification::BudgetOverrun {
+            property_name: "Lake House".to_string(),
+            category: Some("Maintenance".to_string()),
+            period: "2025-01".to_string(),
+            spent,
+            amount: 1000.0,
+            threshold,
+        }
+    }
+
+    #[test]
+    fn budgets_are_only_exceeded_when_spending_is_over() {
+        let (subject, _) = budget_overrun(800.0, 0.8).render("Sam");
+        assert_eq!(
+            subject,
+            "The Maintenance budget for Lake House has reached 80%"
+        );
+
+        let (subject, body) = budget_overrun(1000.0, 1.0).render("Sam");
+        assert_eq!(
+            subject,
+            "The Maintenance budget for Lake House has reached 100%"
+        );
+        assert!(body.contains("Spent: $1000.00\nBudget: $1000.00"), "{body}");
+
+        let (subject, _) = budget_overrun(1000.01, 1.0).render("Sam");
+        assert_eq!(
+            subject,
+            "The Maintenance budget for Lake House has been exceeded"
+        );
+    }
+
+    #[test]
+    fn messages_greet_the_recipient() {
+        for kind in [
+            NotificationKind::StatementReady,
+            NotificationKind::NewReservation,
+            NotificationKind::LargeExpense,
+            NotificationKind::BudgetOverrun,
+        ] {
+            let (subject, body) = Notification::sample(kind).render("Sam");
+
+            assert!(subject.contains("Sample Property"), "{subject}");
+            assert!(body.starts_with("Hi Sam,\n\n"), "{body}");
+            assert!(body.contains("notification preferences"), "{body}");
+        }
+    }
+
+    #[test]
+    fn reservations_and_expenses_include_their_details() {
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+
+        let (subject, body) = Notification::NewReservation {
+            property_name: "Lake House".to_string(),
+            platform: "airbnb".to_string(),
+            check_in: date,
+            check_out: date + chrono::Days::new(2),
+            revenue: 1234.5,
+        }
+        .render("Sam");
+        assert_eq!(subject, "New reservation at Lake House");
+        assert!(body.contains("through airbnb"), "{body}");
+        assert!(
+            body.contains("Check-in: 2025-01-31\nCheck-out: 2025-02-02"),
+            "{body}"
+        );
+        assert!(body.contains("Revenue: $1234.50"), "{body}");
+
+        let (subject, body) = Notification::LargeExpense {
+            property_name: "Lake House".to_string(),
+            amount: 789.0,
+            description: "Replaced water heater".to_string(),
+            merchant: "Home Depot".to_string(),
+            date,
+        }
+        .render("Sam");
+        assert_eq!(subject, "New expense of $789.00 at Lake House");
+        assert!(
+            body.contains("Merchant: Home Depot\nDate: 2025-01-31"),
+            "{body}"
+        );
+    }
+
+    #[test]
+    fn money_is_formatted_with_the_sign_first() {
+        assert_eq!(format_money(-12.5), "-$12.50");
+        assert_eq!(format_money(0.0), "$0.00");
+    }
+}
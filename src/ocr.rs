@@ -0,0 +1,199 @@
+//! Recognizes text in images using a local installation of Tesseract.
+//!
+//! Tesseract is run as a separate process (instead of being linked into the
+//! application) so that the backend can still be built on machines that do
+//! not have it installed. See: https://github.com/tesseract-ocr/tesseract
+
+use std::{collections::BTreeMap, error, fmt, io, process::Stdio, time::Duration};
+
+use tokio::{io::AsyncWriteExt, sync::Semaphore};
+
+/// A line of text recognized in an image.
+#[derive(Debug, Clone)]
+pub struct Line {
+    pub text: String,
+    /// How confident Tesseract is in the text (0.0 to 1.0).
+    pub confidence: f32,
+}
+
+/// The number of images Tesseract can recognize at the same time; each run
+/// uses a lot of memory and most of a CPU core.
+const MAX_CONCURRENT_RECOGNITIONS: usize = 2;
+
+/// How long recognizing an image can take, including waiting for other
+/// images to be recognized first.
+const RECOGNITION_TIMEOUT: Duration = Duration::from_secs(60);
+
+static RECOGNITIONS: Semaphore = Semaphore::const_new(MAX_CONCURRENT_RECOGNITIONS);
+
+/// Recognize the lines of text in an image, from top to bottom.
+pub async fn recognize(image: &[u8]) -> Result<Vec<Line>, OcrError> {
+    let recognition = async {
+        // The semaphore is never closed, so a permit is always acquired.
+        let _permit = RECOGNITIONS.acquire().await.unwrap();
+        run_tesseract(image).await
+    };
+
+    // If it takes too long, Tesseract is killed when the child is dropped.
+    tokio::time::timeout(RECOGNITION_TIMEOUT, recognition)
+        .await
+        .map_err(|_| OcrError::TimedOut)?
+}
+
+async fn run_tesseract(image: &[u8]) -> Result<Vec<Line>, OcrError> {
+    let mut child = tokio::process::Command::new("tesseract")
+        .args(["stdin", "stdout", "tsv"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => OcrError::Unavailable,
+            _ => OcrError::RequestFailure(err.to_string()),
+        })?;
+
+    // Tesseract does not start writing until it has read the entire image,
+    // so the image can be written before reading any of the output.
+    let mut stdin = child.stdin.take().expect("expected stdin to be piped");
+    stdin
+        .write_all(image)
+        .await
+        .map_err(|err| OcrError::RequestFailure(err.to_string()))?;
+    drop(stdin);
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|err| OcrError::RequestFailure(err.to_string()))?;
+
+    if !output.status.success() {
+        let reason = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(OcrError::RequestFailure(reason));
+    }
+
+    Ok(parse_tsv(&String::from_utf8_lossy(&output.stdout)))
+}
+
+type LineKey = (u32, u32, u32, u32);
+
+/// Group the words in Tesseract's TSV output into lines.
+///
+/// Each row of the output describes a page, block, paragraph, line or word;
+/// only words (level 5) contain text. The columns are: level, page_num,
+/// block_num, par_num, line_num, word_num, left, top, width, height, conf
+/// and text.
+fn parse_tsv(tsv: &str) -> Vec<Line> {
+    // Words are grouped by page, block, paragraph and line number.
+    let mut lines: BTreeMap<LineKey, Vec<(String, f32)>> = BTreeMap::new();
+
+    for row in tsv.lines().skip(1) {
+        let columns: Vec<&str> = row.split('\t').collect();
+
+        if columns.len() < 12 || columns[0] != "5" {
+            continue;
+        }
+
+        let text = columns[11].trim();
+        let confidence: f32 = columns[10].parse().unwrap_or(-1.0);
+
+        if text.is_empty() || confidence < 0.0 {
+            continue;
+        }
+
+        let parse = |value: &str| value.parse::<u32>().unwrap_or_default();
+        let key = (
+            parse(columns[1]),
+            parse(columns[2]),
+            parse(columns[3]),
+            parse(columns[4]),
+        );
+
+        lines
+            .entry(key)
+            .or_default()
+            .push((text.to_string(), confidence / 100.0));
+    }
+
+    lines
+        .into_values()
+        .map(|words| Line {
+            text: words
+                .iter()
+                .map(|(text, _)| text.as_str())
+                .collect::<Vec<&str>>()
+                .join(" "),
+            confidence: words.iter().map(|(_, c)| c).sum::<f32>() / words.len() as f32,
+        })
+        .collect()
+}
+
+/// An error occurred while trying to recognize text in an image.
+#[derive(Debug)]
+pub enum OcrError {
+    /// Tesseract is not installed (or is not on the `PATH`).
+    Unavailable,
+    /// Tesseract did not finish recognizing the image in time.
+    TimedOut,
+    /// An unexpected error occurred while running Tesseract.
+    RequestFailure(String),
+}
+
+impl error::Error for OcrError {}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable => write!(f, "text recognition is not available on this server"),
+            Self::TimedOut => write!(f, "text recognition took too long"),
+            Self::RequestFailure(reason) => write!(f, "failed to recognize text: {}", reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The output of `tesseract receipt.png stdout tsv` for a small receipt,
+    /// including the rows for the page, block, paragraph and lines.
+    const TSV: &str = "\
+level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext
+1\t1\t0\t0\t0\t0\t0\t0\t640\t480\t-1\t
+2\t1\t1\t0\t0\t0\t20\t20\t300\t80\t-1\t
+3\t1\t1\t1\t0\t0\t20\t20\t300\t80\t-1\t
+4\t1\t1\t1\t1\t0\t20\t20\t200\t30\t-1\t
+5\t1\t1\t1\t1\t1\t20\t20\t90\t30\t96.0\tCorner
+5\t1\t1\t1\t1\t2\t120\t20\t100\t30\t90.0\tHardware
+4\t1\t1\t1\t2\t0\t20\t60\t200\t30\t-1\t
+5\t1\t1\t1\t2\t1\t20\t60\t60\t30\t80.5\tTOTAL
+5\t1\t1\t1\t2\t2\t90\t60\t80\t30\t-1\t
+5\t1\t1\t1\t2\t3\t180\t60\t80\t30\t70.5\t$12.99
+5\t1\t2\t1\t1\t1\t20\t200\t80\t30\t88.0\t\x20
+";
+
+    #[test]
+    fn words_are_grouped_into_lines() {
+        let lines = parse_tsv(TSV);
+
+        let text: Vec<&str> = lines.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(text, ["Corner Hardware", "TOTAL $12.99"]);
+    }
+
+    #[test]
+    fn confidence_is_the_average_of_the_words() {
+        let lines = parse_tsv(TSV);
+
+        assert!((lines[0].confidence - 0.93).abs() < 1e-6);
+        // Words without a confidence (e.g., blank space) are skipped.
+        assert!((lines[1].confidence - 0.755).abs() < 1e-6);
+    }
+
+    #[test]
+    fn malformed_rows_are_skipped() {
+        let tsv = "level\tpage_num\n5\t1\t1\n5\t1\t1\t1\t1\t1\t0\t0\t0\t0\tbad\tword\n";
+
+        assert!(parse_tsv(tsv).is_empty());
+        assert!(parse_tsv("").is_empty());
+    }
+}
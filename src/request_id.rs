@@ -0,0 +1,46 @@
+//! Identifies every request made to the API, so an error reported by a user
+//! can be matched with what the server logged while handling it.
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+
+static HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// IDs provided by clients longer than this are replaced.
+const MAX_LENGTH: usize = 64;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Assign an ID to every request, and return it in the `X-Request-Id` header.
+///
+/// If the request already has an ID (e.g., assigned by a proxy), it is kept,
+/// so the request can be followed across services.
+pub async fn middleware(request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(&HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= MAX_LENGTH)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{:032x}", rand::random::<u128>()));
+
+    let mut response = REQUEST_ID.scope(id.clone(), next.run(request)).await;
+
+    // The ID is either generated or was already a valid header value.
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(HEADER.clone(), value);
+    }
+
+    response
+}
+
+/// Get the ID of the request being handled, if any.
+pub fn current() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
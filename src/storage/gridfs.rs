@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use futures::{AsyncReadExt, AsyncWriteExt};
+use mongodb::{
+    bson::Bson,
+    error::{ErrorKind, GridFsErrorKind},
+    gridfs::GridFsBucket,
+    options::GridFsBucketOptions,
+};
+
+use super::{Storage, StorageError};
+
+/// Stores files in MongoDB using GridFS.
+pub struct GridFsStorage {
+    bucket: GridFsBucket,
+}
+
+impl GridFsStorage {
+    pub fn new(database: &mongodb::Database, bucket_name: &str) -> Self {
+        let options = GridFsBucketOptions::builder()
+            .bucket_name(bucket_name.to_string())
+            .build();
+
+        Self {
+            bucket: database.gridfs_bucket(options),
+        }
+    }
+}
+
+#[async_trait]
+impl Storage for GridFsStorage {
+    async fn put(&self, key: &str, contents: Vec<u8>) -> Result<(), StorageError> {
+        // The key is used as the file's ID so it can be retrieved directly.
+        let mut stream = self
+            .bucket
+            .open_upload_stream(key)
+            .id(Bson::String(key.to_string()))
+            .await
+            .map_err(|err| StorageError::RequestFailure(err.to_string()))?;
+
+        stream
+            .write_all(&contents)
+            .await
+            .map_err(|err| StorageError::RequestFailure(err.to_string()))?;
+        stream
+            .close()
+            .await
+            .map_err(|err| StorageError::RequestFailure(err.to_string()))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        let mut stream = self
+            .bucket
+            .open_download_stream(Bson::String(key.to_string()))
+            .await
+            .map_err(|err| match *err.kind {
+                ErrorKind::GridFs(GridFsErrorKind::FileNotFound { .. }) => {
+                    StorageError::NotFound(key.to_string())
+                }
+                _ => StorageError::RequestFailure(err.to_string()),
+            })?;
+
+        let mut contents = Vec::new();
+        stream
+            .read_to_end(&mut contents)
+            .await
+            .map_err(|err| StorageError::RequestFailure(err.to_string()))?;
+
+        Ok(contents)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        self.bucket
+            .delete(Bson::String(key.to_string()))
+            .await
+            .map_err(|err| match *err.kind {
+                ErrorKind::GridFs(GridFsErrorKind::FileNotFound { .. }) => {
+                    StorageError::NotFound(key.to_string())
+                }
+                _ => StorageError::RequestFailure(err.to_string()),
+            })
+    }
+}
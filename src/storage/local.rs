@@ -0,0 +1,119 @@
+use std::{io, path};
+
+use async_trait::async_trait;
+
+use super::{Storage, StorageError};
+
+/// Stores files in a directory on the local filesystem.
+///
+/// Useful for local development; files stored on a deployed instance are
+/// lost whenever the application is redeployed.
+pub struct LocalStorage {
+    root: path::PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<path::PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn get_path(&self, key: &str) -> Result<path::PathBuf, StorageError> {
+        // Keys are used as file names; make sure they cannot be used to
+        // read or write files outside of the root directory.
+        let is_valid = !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+        if !is_valid {
+            return Err(StorageError::NotFound(key.to_string()));
+        }
+
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn put(&self, key: &str, contents: Vec<u8>) -> Result<(), StorageError> {
+        let path = self.get_path(key)?;
+
+        tokio::fs::create_dir_all(&self.root)
+            .await
+            .map_err(|err| StorageError::RequestFailure(err.to_string()))?;
+        tokio::fs::write(path, contents)
+            .await
+            .map_err(|err| StorageError::RequestFailure(err.to_string()))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        let path = self.get_path(key)?;
+
+        tokio::fs::read(path).await.map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => StorageError::NotFound(key.to_string()),
+            _ => StorageError::RequestFailure(err.to_string()),
+        })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let path = self.get_path(key)?;
+
+        tokio::fs::remove_file(path)
+            .await
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::NotFound => StorageError::NotFound(key.to_string()),
+                _ => StorageError::RequestFailure(err.to_string()),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_storage() -> LocalStorage {
+        let root = std::env::temp_dir().join(format!("storage-{}", rand::random::<u64>()));
+        LocalStorage::new(root)
+    }
+
+    #[test]
+    fn keys_cannot_leave_the_root() {
+        let storage = get_storage();
+
+        for key in ["../x", "a/b", "", "..", "a\\b", "/etc/passwd"] {
+            assert!(
+                matches!(storage.get_path(key), Err(StorageError::NotFound(..))),
+                "{key:?}"
+            );
+        }
+
+        assert_eq!(
+            storage.get_path("65a1f0c2-receipt_1").unwrap(),
+            storage.root.join("65a1f0c2-receipt_1")
+        );
+    }
+
+    #[tokio::test]
+    async fn files_are_stored_read_and_deleted() {
+        let storage = get_storage();
+
+        storage.put("receipt", b"contents".to_vec()).await.unwrap();
+        assert_eq!(storage.get("receipt").await.unwrap(), b"contents");
+
+        storage.delete("receipt").await.unwrap();
+        assert!(matches!(
+            storage.get("receipt").await,
+            Err(StorageError::NotFound(..))
+        ));
+        assert!(matches!(
+            storage.delete("receipt").await,
+            Err(StorageError::NotFound(..))
+        ));
+        assert!(matches!(
+            storage.put("../receipt", Vec::new()).await,
+            Err(StorageError::NotFound(..))
+        ));
+
+        tokio::fs::remove_dir_all(&storage.root).await.unwrap();
+    }
+}
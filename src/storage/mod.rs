@@ -0,0 +1,47 @@
+//! Implementations for storing uploaded files (e.g., receipts).
+
+mod gridfs;
+mod local;
+
+use std::{error, fmt};
+
+use async_trait::async_trait;
+
+pub use gridfs::GridFsStorage;
+pub use local::LocalStorage;
+
+/// A place to store the contents of uploaded files.
+///
+/// Only the contents of the file are stored; information about the file
+/// (e.g., its content type) is expected to be stored by the caller.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    /// Store the contents of a file under the specified key.
+    async fn put(&self, key: &str, contents: Vec<u8>) -> Result<(), StorageError>;
+
+    /// Get the contents of the file stored under the specified key.
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError>;
+
+    /// Delete the file stored under the specified key.
+    async fn delete(&self, key: &str) -> Result<(), StorageError>;
+}
+
+/// An error occurred while trying to store or retrieve a file.
+#[derive(Debug)]
+pub enum StorageError {
+    /// No file is stored under the key.
+    NotFound(String),
+    /// An unexpected error occurred while trying to read or write the file.
+    RequestFailure(String),
+}
+
+impl error::Error for StorageError {}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(key) => write!(f, "no file stored with key {key}"),
+            Self::RequestFailure(reason) => write!(f, "{}", reason),
+        }
+    }
+}
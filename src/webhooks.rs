@@ -0,0 +1,113 @@
+//! Delivers events to outgoing webhooks.
+//!
+//! Every request is signed so receivers can verify it came from us: the
+//! `X-Webhook-Signature` header contains `sha256=` followed by the hex encoded
+//! HMAC-SHA256 of `{timestamp}.{body}`, using the webhook's secret as the key
+//! and the value of the `X-Webhook-Timestamp` header as the timestamp.
+
+use std::{error, fmt, time::Duration};
+
+use hmac::{Hmac, Mac};
+use rand::{distributions::Alphanumeric, Rng};
+use reqwest::{header, StatusCode};
+use sha2::Sha256;
+
+/// How long to wait for a receiver to respond before giving up.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Create a new random secret for signing deliveries.
+pub fn generate_secret() -> String {
+    let secret: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect();
+
+    format!("whsec_{secret}")
+}
+
+/// Get the signature of a delivery.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    // HMAC accepts keys of any length.
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(format!("{timestamp}.{body}").as_bytes());
+
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Send an event (serialized as JSON) to a webhook.
+pub async fn send(url: &str, secret: &str, event_id: &str, body: String) -> Result<(), SendError> {
+    let timestamp = chrono::Utc::now().timestamp();
+    let signature = sign(secret, timestamp, &body);
+
+    let response = reqwest::Client::new()
+        .post(url)
+        .timeout(TIMEOUT)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("X-Webhook-Id", event_id)
+        .header("X-Webhook-Timestamp", timestamp.to_string())
+        .header("X-Webhook-Signature", signature)
+        .body(body)
+        .send()
+        .await
+        .map_err(|err| SendError::RequestFailure(err.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(SendError::Rejected(response.status()));
+    }
+
+    Ok(())
+}
+
+/// An error occurred while trying to send an event to a webhook.
+#[derive(Debug)]
+pub enum SendError {
+    /// The receiver responded with an unsuccessful status code.
+    Rejected(StatusCode),
+    /// The request could not be sent, or timed out.
+    RequestFailure(String),
+}
+
+impl error::Error for SendError {}
+
+impl fmt::Display for SendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rejected(status) => write!(f, "webhook responded with {status}"),
+            Self::RequestFailure(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signatures_match_a_known_vector() {
+        let body = r#"{"kind":"reservation_added"}"#;
+
+        assert_eq!(
+            sign("whsec_test", 1700000000, body),
+            "sha256=2651583fd0bdbc8b23c5b04315a6f6be8fc927c2c59e0293b0dc53ea1d7c27c8"
+        );
+    }
+
+    #[test]
+    fn signatures_cover_the_secret_timestamp_and_body() {
+        let signature = sign("whsec_test", 1700000000, "{}");
+
+        assert_ne!(sign("whsec_other", 1700000000, "{}"), signature);
+        assert_ne!(sign("whsec_test", 1700000001, "{}"), signature);
+        assert_ne!(sign("whsec_test", 1700000000, "[]"), signature);
+    }
+
+    #[test]
+    fn secrets_are_random() {
+        let secret = generate_secret();
+
+        assert!(secret.starts_with("whsec_"));
+        assert_eq!(secret.len(), "whsec_".len() + 32);
+        assert_ne!(secret, generate_secret());
+    }
+}